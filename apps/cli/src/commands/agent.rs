@@ -1,16 +1,47 @@
 //! Agent command - manage agent configurations
 
-use anyhow::{Result, bail};
-use std::path::PathBuf;
+use anyhow::{Context, Result, bail};
 
-use super::core::config::{AgentConfig, Config};
+use super::core::config::{AgentConfig, Config, PortableConfig};
+use super::core::paths::expand_path;
 
 pub enum AgentCommand {
     List,
-    Add { name: String, dir: String },
-    Remove { name: String },
-    Default { name: String },
-    Show { name: Option<String> },
+    Add {
+        name: String,
+        dir: String,
+    },
+    Remove {
+        name: String,
+    },
+    Default {
+        name: String,
+    },
+    Set {
+        name: String,
+        dir: String,
+    },
+    Rename {
+        old: String,
+        new: String,
+    },
+    /// Write custom agents and registries to `file`, or stdout
+    Export {
+        file: Option<String>,
+    },
+    /// Merge agents and registries from `file` into the config
+    Import {
+        file: String,
+        force: bool,
+    },
+    /// Show the install fallback order, or replace it with `names`
+    DefaultOrder {
+        names: Vec<String>,
+        clear: bool,
+    },
+    Show {
+        name: Option<String>,
+    },
 }
 
 pub async fn run(cmd: AgentCommand) -> Result<()> {
@@ -52,7 +83,7 @@ pub async fn run(cmd: AgentCommand) -> Result<()> {
             }
 
             // Expand path
-            let skills_dir: PathBuf = shellexpand::tilde(&dir).to_string().into();
+            let skills_dir = expand_path(&dir);
 
             // Create the agent config
             let agent_config = AgentConfig {
@@ -76,18 +107,7 @@ pub async fn run(cmd: AgentCommand) -> Result<()> {
 
         AgentCommand::Remove { name } => {
             // Check if it's a built-in agent
-            let builtins = [
-                "stakpak",
-                "claude-code",
-                "cursor",
-                "vscode",
-                "copilot",
-                "goose",
-                "opencode",
-                "amp",
-                "codex",
-            ];
-            if builtins.contains(&name.as_str()) {
+            if Config::is_builtin_agent(&name) {
                 bail!(
                     "Cannot remove built-in agent '{}'. Built-in agents are always available.",
                     name
@@ -104,6 +124,7 @@ pub async fn run(cmd: AgentCommand) -> Result<()> {
             if config.default_agent.as_ref() == Some(&name) {
                 config.default_agent = None;
             }
+            config.install_fallback.retain(|agent| agent != &name);
 
             config.save()?;
             println!("✓ Removed agent '{}'", name);
@@ -120,6 +141,114 @@ pub async fn run(cmd: AgentCommand) -> Result<()> {
             println!("✓ Default agent set to '{}'", name);
         }
 
+        AgentCommand::Set { name, dir } => {
+            let skills_dir = expand_path(&dir);
+            config.set_agent_dir(&name, skills_dir.clone())?;
+            config.save()?;
+
+            println!("✓ Agent '{}' now uses {}", name, skills_dir.display());
+
+            // Create directory if it doesn't exist
+            if !skills_dir.exists() {
+                std::fs::create_dir_all(&skills_dir)?;
+                println!("  Created directory: {}", skills_dir.display());
+            }
+        }
+
+        AgentCommand::Rename { old, new } => {
+            config.rename_agent(&old, &new)?;
+            config.save()?;
+
+            println!("✓ Renamed agent '{}' to '{}'", old, new);
+        }
+
+        AgentCommand::Export { file } => {
+            let exported = config.export();
+            match file {
+                Some(file) => {
+                    let path = expand_path(&file);
+                    std::fs::write(&path, exported.render(Some(&path))?)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    println!(
+                        "✓ Exported {} agent(s) and {} registr{} to {}",
+                        exported.agents.len(),
+                        exported.registries.len(),
+                        if exported.registries.len() == 1 {
+                            "y"
+                        } else {
+                            "ies"
+                        },
+                        path.display()
+                    );
+                }
+                None => print!("{}", exported.render(None)?),
+            }
+        }
+
+        AgentCommand::Import { file, force } => {
+            let portable = PortableConfig::read(&expand_path(&file))?;
+            let summary = config.import(portable, force);
+            config.save()?;
+
+            for entry in &summary.added {
+                println!("✓ Added {}", entry);
+            }
+            for entry in &summary.replaced {
+                println!("✓ Replaced {}", entry);
+            }
+            for entry in &summary.skipped {
+                println!(
+                    "  Skipped {} (already configured, use --force to replace)",
+                    entry
+                );
+            }
+            if summary.added.is_empty() && summary.replaced.is_empty() && summary.skipped.is_empty()
+            {
+                println!("Nothing to import");
+            }
+        }
+
+        AgentCommand::DefaultOrder { names, clear } => {
+            if clear {
+                config.install_fallback.clear();
+                config.save()?;
+                println!("✓ Cleared install fallback order");
+            } else if names.is_empty() {
+                if config.install_fallback.is_empty() {
+                    println!("No install fallback order set");
+                } else {
+                    println!("Install fallback order:");
+                    for (i, name) in config.install_fallback.iter().enumerate() {
+                        let exists = config
+                            .get_agent(name)
+                            .is_some_and(|agent| agent.skills_dir.is_dir());
+                        let marker = if exists { "" } else { " (no skills directory)" };
+                        println!("  {}. {}{}", i + 1, name, marker);
+                    }
+                }
+                match config.default_agent_id() {
+                    Some(agent) => println!("\nInstalls without --agent go to '{}'", agent),
+                    None => println!(
+                        "\nInstalls without --agent go to {}",
+                        Config::default_skills_dir().display()
+                    ),
+                }
+            } else {
+                if let Some(unknown) = names.iter().find(|n| !config.agents.contains_key(*n)) {
+                    bail!("Agent '{}' not found", unknown);
+                }
+                config.install_fallback = names;
+                config.save()?;
+                println!(
+                    "✓ Install fallback order set to {}",
+                    config.install_fallback.join(", ")
+                );
+                if config.default_agent.is_some() {
+                    println!("  (only used while no default agent is set)");
+                }
+            }
+        }
+
         AgentCommand::Show { name } => {
             if let Some(agent_name) = name {
                 if let Some(agent) = config.get_agent(&agent_name) {
@@ -0,0 +1,29 @@
+//! Cache command - manage the local registry metadata and clone caches
+
+use anyhow::Result;
+
+use super::core::cache::{self, CloneCache, MetadataCache};
+
+pub enum CacheCommand {
+    Clear,
+}
+
+pub async fn run(cmd: CacheCommand) -> Result<()> {
+    match cmd {
+        CacheCommand::Clear => {
+            let removed = cache::clear(&MetadataCache::default_dir()?)?;
+            println!(
+                "✓ Cleared {} cached {}",
+                removed,
+                if removed == 1 { "entry" } else { "entries" }
+            );
+            let clones = cache::clear_clones(&CloneCache::default_dir()?)?;
+            println!(
+                "✓ Cleared {} cached {}",
+                clones,
+                if clones == 1 { "clone" } else { "clones" }
+            );
+        }
+    }
+    Ok(())
+}
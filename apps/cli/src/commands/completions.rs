@@ -0,0 +1,25 @@
+//! Completions command - print a shell completion script
+//!
+//! Completes subcommands, flags and enumerated values such as agent names.
+
+use anyhow::Result;
+use clap::Command;
+use clap_complete::Shell;
+use std::io::{self, Write};
+
+pub fn run(shell: Shell, command: Command) -> Result<()> {
+    // clap_complete panics on write errors, so render into a buffer first
+    let mut script = Vec::new();
+    write_completions(shell, command, &mut script);
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(&script)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Write the completion script for `command` in `shell`'s syntax to `out`
+pub fn write_completions(shell: Shell, mut command: Command, out: &mut impl Write) {
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
@@ -0,0 +1,83 @@
+//! Unpacking pak archives downloaded from the registry
+//!
+//! Archives are gzipped tarballs of the skill directory. Its files may sit at
+//! the top level or under a single directory, as in the tarballs git hosts
+//! produce.
+
+use anyhow::{Context, Result, bail};
+use flate2::read::GzDecoder;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Unpack a `.tar.gz` `archive` into `dir`, returning the skill's root
+pub fn unpack(archive: impl Read, dir: &Path) -> Result<PathBuf> {
+    let mut tar = tar::Archive::new(GzDecoder::new(archive));
+    for entry in tar.entries().context("Failed to read pak archive")? {
+        let mut entry = entry.context("Failed to read pak archive")?;
+        // `unpack_in` refuses entries that would land outside `dir`
+        if !entry.unpack_in(dir)? {
+            bail!(
+                "Pak archive contains an unsafe path: {}",
+                entry.path()?.display()
+            );
+        }
+    }
+    skill_root(dir)
+}
+
+/// The directory holding SKILL.md: `dir`, or its only subdirectory
+fn skill_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join("SKILL.md").is_file() {
+        return Ok(dir.to_path_buf());
+    }
+    let entries: Vec<_> = std::fs::read_dir(dir)?.collect::<std::io::Result<_>>()?;
+    if let [entry] = entries.as_slice()
+        && entry.path().join("SKILL.md").is_file()
+    {
+        return Ok(entry.path());
+    }
+    bail!("Pak archive has no SKILL.md")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    fn tarball(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (path, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn test_unpack_finds_skill_root() {
+        let flat = tarball(&[
+            ("SKILL.md", "---\nname: demo\n---\n"),
+            ("scripts/run.sh", ""),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let root = unpack(flat.as_slice(), dir.path()).unwrap();
+        assert_eq!(root, dir.path());
+        assert!(root.join("scripts/run.sh").is_file());
+
+        let nested = tarball(&[("demo-1.0.0/SKILL.md", "---\nname: demo\n---\n")]);
+        let dir = tempfile::tempdir().unwrap();
+        let root = unpack(nested.as_slice(), dir.path()).unwrap();
+        assert_eq!(root, dir.path().join("demo-1.0.0"));
+
+        let empty = tarball(&[("README.md", "no skill here")]);
+        let dir = tempfile::tempdir().unwrap();
+        let err = unpack(empty.as_slice(), dir.path()).unwrap_err();
+        assert!(err.to_string().contains("no SKILL.md"));
+    }
+}
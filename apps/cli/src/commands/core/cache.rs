@@ -0,0 +1,559 @@
+//! On-disk caches for registry metadata and git clones
+//!
+//! Responses are stored as JSON files under `~/.paks/cache/metadata`, keyed by
+//! the registry URL, a namespace (e.g. `search`) and the request key. Entries
+//! expire after a short TTL.
+//!
+//! Clones are kept under `~/.paks/cache/clones`, keyed by `clone_url@ref`, and
+//! reused for as long as the remote ref still points at the cached commit.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::git;
+
+/// Default time-to-live for cached responses
+pub const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Source of the current time, injectable for tests
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// Wall clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A cached response as stored on disk
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// Registry the response came from
+    registry: String,
+    /// Namespace and key the entry was stored under
+    key: String,
+    /// Seconds since the Unix epoch when the entry was written
+    stored_at: u64,
+    value: serde_json::Value,
+}
+
+/// Short-lived cache of registry responses
+pub struct MetadataCache<C: Clock = SystemClock> {
+    dir: PathBuf,
+    registry: String,
+    ttl: Duration,
+    clock: C,
+}
+
+impl MetadataCache {
+    /// Cache for `registry` in the default location
+    pub fn new(registry: &str, ttl: Duration) -> Result<Self> {
+        Ok(Self::with_clock(
+            Self::default_dir()?,
+            registry,
+            ttl,
+            SystemClock,
+        ))
+    }
+
+    /// Default cache directory (~/.paks/cache/metadata)
+    pub fn default_dir() -> Result<PathBuf> {
+        Ok(dirs::home_dir()
+            .context("Could not determine home directory")?
+            .join(".paks")
+            .join("cache")
+            .join("metadata"))
+    }
+}
+
+impl<C: Clock> MetadataCache<C> {
+    /// Cache in `dir` using a custom clock
+    pub fn with_clock(dir: PathBuf, registry: &str, ttl: Duration, clock: C) -> Self {
+        Self {
+            dir,
+            registry: registry.trim_end_matches('/').to_string(),
+            ttl,
+            clock,
+        }
+    }
+
+    /// Look up a fresh entry; missing, expired or unreadable entries are misses
+    pub fn get<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<T> {
+        let entry = self.read_entry(namespace, key)?;
+        let age = self.now_secs().checked_sub(entry.stored_at)?;
+        if age >= self.ttl.as_secs() {
+            return None;
+        }
+
+        serde_json::from_value(entry.value).ok()
+    }
+
+    /// Look up an entry however old it is, for when the registry can't be asked
+    pub fn get_stale<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<T> {
+        serde_json::from_value(self.read_entry(namespace, key)?.value).ok()
+    }
+
+    fn read_entry(&self, namespace: &str, key: &str) -> Option<CacheEntry> {
+        let full_key = format!("{}:{}", namespace, key);
+        let content = std::fs::read_to_string(self.entry_path(&full_key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+
+        // Guard against hash collisions and entries from another registry
+        if entry.registry != self.registry || entry.key != full_key {
+            return None;
+        }
+        Some(entry)
+    }
+
+    /// Store a response
+    pub fn put<T: Serialize>(&self, namespace: &str, key: &str, value: &T) -> Result<()> {
+        let full_key = format!("{}:{}", namespace, key);
+        let entry = CacheEntry {
+            registry: self.registry.clone(),
+            key: full_key.clone(),
+            stored_at: self.now_secs(),
+            value: serde_json::to_value(value)?,
+        };
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+
+        // Write then rename so readers never see a partial entry
+        let path = self.entry_path(&full_key);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&entry)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(())
+    }
+
+    fn entry_path(&self, full_key: &str) -> PathBuf {
+        let hash = fnv1a(&format!("{}\0{}", self.registry, full_key));
+        self.dir.join(format!("{:016x}.json", hash))
+    }
+
+    fn now_secs(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+}
+
+/// Remove every cached entry in `dir`, returning how many were removed
+pub fn clear(dir: &Path) -> Result<usize> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json" || e == "tmp") {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// A checkout served by [`CloneCache::checkout`]
+///
+/// The entry stays locked until this is dropped, so another install can't
+/// replace it while its files are being copied.
+#[derive(Debug)]
+pub struct CachedCheckout {
+    pub path: PathBuf,
+    /// Whether an existing clone was reused rather than cloned afresh
+    pub reused: bool,
+    _lock: File,
+}
+
+/// Cache of git clones, so repeated or retried installs skip the clone
+#[derive(Debug, Clone)]
+pub struct CloneCache {
+    dir: PathBuf,
+}
+
+impl CloneCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Default clone cache directory (~/.paks/cache/clones)
+    pub fn default_dir() -> Result<PathBuf> {
+        Ok(dirs::home_dir()
+            .context("Could not determine home directory")?
+            .join(".paks")
+            .join("cache")
+            .join("clones"))
+    }
+
+    /// Checkout of `url` at `git_ref`, calling `clone` to fill the entry on a miss
+    ///
+    /// `subpath` is part of the key since sparse clones only contain that
+    /// path. A cached clone is reused if it is at `commit`, when the caller
+    /// knows which commit `git_ref` should be at (as the registry does).
+    /// Otherwise it is reused if the remote still resolves `git_ref` to the
+    /// cached commit, or if the remote can't be reached. A stale entry is
+    /// replaced.
+    ///
+    /// Each entry has a lockfile next to it, held while the entry is checked,
+    /// filled, and used, so concurrent installs never see a half-written clone.
+    /// Waiting for the lock blocks the thread, so async callers should run
+    /// this on a blocking thread.
+    pub fn checkout(
+        &self,
+        url: &str,
+        git_ref: Option<&str>,
+        commit: Option<&str>,
+        subpath: Option<&str>,
+        clone: impl FnOnce(&Path) -> Result<()>,
+    ) -> Result<CachedCheckout> {
+        let (entry, lock) = self.lock_entry(url, git_ref, subpath)?;
+
+        if entry.join(".git").exists() {
+            if clone_is_current(&entry, url, git_ref, commit) {
+                return Ok(CachedCheckout {
+                    path: entry,
+                    reused: true,
+                    _lock: lock,
+                });
+            }
+            std::fs::remove_dir_all(&entry)
+                .with_context(|| format!("Failed to remove {}", entry.display()))?;
+        }
+
+        // Clone next to the entry and move it into place, so a failed clone
+        // never leaves a partial entry behind
+        let partial = tempfile::tempdir_in(&self.dir)?;
+        clone(partial.path())?;
+        std::fs::rename(partial.keep(), &entry)
+            .with_context(|| format!("Failed to store clone in {}", entry.display()))?;
+
+        Ok(CachedCheckout {
+            path: entry,
+            reused: false,
+            _lock: lock,
+        })
+    }
+
+    /// The cached checkout of `url` at `git_ref`, without touching the network
+    ///
+    /// Returns `None` if nothing is cached, or if `commit` is given and the
+    /// cached clone is at a different one.
+    pub fn cached(
+        &self,
+        url: &str,
+        git_ref: Option<&str>,
+        commit: Option<&str>,
+        subpath: Option<&str>,
+    ) -> Result<Option<CachedCheckout>> {
+        let (entry, lock) = self.lock_entry(url, git_ref, subpath)?;
+        if !entry.join(".git").exists() {
+            return Ok(None);
+        }
+        if let Some(commit) = commit
+            && git::git_cmd(&["rev-parse", "HEAD"], &entry).ok().as_deref() != Some(commit)
+        {
+            return Ok(None);
+        }
+        Ok(Some(CachedCheckout {
+            path: entry,
+            reused: true,
+            _lock: lock,
+        }))
+    }
+
+    /// Path of the entry for `url@git_ref:subpath`, with its lock held
+    fn lock_entry(
+        &self,
+        url: &str,
+        git_ref: Option<&str>,
+        subpath: Option<&str>,
+    ) -> Result<(PathBuf, File)> {
+        let key = format!(
+            "{}@{}:{}",
+            url,
+            git_ref.unwrap_or("HEAD"),
+            subpath.unwrap_or_default()
+        );
+        let hash = format!("{:016x}", fnv1a(&key));
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let lock_path = self.dir.join(format!("{}.lock", hash));
+        let lock = File::create(&lock_path)
+            .with_context(|| format!("Failed to create {}", lock_path.display()))?;
+        lock.lock()
+            .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+
+        Ok((self.dir.join(hash), lock))
+    }
+}
+
+/// Whether the clone in `entry` is at `commit`, or else at the commit the
+/// remote resolves `git_ref` to
+fn clone_is_current(entry: &Path, url: &str, git_ref: Option<&str>, commit: Option<&str>) -> bool {
+    let Ok(head) = git::git_cmd(&["rev-parse", "HEAD"], entry) else {
+        return false;
+    };
+    if let Some(commit) = commit {
+        return head == commit;
+    }
+    match git::remote_commit(url, git_ref) {
+        Some(remote) => remote == head,
+        // Offline: the cached clone is the best we have
+        None => true,
+    }
+}
+
+/// Remove every cached clone in `dir`, returning how many were removed
+pub fn clear_clones(dir: &Path) -> Result<usize> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// 64-bit FNV-1a, stable across runs and Rust versions
+fn fnv1a(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tempfile::tempdir;
+
+    /// Clock that only moves when told to
+    struct FakeClock(Cell<SystemTime>);
+
+    impl FakeClock {
+        fn new() -> Self {
+            Self(Cell::new(UNIX_EPOCH + Duration::from_secs(1_700_000_000)))
+        }
+
+        fn advance(&self, by: Duration) {
+            self.0.set(self.0.get() + by);
+        }
+    }
+
+    impl Clock for &FakeClock {
+        fn now(&self) -> SystemTime {
+            self.0.get()
+        }
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let dir = tempdir().unwrap();
+        let clock = FakeClock::new();
+        let cache = MetadataCache::with_clock(
+            dir.path().to_path_buf(),
+            "https://a.dev",
+            DEFAULT_TTL,
+            &clock,
+        );
+
+        assert_eq!(cache.get::<Vec<String>>("search", "kubernetes"), None);
+
+        cache
+            .put("search", "kubernetes", &vec!["acme/k8s".to_string()])
+            .unwrap();
+        assert_eq!(
+            cache.get::<Vec<String>>("search", "kubernetes"),
+            Some(vec!["acme/k8s".to_string()])
+        );
+        assert_eq!(cache.get::<Vec<String>>("search", "terraform"), None);
+    }
+
+    #[test]
+    fn test_cache_entry_expires_after_ttl() {
+        let dir = tempdir().unwrap();
+        let clock = FakeClock::new();
+        let cache = MetadataCache::with_clock(
+            dir.path().to_path_buf(),
+            "https://a.dev",
+            Duration::from_secs(60),
+            &clock,
+        );
+        cache.put("search", "q", &1u32).unwrap();
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(cache.get::<u32>("search", "q"), Some(1));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(cache.get::<u32>("search", "q"), None);
+        assert_eq!(cache.get_stale::<u32>("search", "q"), Some(1));
+    }
+
+    #[test]
+    fn test_cache_is_scoped_to_registry() {
+        let dir = tempdir().unwrap();
+        let clock = FakeClock::new();
+        let a = MetadataCache::with_clock(
+            dir.path().to_path_buf(),
+            "https://a.dev",
+            DEFAULT_TTL,
+            &clock,
+        );
+        let b = MetadataCache::with_clock(
+            dir.path().to_path_buf(),
+            "https://b.dev",
+            DEFAULT_TTL,
+            &clock,
+        );
+
+        a.put("search", "q", &"from a").unwrap();
+        assert_eq!(b.get::<String>("search", "q"), None);
+        assert_eq!(a.get::<String>("search", "q").as_deref(), Some("from a"));
+    }
+
+    #[test]
+    fn test_clear_removes_entries() {
+        let dir = tempdir().unwrap();
+        let clock = FakeClock::new();
+        let cache = MetadataCache::with_clock(
+            dir.path().to_path_buf(),
+            "https://a.dev",
+            DEFAULT_TTL,
+            &clock,
+        );
+        cache.put("search", "a", &1u32).unwrap();
+        cache.put("search", "b", &2u32).unwrap();
+
+        assert_eq!(clear(dir.path()).unwrap(), 2);
+        assert_eq!(cache.get::<u32>("search", "a"), None);
+        assert_eq!(clear(&dir.path().join("missing")).unwrap(), 0);
+    }
+
+    fn commit(repo: &Path, file: &str) {
+        std::fs::write(repo.join(file), file).unwrap();
+        for args in [
+            &["add", "."][..],
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "-m",
+                file,
+            ],
+        ] {
+            git::git_cmd(args, repo).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_checkout_reuses_clone_until_ref_moves() {
+        let repo = tempdir().unwrap();
+        git::git_cmd(&["init", "-q", "-b", "main"], repo.path()).unwrap();
+        commit(repo.path(), "SKILL.md");
+        let url = format!("file://{}", repo.path().display());
+
+        let cache_dir = tempdir().unwrap();
+        let cache = CloneCache::new(cache_dir.path().to_path_buf());
+        let clones = Cell::new(0);
+        let checkout = |commit: Option<&str>| {
+            let checkout = cache
+                .checkout(&url, Some("main"), commit, None, |path| {
+                    clones.set(clones.get() + 1);
+                    git::git_cmd(&["clone", "-q", &url, &path.to_string_lossy()], path)?;
+                    Ok(())
+                })
+                .unwrap();
+            (checkout.path.clone(), checkout.reused)
+        };
+
+        let (first, reused) = checkout(None);
+        assert!(!reused);
+        let (second, reused) = checkout(None);
+        assert!(reused);
+        assert_eq!(second, first);
+        assert_eq!(clones.get(), 1);
+
+        commit(repo.path(), "NEW.md");
+        let (third, reused) = checkout(None);
+        assert!(!reused);
+        assert!(third.join("NEW.md").is_file());
+        assert_eq!(clones.get(), 2);
+
+        assert_eq!(clear_clones(cache_dir.path()).unwrap(), 1);
+        assert!(!first.exists());
+    }
+
+    #[test]
+    fn test_checkout_checks_expected_commit() {
+        let repo = tempdir().unwrap();
+        git::git_cmd(&["init", "-q", "-b", "main"], repo.path()).unwrap();
+        commit(repo.path(), "SKILL.md");
+        let url = format!("file://{}", repo.path().display());
+        let head = git::git_cmd(&["rev-parse", "HEAD"], repo.path()).unwrap();
+
+        let cache_dir = tempdir().unwrap();
+        let cache = CloneCache::new(cache_dir.path().to_path_buf());
+        let checkout = |commit: &str| {
+            cache
+                .checkout(&url, Some("main"), Some(commit), None, |path| {
+                    git::git_cmd(&["clone", "-q", &url, &path.to_string_lossy()], path)?;
+                    Ok(())
+                })
+                .unwrap()
+                .reused
+        };
+
+        assert!(!checkout(&head));
+        assert!(checkout(&head));
+        // The registry expects a different commit than the one cached
+        assert!(!checkout("0000000000000000000000000000000000000000"));
+    }
+
+    #[test]
+    fn test_checkout_locks_entry() {
+        let cache_dir = tempdir().unwrap();
+        let cache = CloneCache::new(cache_dir.path().to_path_buf());
+        let fake_clone = |path: &Path| {
+            std::fs::create_dir_all(path.join(".git"))?;
+            Ok(())
+        };
+        let held = cache
+            .checkout("file:///repo", None, None, None, fake_clone)
+            .unwrap();
+
+        let lock = File::open(cache_dir.path().join(format!(
+            "{}.lock",
+            held.path.file_name().unwrap().to_string_lossy()
+        )))
+        .unwrap();
+        assert!(lock.try_lock().is_err());
+        drop(held);
+        assert!(lock.try_lock().is_ok());
+    }
+}
@@ -0,0 +1,181 @@
+//! Content checksums for installed skills
+//!
+//! The registry reports a checksum for each published version. After an
+//! install, the same digest is computed over the installed files and the two
+//! must match.
+//!
+//! The digest is SHA-256 over every regular file and symlink under the skill
+//! directory, skipping `.git` and anything the skill's `.paksignore` excludes,
+//! in byte order of their relative paths. Paths use
+//! `/` as the separator on every platform. Each entry contributes:
+//!
+//! ```text
+//! <relative path> NUL <length as u64 big-endian> <contents>
+//! ```
+//!
+//! where a symlink's contents are its target path. The result is written as
+//! `sha256:<hex>`.
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+use super::paksignore::IgnoreRules;
+
+/// Compute the content checksum of the skill in `dir`
+pub fn compute(dir: &Path) -> Result<String> {
+    let mut entries = Vec::new();
+    collect(dir, "", &IgnoreRules::load(dir), &mut entries)?;
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut hasher = Sha256::new();
+    for (rel, contents) in entries {
+        hasher.update(rel.as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_be_bytes());
+        hasher.update(&contents);
+    }
+    let digest: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok(format!("sha256:{}", digest))
+}
+
+/// Check the skill in `dir` against the registry's `expected` checksum
+///
+/// A bare hex digest is accepted as well as `sha256:<hex>`.
+pub fn verify(dir: &Path, expected: &str) -> Result<()> {
+    let actual = compute(dir)?;
+    let expected = expected.trim();
+    if !same(&actual, expected) {
+        bail!(
+            "Checksum mismatch for {}.\n  expected: {}\n  actual:   {}\n\
+             Hint: The downloaded content differs from what was published. \
+             Pass --skip-checksum to install anyway.",
+            dir.display(),
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Whether two checksums name the same digest
+///
+/// Either may be a bare hex digest or `sha256:<hex>`, in any case.
+pub fn same(a: &str, b: &str) -> bool {
+    let hex = |checksum: &str| {
+        let checksum = checksum.trim();
+        checksum
+            .strip_prefix("sha256:")
+            .unwrap_or(checksum)
+            .to_ascii_lowercase()
+    };
+    hex(a) == hex(b)
+}
+
+/// Gather `(relative path, contents)` for everything under `dir` that `rules`
+/// don't exclude
+fn collect(
+    dir: &Path,
+    prefix: &str,
+    rules: &IgnoreRules,
+    entries: &mut Vec<(String, Vec<u8>)>,
+) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rel = format!("{}{}", prefix, name);
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if rules.is_ignored(Path::new(&rel), file_type.is_dir()) {
+            continue;
+        }
+
+        if file_type.is_symlink() {
+            let target = std::fs::read_link(&path)?;
+            let target = target.to_string_lossy().replace('\\', "/");
+            entries.push((rel, target.into_bytes()));
+        } else if file_type.is_dir() {
+            if name != ".git" {
+                collect(&path, &format!("{}/", rel), rules, entries)?;
+            }
+        } else if file_type.is_file() {
+            let contents = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            entries.push((rel, contents));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        std::fs::write(dir.path().join("scripts/run.sh"), "echo hi\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_checksum_is_stable() {
+        let dir = fixture();
+        assert_eq!(
+            compute(dir.path()).unwrap(),
+            "sha256:6e6f19facb24625c574695952af716fc3b7dd45ccc0fbb321993f1ea742f1c03"
+        );
+
+        // .git is not part of the content
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "ref: main\n").unwrap();
+        assert_eq!(
+            compute(dir.path()).unwrap(),
+            compute(fixture().path()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_checksum_skips_paksignored_files() {
+        let dir = fixture();
+        std::fs::create_dir_all(dir.path().join("scripts/node_modules/dep")).unwrap();
+        std::fs::write(dir.path().join("scripts/node_modules/dep/index.js"), "").unwrap();
+        let with_deps = compute(dir.path()).unwrap();
+
+        std::fs::write(dir.path().join(".paksignore"), "node_modules/\n").unwrap();
+        let ignored = compute(dir.path()).unwrap();
+        assert_ne!(ignored, with_deps);
+
+        // Same as the fixture plus the .paksignore itself
+        let expected = fixture();
+        std::fs::write(expected.path().join(".paksignore"), "node_modules/\n").unwrap();
+        assert_eq!(ignored, compute(expected.path()).unwrap());
+    }
+
+    #[test]
+    fn test_verify_detects_changes() {
+        let dir = fixture();
+        let checksum = compute(dir.path()).unwrap();
+        verify(dir.path(), &checksum).unwrap();
+        verify(dir.path(), &checksum["sha256:".len()..].to_uppercase()).unwrap();
+
+        std::fs::write(dir.path().join("scripts/run.sh"), "echo bye\n").unwrap();
+        let err = verify(dir.path(), &checksum).unwrap_err();
+        assert!(err.to_string().contains("--skip-checksum"));
+    }
+
+    #[test]
+    fn test_same_ignores_prefix_and_case() {
+        assert!(same("sha256:abcd", "ABCD"));
+        assert!(same(" abcd\n", "sha256:abcd"));
+        assert!(!same("sha256:abcd", "sha256:abce"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Registry clients
+//!
+//! Every command builds its `PaksClient` here, so the global `--offline` and
+//! `--timeout` flags apply everywhere. The timeout comes from `--timeout
+//! <secs>` or `PAKS_TIMEOUT`; zero or unset keeps the client's default.
+
+use anyhow::{Context, Result};
+use paks_api::PaksClient;
+use paks_api::client::PaksClientBuilder;
+use std::ffi::OsString;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::config::Config;
+use super::offline;
+
+static TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// Decide the request timeout from the `--timeout` flag and the value of
+/// `PAKS_TIMEOUT`
+///
+/// The flag wins over the environment. Zero, or a value that isn't a whole
+/// number of seconds, leaves the default in place.
+pub fn resolve_timeout(
+    timeout_flag: Option<u64>,
+    timeout_env: Option<OsString>,
+) -> Option<Duration> {
+    let secs = timeout_flag.or_else(|| timeout_env?.to_str()?.trim().parse().ok())?;
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// Set the request timeout for the process from the global `--timeout` flag
+///
+/// Must be called before any client is built; later calls are ignored.
+pub fn init(timeout_flag: Option<u64>) {
+    TIMEOUT.get_or_init(|| resolve_timeout(timeout_flag, std::env::var_os("PAKS_TIMEOUT")));
+}
+
+/// Request timeout chosen at startup, if any
+pub fn timeout() -> Option<Duration> {
+    *TIMEOUT.get_or_init(|| resolve_timeout(None, std::env::var_os("PAKS_TIMEOUT")))
+}
+
+/// Builder for a client of the registry at `url`, with the global flags applied
+pub fn builder(url: impl Into<String>) -> PaksClientBuilder {
+    configure(
+        PaksClient::builder().base_url(url),
+        offline::is_offline(),
+        timeout(),
+    )
+}
+
+/// Client for the default registry
+pub fn registry_client(config: &Config) -> Result<PaksClient> {
+    builder(config.resolve_registry_url(None)?)
+        .build()
+        .context("Failed to create API client")
+}
+
+fn configure(
+    builder: PaksClientBuilder,
+    offline: bool,
+    timeout: Option<Duration>,
+) -> PaksClientBuilder {
+    let builder = builder.offline(offline);
+    match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_timeout() {
+        assert_eq!(resolve_timeout(None, None), None);
+        assert_eq!(resolve_timeout(Some(5), None), Some(Duration::from_secs(5)));
+        assert_eq!(
+            resolve_timeout(None, Some("12".into())),
+            Some(Duration::from_secs(12))
+        );
+        // The flag wins, and zero means the default
+        assert_eq!(
+            resolve_timeout(Some(3), Some("12".into())),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(resolve_timeout(Some(0), Some("12".into())), None);
+        assert_eq!(resolve_timeout(None, Some("0".into())), None);
+        assert_eq!(resolve_timeout(None, Some("soon".into())), None);
+    }
+
+    #[test]
+    fn test_builder_gets_timeout() {
+        let client = configure(PaksClient::builder(), false, Some(Duration::from_secs(7)))
+            .build()
+            .unwrap();
+        assert_eq!(client.timeout(), Duration::from_secs(7));
+
+        let default = configure(PaksClient::builder(), false, None)
+            .build()
+            .unwrap();
+        assert_eq!(default.timeout(), PaksClient::new().unwrap().timeout());
+    }
+}
@@ -2,10 +2,18 @@
 //!
 //! Config file location: ~/.paks/config.toml
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use paks_api::client::{DEFAULT_BASE_URL, env_auth_token};
+
+use super::cache;
+use super::keychain::SecretStore;
+use super::paths::{contract_home, expand_path};
 
 /// Main configuration structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -14,6 +22,11 @@ pub struct Config {
     #[serde(default)]
     pub default_agent: Option<String>,
 
+    /// Agents to fall back to, in order, when no default agent is set: the
+    /// first whose skills directory already exists is used
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub install_fallback: Vec<String>,
+
     /// Default registry to use when --registry is not specified
     #[serde(default)]
     pub default_registry: Option<String>,
@@ -25,12 +38,47 @@ pub struct Config {
     /// Configured registries
     #[serde(default)]
     pub registries: IndexMap<String, RegistryConfig>,
+
+    /// How long cached registry metadata stays fresh, in seconds (default 300)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_secs: Option<u64>,
+
+    /// Minisign public key used to verify skill signatures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_public_key: Option<String>,
+
+    /// Registry requests in flight at once for bulk queries (default 4)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jobs: Option<usize>,
+
+    /// Delay before each request of a bulk query, in milliseconds (default 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_delay_ms: Option<u64>,
+
+    /// Ask before installing registry paks larger than this many megabytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_install_over_mb: Option<u64>,
+
+    /// Clone registry skills over SSH instead of HTTPS (default false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefer_ssh: Option<bool>,
+
+    /// Keep tokens in this file when the OS keychain is unavailable (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plaintext_token_fallback: Option<bool>,
+
+    /// Former names of skills moved with `paks rename`, per agent:
+    /// agent id → old name → current directory name
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub aliases: IndexMap<String, IndexMap<String, String>>,
 }
 
 /// Agent configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentConfig {
-    /// Display name for the agent
+    /// Display name for the agent (may be omitted when overriding a
+    /// built-in agent)
+    #[serde(default)]
     pub name: String,
 
     /// Path to the skills directory
@@ -41,15 +89,87 @@ pub struct AgentConfig {
     pub description: Option<String>,
 }
 
+/// Custom agents and registries shared between machines with
+/// `paks agent export` and `paks agent import`
+///
+/// Built-in agents are left out since every config has them, and registries
+/// carry only their URL, never a token.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PortableConfig {
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub agents: IndexMap<String, AgentConfig>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub registries: IndexMap<String, PortableRegistry>,
+}
+
+/// A registry in a [`PortableConfig`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortableRegistry {
+    pub url: String,
+}
+
+impl PortableConfig {
+    /// Serialize as JSON for a `.json` file, TOML otherwise (and for stdout)
+    pub fn render(&self, path: Option<&Path>) -> Result<String> {
+        if path.is_some_and(is_json_path) {
+            Ok(serde_json::to_string_pretty(self)? + "\n")
+        } else {
+            toml::to_string_pretty(self).context("Failed to serialize config")
+        }
+    }
+
+    /// Read an export written by [`PortableConfig::render`]
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if is_json_path(path) {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))
+        } else {
+            toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+        }
+    }
+}
+
+fn is_json_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// What [`Config::import`] did with each agent and registry
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+    /// Entries already present locally, left alone without `--force`
+    pub skipped: Vec<String>,
+}
+
 /// Registry configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryConfig {
     /// Registry URL
     pub url: String,
 
-    /// Optional API token (stored separately in keychain ideally)
+    /// API token, when it isn't kept in the OS keychain
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
+
+    /// Whether the OS keychain holds this registry's token
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub token_in_keychain: bool,
+
+    /// When the token expires, as reported by the registry at login
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_expires_at: Option<DateTime<Utc>>,
+}
+
+impl RegistryConfig {
+    /// Whether a token is stored for this registry, in either place
+    pub fn has_token(&self) -> bool {
+        self.token.is_some() || self.token_in_keychain
+    }
 }
 
 impl Config {
@@ -74,15 +194,174 @@ impl Config {
         let mut config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse config from {}", path.display()))?;
 
-        // Merge built-in agents (user config takes precedence)
-        let builtin = Self::builtin_agents();
-        for (key, agent) in builtin {
-            config.agents.entry(key).or_insert(agent);
+        // Allow `~` and environment variables in configured skills directories
+        for agent in config.agents.values_mut() {
+            agent.skills_dir = expand_path(&agent.skills_dir.to_string_lossy());
         }
 
+        config.merge_builtin_agents();
         Ok(config)
     }
 
+    /// Add the built-in agents to the configured ones
+    ///
+    /// A configured agent with a built-in's id overrides it; its name and
+    /// description fall back to the built-in's when left out.
+    fn merge_builtin_agents(&mut self) {
+        for (key, builtin) in Self::builtin_agents() {
+            match self.agents.get_mut(&key) {
+                Some(agent) => {
+                    if agent.name.is_empty() {
+                        agent.name = builtin.name;
+                    }
+                    if agent.description.is_none() {
+                        agent.description = builtin.description;
+                    }
+                }
+                None => {
+                    self.agents.insert(key, builtin);
+                }
+            }
+        }
+    }
+
+    /// Whether `name` is one of the agents paks knows out of the box
+    pub fn is_builtin_agent(name: &str) -> bool {
+        Self::builtin_agents().contains_key(name)
+    }
+
+    /// Point the agent `name` (built-in or custom) at `skills_dir`
+    pub fn set_agent_dir(&mut self, name: &str, skills_dir: PathBuf) -> Result<()> {
+        let Some(agent) = self.agents.get_mut(name) else {
+            bail!("Agent '{}' not found", name);
+        };
+        if skills_dir.exists() && !skills_dir.is_dir() {
+            bail!("{} exists and is not a directory", skills_dir.display());
+        }
+        agent.skills_dir = skills_dir;
+        Ok(())
+    }
+
+    /// Custom agents and registries in a form that can be shared, with
+    /// skills directories under the home directory written as `~/...`
+    pub fn export(&self) -> PortableConfig {
+        let agents = self
+            .agents
+            .iter()
+            .filter(|(id, _)| !Self::is_builtin_agent(id))
+            .map(|(id, agent)| {
+                let mut agent = agent.clone();
+                agent.skills_dir = PathBuf::from(contract_home(&agent.skills_dir));
+                (id.clone(), agent)
+            })
+            .collect();
+        let registries = self
+            .registries
+            .iter()
+            .map(|(name, registry)| {
+                let url = registry.url.clone();
+                (name.clone(), PortableRegistry { url })
+            })
+            .collect();
+        PortableConfig { agents, registries }
+    }
+
+    /// Merge an export into this config
+    ///
+    /// Existing agents and registries are kept unless `force` is set. A
+    /// replaced registry keeps its token only if its URL is unchanged.
+    pub fn import(&mut self, portable: PortableConfig, force: bool) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+
+        for (id, mut agent) in portable.agents {
+            let label = format!("agent {}", id);
+            agent.skills_dir = expand_path(&agent.skills_dir.to_string_lossy());
+            match self.agents.get_mut(&id) {
+                Some(_) if !force => summary.skipped.push(label),
+                Some(existing) => {
+                    *existing = agent;
+                    summary.replaced.push(label);
+                }
+                None => {
+                    self.agents.insert(id, agent);
+                    summary.added.push(label);
+                }
+            }
+        }
+
+        for (name, registry) in portable.registries {
+            let label = format!("registry {}", name);
+            match self.registries.get_mut(&name) {
+                Some(_) if !force => summary.skipped.push(label),
+                Some(existing) => {
+                    if existing.url != registry.url {
+                        *existing = RegistryConfig {
+                            url: registry.url,
+                            token: None,
+                            token_in_keychain: false,
+                            token_expires_at: None,
+                        };
+                    }
+                    summary.replaced.push(label);
+                }
+                None => {
+                    self.registries.insert(
+                        name,
+                        RegistryConfig {
+                            url: registry.url,
+                            token: None,
+                            token_in_keychain: false,
+                            token_expires_at: None,
+                        },
+                    );
+                    summary.added.push(label);
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Rename the custom agent `old` to `new`, keeping its place in the
+    /// agent list and everything that refers to it
+    pub fn rename_agent(&mut self, old: &str, new: &str) -> Result<()> {
+        if Self::is_builtin_agent(old) {
+            bail!(
+                "Cannot rename built-in agent '{}'. Use 'paks agent set {} --dir <path>' to move its skills directory.",
+                old,
+                old
+            );
+        }
+        if new.is_empty() {
+            bail!("Agent name cannot be empty");
+        }
+        if self.agents.contains_key(new) {
+            bail!("Agent '{}' already exists", new);
+        }
+        let Some((index, _, mut agent)) = self.agents.shift_remove_full(old) else {
+            bail!("Agent '{}' not found", old);
+        };
+
+        // Agents added with `paks agent add` are named after their id
+        if agent.name == old {
+            agent.name = new.to_string();
+        }
+        self.agents.shift_insert(index, new.to_string(), agent);
+
+        if self.default_agent.as_deref() == Some(old) {
+            self.default_agent = Some(new.to_string());
+        }
+        for fallback in &mut self.install_fallback {
+            if fallback == old {
+                *fallback = new.to_string();
+            }
+        }
+        if let Some(aliases) = self.aliases.shift_remove(old) {
+            self.aliases.insert(new.to_string(), aliases);
+        }
+        Ok(())
+    }
+
     /// Save config to disk
     pub fn save(&self) -> Result<()> {
         let path = Self::path()?;
@@ -221,9 +500,44 @@ impl Config {
     fn default_with_builtin_agents() -> Self {
         Self {
             default_agent: None,
+            install_fallback: Vec::new(),
             default_registry: None,
             agents: Self::builtin_agents(),
             registries: IndexMap::new(),
+            cache_ttl_secs: None,
+            signature_public_key: None,
+            jobs: None,
+            request_delay_ms: None,
+            confirm_install_over_mb: None,
+            prefer_ssh: None,
+            plaintext_token_fallback: None,
+            aliases: IndexMap::new(),
+        }
+    }
+
+    /// Time-to-live for cached registry metadata
+    pub fn cache_ttl(&self) -> Duration {
+        self.cache_ttl_secs
+            .map(Duration::from_secs)
+            .unwrap_or(cache::DEFAULT_TTL)
+    }
+
+    /// URL of the registry `name_or_url` (a configured name or a URL), or of
+    /// the default registry if `None`
+    ///
+    /// Falls back to [`DEFAULT_BASE_URL`] when no default registry is configured.
+    pub fn resolve_registry_url(&self, name_or_url: Option<&str>) -> Result<String> {
+        match name_or_url {
+            Some(url) if url.contains("://") => Ok(url.to_string()),
+            Some(name) => match self.registries.get(name) {
+                Some(registry) => Ok(registry.url.clone()),
+                None => bail!("Registry '{}' is not configured", name),
+            },
+            None => Ok(self
+                .default_registry
+                .as_ref()
+                .and_then(|name| self.registries.get(name))
+                .map_or_else(|| DEFAULT_BASE_URL.to_string(), |r| r.url.clone())),
         }
     }
 
@@ -232,64 +546,311 @@ impl Config {
         self.agents.get(name)
     }
 
-    /// Get the default agent config
+    /// Id of the agent used when `--agent` is not given
+    ///
+    /// The configured default agent wins; otherwise the first agent in
+    /// `install_fallback` whose skills directory already exists. `None` means
+    /// the shared [`Config::default_skills_dir`].
+    pub fn default_agent_id(&self) -> Option<&str> {
+        if let Some(name) = self.default_agent.as_deref()
+            && self.agents.contains_key(name)
+        {
+            return Some(name);
+        }
+        self.install_fallback
+            .iter()
+            .map(String::as_str)
+            .find(|name| {
+                self.agents
+                    .get(*name)
+                    .is_some_and(|agent| agent.skills_dir.is_dir())
+            })
+    }
+
+    /// Get the default agent config, following `install_fallback` when no
+    /// default agent is set
     pub fn get_default_agent(&self) -> Option<&AgentConfig> {
-        self.default_agent
-            .as_ref()
+        self.default_agent_id()
             .and_then(|name| self.agents.get(name))
     }
 
-    /// Get the auth token for the default registry
-    pub fn get_auth_token(&self) -> Option<&str> {
-        // First check default registry
-        if let Some(default_reg) = &self.default_registry
-            && let Some(reg) = self.registries.get(default_reg)
-            && reg.token.is_some()
-        {
-            return reg.token.as_deref();
+    /// Id of the agent whose skills directory is used for `agent`, or for
+    /// the default agent; `paks` for the shared default directory
+    pub fn skills_scope<'a>(&'a self, agent: Option<&'a str>) -> &'a str {
+        match agent {
+            Some(agent) => agent,
+            None => self.default_agent_id().unwrap_or("paks"),
+        }
+    }
+
+    /// Directory name of the skill called `name` in `agent`'s skills
+    /// directory, following an alias left by `paks rename`
+    ///
+    /// A directory that's actually called `name` wins over the alias, so a
+    /// skill installed again under its old name is never mistaken for the
+    /// renamed one.
+    pub fn resolve_skill_alias<'a>(
+        &'a self,
+        agent: &str,
+        skills_dir: &Path,
+        name: &'a str,
+    ) -> &'a str {
+        if skills_dir.join(name).exists() {
+            return name;
+        }
+        self.aliases
+            .get(agent)
+            .and_then(|aliases| aliases.get(name))
+            .map_or(name, String::as_str)
+    }
+
+    /// Remember that skill `old` in `agent` is now called `new`
+    pub fn record_rename(&mut self, agent: &str, old: &str, new: &str) {
+        let aliases = self.aliases.entry(agent.to_string()).or_default();
+        // Older names of the skill follow it to its new name
+        for target in aliases.values_mut() {
+            if target == old {
+                *target = new.to_string();
+            }
+        }
+        // A name that's a directory again is no longer an alias
+        aliases.shift_remove(new);
+        aliases.insert(old.to_string(), new.to_string());
+    }
+
+    /// Drop the aliases of skill `name` in `agent`, once it's removed
+    pub fn forget_aliases(&mut self, agent: &str, name: &str) {
+        if let Some(aliases) = self.aliases.get_mut(agent) {
+            aliases.retain(|_, target| target != name);
+            if aliases.is_empty() {
+                self.aliases.shift_remove(agent);
+            }
         }
-        // Fall back to "stakpak" registry
+    }
+
+    /// Name of the registry whose token is used: the default registry, or
+    /// `stakpak` if none is configured
+    ///
+    /// This is the registry [`Config::resolve_registry_url`] points at, so a
+    /// token never goes to a registry other than the one it was issued for.
+    fn auth_registry_name(&self) -> &str {
+        self.default_registry.as_deref().unwrap_or("stakpak")
+    }
+
+    /// Name and config of the registry whose token is used, if it has one
+    fn auth_registry(&self) -> Option<(&str, &RegistryConfig)> {
+        let name = self.auth_registry_name();
         self.registries
-            .get("stakpak")
-            .and_then(|r| r.token.as_deref())
+            .get(name)
+            .filter(|r| r.has_token())
+            .map(|r| (name, r))
+    }
+
+    /// Get the auth token for the default registry
+    ///
+    /// `PAKS_TOKEN` takes precedence when set, then the keychain (`store`),
+    /// then a token kept in the config.
+    pub fn get_auth_token(&self, store: &impl SecretStore) -> Result<Option<String>> {
+        self.auth_token_with_env(env_auth_token(), store)
+    }
+
+    fn auth_token_with_env(
+        &self,
+        env_token: Option<String>,
+        store: &impl SecretStore,
+    ) -> Result<Option<String>> {
+        match env_token {
+            Some(token) => Ok(Some(token)),
+            None => self.get_stored_auth_token(store),
+        }
+    }
+
+    /// Get the auth token `paks login` stored for the default registry,
+    /// reading it from `store` if it's kept in the keychain
+    pub fn get_stored_auth_token(&self, store: &impl SecretStore) -> Result<Option<String>> {
+        let Some((name, reg)) = self.auth_registry() else {
+            return Ok(None);
+        };
+        if !reg.token_in_keychain {
+            return Ok(reg.token.clone());
+        }
+        store.get(name).with_context(|| {
+            format!(
+                "Failed to read the token for registry '{}' from the OS keychain",
+                name
+            )
+        })
+    }
+
+    /// Expiry of the token returned by [`Config::get_auth_token`], if known
+    ///
+    /// Unknown for a token from `PAKS_TOKEN`.
+    pub fn get_auth_token_expiry(&self) -> Option<DateTime<Utc>> {
+        if env_auth_token().is_some() {
+            return None;
+        }
+        self.auth_registry()
+            .and_then(|(_, reg)| reg.token_expires_at)
+    }
+
+    /// Whether tokens may be kept in plaintext when the keychain is unavailable
+    pub fn plaintext_token_fallback(&self) -> bool {
+        self.plaintext_token_fallback.unwrap_or(true)
     }
 
     /// Set the auth token for the default registry
-    pub fn set_auth_token(&mut self, token: String) {
-        let registry_name = self
-            .default_registry
-            .clone()
-            .unwrap_or_else(|| "stakpak".to_string());
+    ///
+    /// The token goes into `store`, leaving only a marker in the config. If
+    /// that fails it's kept in the config instead, unless
+    /// `plaintext_token_fallback` is off.
+    pub fn set_auth_token(
+        &mut self,
+        store: &impl SecretStore,
+        token: String,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let registry_name = self.auth_registry_name().to_string();
 
-        if let Some(reg) = self.registries.get_mut(&registry_name) {
-            reg.token = Some(token);
+        let in_keychain = match store.set(&registry_name, &token) {
+            Ok(()) => true,
+            Err(e) if self.plaintext_token_fallback() => {
+                eprintln!(
+                    "⚠ Warning: OS keychain unavailable ({:#}); storing the token in plaintext",
+                    e
+                );
+                false
+            }
+            Err(e) => bail!(
+                "Failed to store the token in the OS keychain: {:#}\n\
+                 Hint: Set plaintext_token_fallback = true in ~/.paks/config.toml to store it there instead",
+                e
+            ),
+        };
+
+        let reg = self
+            .registries
+            .entry(registry_name)
+            .or_insert_with(|| RegistryConfig {
+                url: "https://apiv2.stakpak.dev".to_string(),
+                token: None,
+                token_in_keychain: false,
+                token_expires_at: None,
+            });
+        reg.token = (!in_keychain).then_some(token);
+        reg.token_in_keychain = in_keychain;
+        reg.token_expires_at = expires_at;
+        Ok(())
+    }
+
+    /// Move tokens kept in plaintext into `store`
+    ///
+    /// Tokens the keychain won't take stay where they are. Returns how many
+    /// were moved.
+    pub fn migrate_tokens_to_keychain(&mut self, store: &impl SecretStore) -> usize {
+        let mut moved = 0;
+        for (name, reg) in self.registries.iter_mut() {
+            if let Some(token) = &reg.token
+                && store.set(name, token).is_ok()
+            {
+                reg.token = None;
+                reg.token_in_keychain = true;
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    /// Make a registry the default, adding it first if given by URL
+    ///
+    /// A URL matching a configured registry selects that registry; any other
+    /// URL is added under a name derived from its host. A bare name must
+    /// already be configured. Returns the registry's name and URL.
+    pub fn select_registry(&mut self, name_or_url: &str) -> Result<(String, String)> {
+        let name = if name_or_url.starts_with("https://") || name_or_url.starts_with("http://") {
+            let url = name_or_url.trim_end_matches('/');
+            match self
+                .registries
+                .iter()
+                .find(|(_, r)| r.url.trim_end_matches('/') == url)
+            {
+                Some((name, _)) => name.clone(),
+                None => {
+                    let name = registry_name_from_url(url)?;
+                    if self.registries.contains_key(&name) {
+                        bail!(
+                            "A registry named '{}' already exists with a different URL",
+                            name
+                        );
+                    }
+                    self.registries.insert(
+                        name.clone(),
+                        RegistryConfig {
+                            url: url.to_string(),
+                            token: None,
+                            token_in_keychain: false,
+                            token_expires_at: None,
+                        },
+                    );
+                    name
+                }
+            }
+        } else if self.registries.contains_key(name_or_url) {
+            name_or_url.to_string()
         } else {
-            self.registries.insert(
-                registry_name,
-                RegistryConfig {
-                    url: "https://apiv2.stakpak.dev".to_string(),
-                    token: Some(token),
-                },
+            bail!(
+                "Registry '{}' is not configured.\n\
+                 Hint: Pass the registry URL instead to add it, e.g. --registry https://...",
+                name_or_url
             );
-        }
+        };
+
+        let url = self.registries[&name].url.clone();
+        self.default_registry = Some(name.clone());
+        Ok((name, url))
     }
 
-    /// Clear the auth token for the default registry
-    pub fn clear_auth_token(&mut self) {
-        let registry_name = self
-            .default_registry
-            .clone()
-            .unwrap_or_else(|| "stakpak".to_string());
+    /// Clear the auth token for the default registry, removing it from
+    /// `store` if it's kept there
+    pub fn clear_auth_token(&mut self, store: &impl SecretStore) {
+        let registry_name = self.auth_registry_name().to_string();
 
         if let Some(reg) = self.registries.get_mut(&registry_name) {
+            if reg.token_in_keychain
+                && let Err(e) = store.delete(&registry_name)
+            {
+                eprintln!(
+                    "⚠ Warning: Failed to remove the token from the OS keychain: {:#}",
+                    e
+                );
+            }
             reg.token = None;
+            reg.token_in_keychain = false;
+            reg.token_expires_at = None;
         }
     }
 }
 
+/// Registry name derived from a URL's host, e.g. `paks-acme-dev` for
+/// `https://paks.acme.dev`
+fn registry_name_from_url(url: &str) -> Result<String> {
+    let host = url
+        .split_once("://")
+        .map(|(_, rest)| rest)
+        .unwrap_or(url)
+        .split(['/', ':'])
+        .next()
+        .unwrap_or_default();
+    if host.is_empty() {
+        bail!("Invalid registry URL '{}'", url);
+    }
+    Ok(host.to_ascii_lowercase().replace('.', "-"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::core::keychain::MemoryStore;
+    use std::path::Path;
 
     #[test]
     fn test_default_config_has_builtin_agents() {
@@ -306,4 +867,472 @@ mod tests {
         let parsed: Config = toml::from_str(&toml_str).unwrap_or_default();
         assert_eq!(config.default_agent, parsed.default_agent);
     }
+
+    fn agent_in(dir: &Path) -> AgentConfig {
+        AgentConfig {
+            name: "Test".to_string(),
+            skills_dir: dir.to_path_buf(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_default_agent_resolution_order() {
+        let home = tempfile::tempdir().unwrap();
+        let present = home.path().join("present");
+        std::fs::create_dir(&present).unwrap();
+
+        let mut config = Config::default();
+        config.agents.insert(
+            "missing".to_string(),
+            agent_in(&home.path().join("missing")),
+        );
+        config
+            .agents
+            .insert("present".to_string(), agent_in(&present));
+        config
+            .agents
+            .insert("chosen".to_string(), agent_in(&present));
+
+        // Nothing configured: the shared directory
+        assert_eq!(config.default_agent_id(), None);
+        assert_eq!(config.skills_scope(None), "paks");
+
+        // Fallbacks are skipped when unknown or their directory is missing
+        config.install_fallback = vec![
+            "unknown".to_string(),
+            "missing".to_string(),
+            "present".to_string(),
+        ];
+        assert_eq!(config.default_agent_id(), Some("present"));
+        assert_eq!(config.skills_scope(None), "present");
+        assert_eq!(config.get_default_agent().unwrap().skills_dir, present);
+
+        // An explicit default agent wins over the fallback order
+        config.default_agent = Some("chosen".to_string());
+        assert_eq!(config.default_agent_id(), Some("chosen"));
+
+        // ... unless it isn't configured
+        config.default_agent = Some("gone".to_string());
+        assert_eq!(config.default_agent_id(), Some("present"));
+
+        // --agent always wins
+        assert_eq!(config.skills_scope(Some("missing")), "missing");
+
+        config.install_fallback = vec!["missing".to_string()];
+        assert_eq!(config.default_agent_id(), None);
+    }
+
+    #[test]
+    fn test_override_builtin_agent_dir() {
+        let mut config: Config =
+            toml::from_str("[agents.claude-code]\nskills_dir = \"/opt/claude/skills\"\n").unwrap();
+        config.merge_builtin_agents();
+
+        let agent = config.get_agent("claude-code").unwrap();
+        assert_eq!(agent.skills_dir, PathBuf::from("/opt/claude/skills"));
+        assert_eq!(agent.name, "Claude Code");
+        assert!(agent.description.is_some());
+        assert!(config.agents.contains_key("cursor"));
+
+        let dir = tempfile::tempdir().unwrap();
+        config
+            .set_agent_dir("cursor", dir.path().to_path_buf())
+            .unwrap();
+        let saved = toml::to_string_pretty(&config).unwrap();
+        let mut reloaded: Config = toml::from_str(&saved).unwrap();
+        reloaded.merge_builtin_agents();
+        assert_eq!(reloaded.get_agent("cursor").unwrap().skills_dir, dir.path());
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(config.set_agent_dir("cursor", file).is_err());
+        assert!(
+            config
+                .set_agent_dir("nope", dir.path().to_path_buf())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rename_agent_updates_references() {
+        let mut config = Config::default_with_builtin_agents();
+        config
+            .agents
+            .insert("mine".to_string(), agent_in(Path::new("/tmp/mine")));
+        config.agents.get_mut("mine").unwrap().name = "mine".to_string();
+        config
+            .agents
+            .insert("last".to_string(), agent_in(Path::new("/tmp/last")));
+        config.default_agent = Some("mine".to_string());
+        config.install_fallback = vec!["mine".to_string()];
+        config.record_rename("mine", "old-skill", "skill");
+
+        config.rename_agent("mine", "ours").unwrap();
+
+        let ids: Vec<&str> = config.agents.keys().map(String::as_str).collect();
+        assert_eq!(&ids[ids.len() - 2..], ["ours", "last"]);
+        assert_eq!(config.get_agent("ours").unwrap().name, "ours");
+        assert_eq!(config.default_agent.as_deref(), Some("ours"));
+        assert_eq!(config.install_fallback, ["ours"]);
+        assert_eq!(
+            config.resolve_skill_alias("ours", Path::new("/nonexistent"), "old-skill"),
+            "skill"
+        );
+
+        assert!(config.rename_agent("claude-code", "claude").is_err());
+        assert!(config.rename_agent("ours", "last").is_err());
+        assert!(config.rename_agent("missing", "other").is_err());
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut source = Config::default_with_builtin_agents();
+        source
+            .agents
+            .insert("mine".to_string(), agent_in(&dir.path().join("mine")));
+        source.registries.insert(
+            "acme".to_string(),
+            RegistryConfig {
+                url: "https://paks.acme.dev".to_string(),
+                token: Some("pak_secret".to_string()),
+                token_in_keychain: false,
+                token_expires_at: None,
+            },
+        );
+
+        for file in ["paks.json", "paks.toml"] {
+            let path = dir.path().join(file);
+            let exported = source.export();
+            std::fs::write(&path, exported.render(Some(&path)).unwrap()).unwrap();
+            let written = std::fs::read_to_string(&path).unwrap();
+            assert!(!written.contains("pak_secret"), "{}", file);
+            assert!(!written.contains("claude-code"), "{}", file);
+
+            let mut target = Config::default_with_builtin_agents();
+            let summary = target.import(PortableConfig::read(&path).unwrap(), false);
+            assert_eq!(summary.added, ["agent mine", "registry acme"]);
+            assert_eq!(
+                target.get_agent("mine").unwrap().skills_dir,
+                dir.path().join("mine")
+            );
+            assert_eq!(target.registries["acme"].url, "https://paks.acme.dev");
+            assert!(!target.registries["acme"].has_token());
+        }
+    }
+
+    #[test]
+    fn test_import_keeps_existing_without_force() {
+        let mut local = Config::default_with_builtin_agents();
+        local
+            .agents
+            .insert("mine".to_string(), agent_in(Path::new("/local/mine")));
+        local.registries.insert(
+            "acme".to_string(),
+            RegistryConfig {
+                url: "https://paks.acme.dev".to_string(),
+                token: Some("pak_local".to_string()),
+                token_in_keychain: false,
+                token_expires_at: None,
+            },
+        );
+
+        let mut shared = Config::default_with_builtin_agents();
+        shared
+            .agents
+            .insert("mine".to_string(), agent_in(Path::new("/shared/mine")));
+        shared.registries.insert(
+            "acme".to_string(),
+            RegistryConfig {
+                url: "https://paks.acme.dev".to_string(),
+                token: None,
+                token_in_keychain: false,
+                token_expires_at: None,
+            },
+        );
+
+        let summary = local.import(shared.export(), false);
+        assert_eq!(summary.skipped, ["agent mine", "registry acme"]);
+        assert_eq!(
+            local.get_agent("mine").unwrap().skills_dir,
+            Path::new("/local/mine")
+        );
+
+        let summary = local.import(shared.export(), true);
+        assert_eq!(summary.replaced, ["agent mine", "registry acme"]);
+        assert_eq!(
+            local.get_agent("mine").unwrap().skills_dir,
+            Path::new("/shared/mine")
+        );
+        // Same URL: the local token survives
+        assert_eq!(local.registries["acme"].token.as_deref(), Some("pak_local"));
+
+        shared.registries.get_mut("acme").unwrap().url = "https://other.dev".to_string();
+        local.import(shared.export(), true);
+        assert_eq!(local.registries["acme"].url, "https://other.dev");
+        assert!(!local.registries["acme"].has_token());
+    }
+
+    #[test]
+    fn test_select_registry_adds_url() {
+        let mut config = Config::default();
+
+        let (name, url) = config.select_registry("https://paks.acme.dev/").unwrap();
+
+        assert_eq!(name, "paks-acme-dev");
+        assert_eq!(url, "https://paks.acme.dev");
+        assert_eq!(config.default_registry.as_deref(), Some("paks-acme-dev"));
+
+        // Selecting the same URL again reuses the entry
+        let (again, _) = config.select_registry("https://paks.acme.dev").unwrap();
+        assert_eq!(again, name);
+        assert_eq!(config.registries.len(), 1);
+    }
+
+    #[test]
+    fn test_select_registry_by_name() {
+        let mut config = Config::default();
+        config.registries.insert(
+            "acme".to_string(),
+            RegistryConfig {
+                url: "https://paks.acme.dev".to_string(),
+                token: None,
+                token_in_keychain: false,
+                token_expires_at: None,
+            },
+        );
+
+        let (name, url) = config.select_registry("acme").unwrap();
+        assert_eq!(
+            (name.as_str(), url.as_str()),
+            ("acme", "https://paks.acme.dev")
+        );
+        assert_eq!(config.default_registry.as_deref(), Some("acme"));
+
+        let err = config.select_registry("unknown").unwrap_err();
+        assert!(err.to_string().contains("not configured"));
+    }
+
+    #[test]
+    fn test_resolve_registry_url_fallback() {
+        let config = Config::default();
+        assert_eq!(config.resolve_registry_url(None).unwrap(), DEFAULT_BASE_URL);
+        assert_eq!(
+            config
+                .resolve_registry_url(Some("http://localhost:8080"))
+                .unwrap(),
+            "http://localhost:8080"
+        );
+    }
+
+    #[test]
+    fn test_resolve_registry_url_default_registry() {
+        let mut config = Config::default();
+        config.select_registry("https://paks.acme.dev").unwrap();
+        assert_eq!(
+            config.resolve_registry_url(None).unwrap(),
+            "https://paks.acme.dev"
+        );
+    }
+
+    #[test]
+    fn test_resolve_registry_url_named_registry() {
+        let mut config = Config::default();
+        config.registries.insert(
+            "staging".to_string(),
+            RegistryConfig {
+                url: "https://staging.paks.dev".to_string(),
+                token: None,
+                token_in_keychain: false,
+                token_expires_at: None,
+            },
+        );
+        assert_eq!(
+            config.resolve_registry_url(Some("staging")).unwrap(),
+            "https://staging.paks.dev"
+        );
+        // Naming a registry doesn't need it to be the default
+        assert_eq!(config.resolve_registry_url(None).unwrap(), DEFAULT_BASE_URL);
+        assert!(config.resolve_registry_url(Some("unknown")).is_err());
+    }
+
+    #[test]
+    fn test_auth_token_kept_in_keychain() {
+        let mut config = Config::default();
+        let store = MemoryStore::default();
+        config
+            .set_auth_token(&store, "pak_secret".to_string(), None)
+            .unwrap();
+
+        let reg = &config.registries["stakpak"];
+        assert!(reg.token.is_none());
+        assert!(reg.token_in_keychain);
+        assert!(!toml::to_string(&config).unwrap().contains("pak_secret"));
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_secret")
+        );
+
+        config.clear_auth_token(&store);
+        assert!(store.secrets.borrow().is_empty());
+        assert_eq!(config.get_auth_token(&store).unwrap(), None);
+    }
+
+    #[test]
+    fn test_auth_token_not_sent_to_other_registry() {
+        let mut config = Config::default();
+        let store = MemoryStore::default();
+        config
+            .set_auth_token(&store, "pak_stakpak".to_string(), None)
+            .unwrap();
+
+        config.select_registry("https://paks.acme.dev").unwrap();
+        assert_eq!(config.get_auth_token(&store).unwrap(), None);
+        assert_eq!(config.get_auth_token_expiry(), None);
+
+        config
+            .set_auth_token(&store, "pak_acme".to_string(), None)
+            .unwrap();
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_acme")
+        );
+    }
+
+    #[test]
+    fn test_env_token_wins() {
+        let mut config = Config::default();
+        let store = MemoryStore::unavailable();
+        config
+            .set_auth_token(&store, "pak_config".to_string(), None)
+            .unwrap();
+
+        let token = config
+            .auth_token_with_env(Some("pak_env".to_string()), &store)
+            .unwrap();
+        assert_eq!(token.as_deref(), Some("pak_env"));
+        let token = config.auth_token_with_env(None, &store).unwrap();
+        assert_eq!(token.as_deref(), Some("pak_config"));
+        // login and logout only deal with the stored token
+        assert_eq!(
+            config.get_stored_auth_token(&store).unwrap().as_deref(),
+            Some("pak_config")
+        );
+    }
+
+    #[test]
+    fn test_auth_token_plaintext_fallback() {
+        let mut config = Config::default();
+        let store = MemoryStore::unavailable();
+        config
+            .set_auth_token(&store, "pak_secret".to_string(), None)
+            .unwrap();
+        assert_eq!(
+            config.registries["stakpak"].token.as_deref(),
+            Some("pak_secret")
+        );
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_secret")
+        );
+
+        let mut config = Config {
+            plaintext_token_fallback: Some(false),
+            ..Config::default()
+        };
+        let err = config
+            .set_auth_token(&store, "pak_secret".to_string(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("plaintext_token_fallback"));
+        assert!(config.registries.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_tokens_to_keychain() {
+        let mut config = Config::default();
+        config
+            .set_auth_token(&MemoryStore::unavailable(), "pak_old".to_string(), None)
+            .unwrap();
+
+        assert_eq!(
+            config.migrate_tokens_to_keychain(&MemoryStore::unavailable()),
+            0
+        );
+        assert!(config.registries["stakpak"].token.is_some());
+
+        let store = MemoryStore::default();
+        assert_eq!(config.migrate_tokens_to_keychain(&store), 1);
+        assert!(config.registries["stakpak"].token.is_none());
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_old")
+        );
+    }
+
+    #[test]
+    fn test_skill_aliases_follow_renames() {
+        let mut config = Config::default();
+        let skills = tempfile::tempdir().unwrap();
+        let dir = skills.path();
+        assert_eq!(
+            config.resolve_skill_alias("cursor", dir, "acme--deploy"),
+            "acme--deploy"
+        );
+
+        config.record_rename("cursor", "acme--deploy", "deploy");
+        assert_eq!(
+            config.resolve_skill_alias("cursor", dir, "acme--deploy"),
+            "deploy"
+        );
+        assert_eq!(
+            config.resolve_skill_alias("cursor", dir, "deploy"),
+            "deploy"
+        );
+        // Aliases are per agent
+        assert_eq!(
+            config.resolve_skill_alias("goose", dir, "acme--deploy"),
+            "acme--deploy"
+        );
+
+        config.record_rename("cursor", "deploy", "ship");
+        assert_eq!(
+            config.resolve_skill_alias("cursor", dir, "acme--deploy"),
+            "ship"
+        );
+        assert_eq!(config.resolve_skill_alias("cursor", dir, "deploy"), "ship");
+
+        // Renaming back to an old name drops that alias
+        config.record_rename("cursor", "ship", "acme--deploy");
+        assert_eq!(
+            config.resolve_skill_alias("cursor", dir, "acme--deploy"),
+            "acme--deploy"
+        );
+        assert_eq!(
+            config.resolve_skill_alias("cursor", dir, "deploy"),
+            "acme--deploy"
+        );
+
+        config.forget_aliases("cursor", "acme--deploy");
+        assert!(config.aliases.is_empty());
+    }
+
+    #[test]
+    fn test_existing_directory_wins_over_alias() {
+        let mut config = Config::default();
+        let skills = tempfile::tempdir().unwrap();
+        std::fs::create_dir(skills.path().join("deploy")).unwrap();
+        config.record_rename("cursor", "acme--deploy", "deploy");
+        assert_eq!(
+            config.resolve_skill_alias("cursor", skills.path(), "acme--deploy"),
+            "deploy"
+        );
+
+        // Installed again under its old name
+        std::fs::create_dir(skills.path().join("acme--deploy")).unwrap();
+        assert_eq!(
+            config.resolve_skill_alias("cursor", skills.path(), "acme--deploy"),
+            "acme--deploy"
+        );
+    }
 }
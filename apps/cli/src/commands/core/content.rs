@@ -0,0 +1,255 @@
+//! Registry content helpers
+//!
+//! Walks `PakContent` listings from the content API to fetch parts of a pak
+//! without cloning its repository.
+
+use anyhow::{Context, Result, bail};
+use paks_api::{ApiError, ContentItemType, PakContent, PakContentResponse, PaksClient};
+use std::path::Path;
+
+/// Source of pak content by URI (`owner/name[@version][/path]`)
+pub trait ContentSource {
+    async fn fetch(&self, uri: &str) -> Result<PakContentResponse, ApiError>;
+}
+
+impl ContentSource for PaksClient {
+    async fn fetch(&self, uri: &str) -> Result<PakContentResponse, ApiError> {
+        self.get_pak_content(uri).await
+    }
+}
+
+/// Download selected files or subtrees of a pak into `target`
+///
+/// `SKILL.md` is always downloaded so the result is a loadable skill.
+/// Fails if any requested path does not exist in the pak.
+pub async fn download_paths(
+    source: &impl ContentSource,
+    base_uri: &str,
+    paths: &[String],
+    target: &Path,
+) -> Result<()> {
+    let mut selected = vec!["SKILL.md".to_string()];
+    for path in paths {
+        let path = normalize_content_path(path)?;
+        if !selected.contains(&path) {
+            selected.push(path);
+        }
+    }
+
+    std::fs::create_dir_all(target)
+        .with_context(|| format!("Failed to create directory {}", target.display()))?;
+
+    for path in &selected {
+        let uri = format!("{}/{}", base_uri, path);
+        let response = match source.fetch(&uri).await {
+            Ok(response) => response,
+            Err(ApiError::NotFound(_)) => bail!("'{}' does not exist in {}", path, base_uri),
+            Err(e) => bail!("Failed to fetch {}: {}", uri, e),
+        };
+        write_content(source, response.content, &target.join(path)).await?;
+    }
+
+    Ok(())
+}
+
+/// Write a file, or a directory listing and everything below it, to `dest`
+async fn write_content(
+    source: &impl ContentSource,
+    content: PakContent,
+    dest: &Path,
+) -> Result<()> {
+    // Iterative walk to avoid recursive async calls
+    let mut pending = vec![(content, dest.to_path_buf())];
+
+    while let Some((content, dest)) = pending.pop() {
+        match content {
+            PakContent::File { content } => {
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create directory {}", parent.display())
+                    })?;
+                }
+                std::fs::write(&dest, content)
+                    .with_context(|| format!("Failed to write {}", dest.display()))?;
+            }
+            PakContent::Directory { items } => {
+                std::fs::create_dir_all(&dest)
+                    .with_context(|| format!("Failed to create directory {}", dest.display()))?;
+
+                for item in items {
+                    let name = normalize_content_path(&item.name)?;
+                    if name.contains('/') {
+                        bail!("Unexpected nested name '{}' in content listing", item.name);
+                    }
+                    let child = match (item.item_type, item.content) {
+                        // Files may come pre-fetched with the listing
+                        (ContentItemType::File, Some(content)) => PakContent::File { content },
+                        _ => {
+                            source
+                                .fetch(&item.uri)
+                                .await
+                                .with_context(|| format!("Failed to fetch {}", item.uri))?
+                                .content
+                        }
+                    };
+                    pending.push((child, dest.join(name)));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Normalize a relative content path, rejecting anything that could escape
+/// the install directory
+fn normalize_content_path(path: &str) -> Result<String> {
+    let trimmed = path.trim().trim_matches('/');
+    if trimmed.is_empty()
+        || trimmed.contains('\\')
+        || trimmed
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        bail!("Invalid path '{}' in pak", path);
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paks_api::ContentItem;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    /// In-memory content tree keyed by URI
+    struct MockContent(HashMap<String, PakContent>);
+
+    impl ContentSource for MockContent {
+        async fn fetch(&self, uri: &str) -> Result<PakContentResponse, ApiError> {
+            self.0
+                .get(uri)
+                .cloned()
+                .map(|content| PakContentResponse {
+                    uri: uri.to_string(),
+                    content,
+                })
+                .ok_or_else(|| ApiError::NotFound(uri.to_string()))
+        }
+    }
+
+    fn file(content: &str) -> PakContent {
+        PakContent::File {
+            content: content.to_string(),
+        }
+    }
+
+    fn item(base: &str, name: &str, item_type: ContentItemType) -> ContentItem {
+        ContentItem {
+            name: name.to_string(),
+            uri: format!("{}/{}", base, name),
+            item_type,
+            size: None,
+            content: None,
+        }
+    }
+
+    fn mock_tree() -> MockContent {
+        let base = "acme/big-skill@1.0.0";
+        let mut tree = HashMap::new();
+        tree.insert(
+            format!("{}/SKILL.md", base),
+            file("---\nname: big-skill\ndescription: A large reference skill\n---\n"),
+        );
+        tree.insert(
+            format!("{}/references", base),
+            PakContent::Directory {
+                items: vec![
+                    item(
+                        &format!("{}/references", base),
+                        "api.md",
+                        ContentItemType::File,
+                    ),
+                    item(
+                        &format!("{}/references", base),
+                        "guides",
+                        ContentItemType::Dir,
+                    ),
+                ],
+            },
+        );
+        tree.insert(format!("{}/references/api.md", base), file("# API"));
+        tree.insert(
+            format!("{}/references/guides", base),
+            PakContent::Directory {
+                items: vec![ContentItem {
+                    content: Some("# Intro".to_string()),
+                    ..item(
+                        &format!("{}/references/guides", base),
+                        "intro.md",
+                        ContentItemType::File,
+                    )
+                }],
+            },
+        );
+        tree.insert(
+            format!("{}/assets", base),
+            PakContent::Directory {
+                items: vec![item(
+                    &format!("{}/assets", base),
+                    "big.bin",
+                    ContentItemType::File,
+                )],
+            },
+        );
+        tree.insert(format!("{}/assets/big.bin", base), file("binary"));
+        MockContent(tree)
+    }
+
+    #[tokio::test]
+    async fn test_download_only_selected_subtree() {
+        let target = tempdir().unwrap();
+        download_paths(
+            &mock_tree(),
+            "acme/big-skill@1.0.0",
+            &["references/".to_string()],
+            target.path(),
+        )
+        .await
+        .unwrap();
+
+        assert!(target.path().join("SKILL.md").is_file());
+        assert_eq!(
+            std::fs::read_to_string(target.path().join("references/api.md")).unwrap(),
+            "# API"
+        );
+        assert!(target.path().join("references/guides/intro.md").is_file());
+        assert!(!target.path().join("assets").exists());
+    }
+
+    #[tokio::test]
+    async fn test_download_missing_subtree_errors() {
+        let target = tempdir().unwrap();
+        let err = download_paths(
+            &mock_tree(),
+            "acme/big-skill@1.0.0",
+            &["scripts".to_string()],
+            target.path(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("'scripts' does not exist"));
+    }
+
+    #[test]
+    fn test_normalize_content_path_rejects_escapes() {
+        assert_eq!(
+            normalize_content_path("/references/").unwrap(),
+            "references"
+        );
+        assert!(normalize_content_path("../secrets").is_err());
+        assert!(normalize_content_path("a/./b").is_err());
+        assert!(normalize_content_path("").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Bounded fan-out of registry requests
+//!
+//! Commands that query the registry once per skill run their requests
+//! through [`FanOut`], which caps how many are in flight, can space them out
+//! with a small delay, and backs off when the registry rate-limits us.
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use paks_api::ApiError;
+use paks_api::client::MAX_RETRY_AFTER;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::time::Duration;
+
+use super::config::Config;
+
+/// Requests in flight at once unless configured otherwise
+pub const DEFAULT_JOBS: usize = 4;
+
+/// Times a single request is retried after being rate limited
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Wait after a 429 without a `Retry-After` header
+const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(1);
+
+/// Concurrency limit and politeness delay for bulk registry queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FanOut {
+    jobs: usize,
+    delay: Duration,
+}
+
+impl FanOut {
+    pub fn new(jobs: usize, delay: Duration) -> Self {
+        Self {
+            jobs: jobs.max(1),
+            delay,
+        }
+    }
+
+    /// Limits from a `--jobs` flag, falling back to the config
+    pub fn from_config(config: &Config, jobs: Option<usize>) -> Self {
+        Self::new(
+            jobs.or(config.jobs).unwrap_or(DEFAULT_JOBS),
+            Duration::from_millis(config.request_delay_ms.unwrap_or(0)),
+        )
+    }
+
+    /// Run `task` for every item, returning results in input order
+    ///
+    /// At most `jobs` tasks run at once, each started after the configured
+    /// delay. A task that is rate limited is retried after the registry's
+    /// `Retry-After` and halves the concurrency for the rest of the run.
+    pub async fn run<T, R, F, Fut>(&self, items: Vec<T>, task: F) -> Vec<Result<R, ApiError>>
+    where
+        T: Clone,
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<R, ApiError>>,
+    {
+        let mut results: Vec<Option<Result<R, ApiError>>> = Vec::new();
+        results.resize_with(items.len(), || None);
+
+        // (index, item, attempt, wait before starting)
+        let mut pending: VecDeque<(usize, T, u32, Duration)> = items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| (i, item, 0, Duration::ZERO))
+            .collect();
+        let mut in_flight = FuturesUnordered::new();
+        let mut limit = self.jobs;
+
+        loop {
+            while in_flight.len() < limit
+                && let Some((i, item, attempt, wait)) = pending.pop_front()
+            {
+                let delay = self.delay + wait;
+                let task = &task;
+                in_flight.push(async move {
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                    let result = task(item.clone()).await;
+                    (i, item, attempt, result)
+                });
+            }
+
+            let Some((i, item, attempt, result)) = in_flight.next().await else {
+                break;
+            };
+            // The client may already have retried; back off here as well
+            // so the whole batch slows down
+            let rate_limit = match &result {
+                Err(e) => match e.root() {
+                    ApiError::RateLimited { retry_after } => Some(*retry_after),
+                    _ => None,
+                },
+                Ok(_) => None,
+            };
+            // A wait longer than the client would accept fails the item
+            let wait = rate_limit.map(|retry_after| {
+                retry_after
+                    .map(Duration::from_secs)
+                    .unwrap_or(DEFAULT_RATE_LIMIT_WAIT)
+            });
+            match wait {
+                Some(wait) if attempt < MAX_RATE_LIMIT_RETRIES && wait <= MAX_RETRY_AFTER => {
+                    limit = (limit / 2).max(1);
+                    pending.push_front((i, item, attempt + 1, wait));
+                }
+                _ => results[i] = Some(result),
+            }
+        }
+
+        results.into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Tracks how many tasks run at once
+    #[derive(Default)]
+    struct Counter {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    impl Counter {
+        async fn track<R>(&self, work: impl Future<Output = R>) -> R {
+            let now = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max.fetch_max(now, Ordering::SeqCst);
+            let result = work.await;
+            self.current.fetch_sub(1, Ordering::SeqCst);
+            result
+        }
+    }
+
+    #[tokio::test]
+    async fn test_never_exceeds_jobs() {
+        let counter = Counter::default();
+        let fanout = FanOut::new(3, Duration::ZERO);
+
+        let results = fanout
+            .run((0..20).collect(), |n: u64| {
+                counter.track(async move {
+                    tokio::time::sleep(Duration::from_millis(5 + n % 3)).await;
+                    Ok::<_, ApiError>(n * 2)
+                })
+            })
+            .await;
+
+        assert_eq!(counter.max.load(Ordering::SeqCst), 3);
+        let values: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, (0..20).map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_retries_and_reduces_concurrency() {
+        let counter = &Counter::default();
+        let attempts = AtomicUsize::new(0);
+        let at_retry = &AtomicUsize::new(0);
+        let fanout = FanOut::new(4, Duration::ZERO);
+
+        let results = fanout
+            .run((0..12).collect(), |n: u64| {
+                let first_attempt = n == 0 && attempts.fetch_add(1, Ordering::SeqCst) == 0;
+                counter.track(async move {
+                    if n == 0 && !first_attempt {
+                        at_retry.store(counter.current.load(Ordering::SeqCst), Ordering::SeqCst);
+                    }
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    if first_attempt {
+                        Err(ApiError::RateLimited {
+                            retry_after: Some(0),
+                        })
+                    } else {
+                        Ok(n)
+                    }
+                })
+            })
+            .await;
+
+        let values: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, (0..12).collect::<Vec<_>>());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        // The retry runs with the concurrency halved from 4 to 2
+        assert!(at_retry.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_repeated_rate_limits() {
+        let fanout = FanOut::new(2, Duration::ZERO);
+        let results = fanout
+            .run(vec![1u8], |_| async {
+                Err::<(), _>(ApiError::RateLimited {
+                    retry_after: Some(0),
+                })
+            })
+            .await;
+
+        assert!(matches!(results[0], Err(ApiError::RateLimited { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_fails_at_once() {
+        let attempts = AtomicUsize::new(0);
+        let fanout = FanOut::new(2, Duration::ZERO);
+        let results = fanout
+            .run(vec![1u8], |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async {
+                    Err::<(), _>(ApiError::RateLimited {
+                        retry_after: Some(86400),
+                    })
+                }
+            })
+            .await;
+
+        assert!(matches!(results[0], Err(ApiError::RateLimited { .. })));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
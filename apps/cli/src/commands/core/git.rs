@@ -1,4 +1,4 @@
-//! Git helper functions for publish command
+//! Git helper functions for publish and install commands
 
 use anyhow::{Result, bail};
 use std::path::Path;
@@ -6,6 +6,11 @@ use std::process::Command;
 
 /// Execute a git command and return stdout
 pub fn git_cmd(args: &[&str], path: &Path) -> Result<String> {
+    Ok(git_output(args, path)?.trim().to_string())
+}
+
+/// Execute a git command and return stdout untrimmed, for column-sensitive output
+fn git_output(args: &[&str], path: &Path) -> Result<String> {
     let output = Command::new("git").args(args).current_dir(path).output()?;
 
     if !output.status.success() {
@@ -13,7 +18,59 @@ pub fn git_cmd(args: &[&str], path: &Path) -> Result<String> {
         bail!("git {} failed: {}", args.join(" "), stderr.trim());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Commit `git_ref` (or `HEAD`) points at in the remote repository
+///
+/// Annotated tags are peeled to their commit. Returns `None` if the remote
+/// can't be reached or doesn't have the ref.
+pub fn remote_commit(url: &str, git_ref: Option<&str>) -> Option<String> {
+    let output = Command::new("git")
+        .args(["ls-remote", url, git_ref.unwrap_or("HEAD")])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_ls_remote(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Pick the commit from `git ls-remote` output, preferring a peeled tag (`^{}`)
+fn parse_ls_remote(output: &str) -> Option<String> {
+    let refs: Vec<(&str, &str)> = output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+    refs.iter()
+        .find(|(_, name)| name.ends_with("^{}"))
+        .or_else(|| refs.first())
+        .map(|(sha, _)| sha.to_string())
+}
+
+/// Installed git version as `(major, minor)`, if git is available
+pub fn git_version() -> Option<(u32, u32)> {
+    let output = Command::new("git").arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_git_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `git --version` output such as `git version 2.39.5 (Apple Git-154)`
+fn parse_git_version(output: &str) -> Option<(u32, u32)> {
+    let version = output.trim().strip_prefix("git version ")?;
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Whether the installed git supports partial clones with cone-mode
+/// sparse-checkout (git 2.27+)
+pub fn supports_sparse_checkout() -> bool {
+    git_version().is_some_and(|v| v >= (2, 27))
 }
 
 /// Check if path is inside a git repository
@@ -40,6 +97,14 @@ pub fn get_current_branch(path: &Path) -> Result<String> {
     Ok(branch)
 }
 
+/// Check whether `rev` is on `branch`, falling back to `<remote>/<branch>`
+/// when there's no local branch (as in most CI checkouts)
+pub fn is_on_branch(path: &Path, rev: &str, branch: &str, remote: &str) -> bool {
+    [branch.to_string(), format!("{}/{}", remote, branch)]
+        .iter()
+        .any(|b| git_cmd(&["merge-base", "--is-ancestor", rev, b], path).is_ok())
+}
+
 /// Check if a tag exists locally
 pub fn tag_exists(path: &Path, tag: &str) -> bool {
     git_cmd(&["tag", "-l", tag], path)
@@ -84,16 +149,72 @@ pub fn get_pak_path_in_repo(pak_path: &Path) -> Result<String> {
 
 /// Check for uncommitted changes in a directory (staged + unstaged + untracked)
 /// Returns a list of changed files relative to the directory
-pub fn get_uncommitted_changes(path: &Path) -> Result<Vec<String>> {
+pub fn get_uncommitted_changes(path: &Path) -> Result<Vec<ChangeEntry>> {
     // When running git status from within the target directory,
     // use "." to check the current directory and its subdirectories
-    let output = git_cmd(&["status", "--porcelain", "."], path)?;
+    let output = git_output(&["status", "--porcelain", "."], path)?;
 
-    if output.is_empty() {
-        return Ok(vec![]);
+    Ok(output.lines().filter_map(parse_porcelain_line).collect())
+}
+
+/// Kind of an uncommitted change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+    Renamed { from: String },
+}
+
+/// One entry of `git status --porcelain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeEntry {
+    pub status: ChangeKind,
+    pub path: String,
+}
+
+impl std::fmt::Display for ChangeEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.status {
+            ChangeKind::Modified => write!(f, "modified: {}", self.path),
+            ChangeKind::Added => write!(f, "new: {}", self.path),
+            ChangeKind::Deleted => write!(f, "deleted: {}", self.path),
+            ChangeKind::Untracked => write!(f, "untracked: {}", self.path),
+            ChangeKind::Renamed { from } => write!(f, "renamed: {} -> {}", from, self.path),
+        }
     }
+}
 
-    Ok(output.lines().map(|s| s.to_string()).collect())
+/// Parse a porcelain v1 line such as ` M SKILL.md` or `R  old -> new`
+///
+/// The staged (X) status wins over the worktree (Y) status when both are set.
+fn parse_porcelain_line(line: &str) -> Option<ChangeEntry> {
+    let code = line.get(..2)?;
+    let path = line.get(3..)?;
+    let unquote = |p: &str| p.trim_matches('"').to_string();
+
+    let kind = code.chars().find(|c| *c != ' ')?;
+    let status = match (code, kind) {
+        ("??", _) => ChangeKind::Untracked,
+        (_, 'R' | 'C') => {
+            let (from, to) = path.split_once(" -> ")?;
+            return Some(ChangeEntry {
+                status: ChangeKind::Renamed {
+                    from: unquote(from),
+                },
+                path: unquote(to),
+            });
+        }
+        (_, 'A') => ChangeKind::Added,
+        (_, 'D') => ChangeKind::Deleted,
+        _ => ChangeKind::Modified,
+    };
+
+    Some(ChangeEntry {
+        status,
+        path: unquote(path),
+    })
 }
 
 #[cfg(test)]
@@ -101,6 +222,76 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_parse_git_version() {
+        assert_eq!(parse_git_version("git version 2.39.5\n"), Some((2, 39)));
+        assert_eq!(
+            parse_git_version("git version 2.37.1 (Apple Git-137.1)"),
+            Some((2, 37))
+        );
+        assert_eq!(
+            parse_git_version("git version 2.45.1.windows.1"),
+            Some((2, 45))
+        );
+        assert_eq!(parse_git_version("not git"), None);
+    }
+
+    #[test]
+    fn test_parse_ls_remote_prefers_peeled_tag() {
+        let output = "1111111111111111111111111111111111111111\trefs/tags/v1.0.0\n\
+                      2222222222222222222222222222222222222222\trefs/tags/v1.0.0^{}\n";
+        assert_eq!(
+            parse_ls_remote(output).as_deref(),
+            Some("2222222222222222222222222222222222222222")
+        );
+        assert_eq!(
+            parse_ls_remote("3333333333333333333333333333333333333333\tHEAD\n").as_deref(),
+            Some("3333333333333333333333333333333333333333")
+        );
+        assert_eq!(parse_ls_remote(""), None);
+    }
+
+    #[test]
+    fn test_parse_porcelain_lines() {
+        let parse = |line| parse_porcelain_line(line).unwrap();
+
+        assert_eq!(
+            parse(" M SKILL.md"),
+            ChangeEntry {
+                status: ChangeKind::Modified,
+                path: "SKILL.md".to_string()
+            }
+        );
+        assert_eq!(parse("A  scripts/run.sh").status, ChangeKind::Added);
+        assert_eq!(parse(" D old.md").status, ChangeKind::Deleted);
+        assert_eq!(parse("AM notes.md").status, ChangeKind::Added);
+        assert_eq!(
+            parse("?? \"new file.md\""),
+            ChangeEntry {
+                status: ChangeKind::Untracked,
+                path: "new file.md".to_string()
+            }
+        );
+        assert_eq!(
+            parse("R  references/a.md -> references/b.md"),
+            ChangeEntry {
+                status: ChangeKind::Renamed {
+                    from: "references/a.md".to_string()
+                },
+                path: "references/b.md".to_string()
+            }
+        );
+        assert_eq!(parse_porcelain_line(""), None);
+    }
+
+    #[test]
+    fn test_change_entry_display() {
+        let entry = parse_porcelain_line("?? draft.md").unwrap();
+        assert_eq!(entry.to_string(), "untracked: draft.md");
+        let entry = parse_porcelain_line("R  a.md -> b.md").unwrap();
+        assert_eq!(entry.to_string(), "renamed: a.md -> b.md");
+    }
+
     #[test]
     fn test_is_git_repo_false() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,275 @@
+//! Skill lifecycle hooks
+//!
+//! A skill can name a script in its SKILL.md metadata to run after it is
+//! installed (`postinstall`) or before it is removed (`preremove`):
+//!
+//! ```yaml
+//! metadata:
+//!   postinstall: scripts/setup.sh
+//! ```
+//!
+//! Hooks never run without consent, given either with `--run-hooks` or at a
+//! prompt. Scripts run from the skill directory and must live inside it; a
+//! hook naming any other script is skipped with a warning.
+
+use anyhow::{Context, Result, bail};
+use dialoguer::Confirm;
+use std::io::IsTerminal;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use super::skill::Skill;
+
+/// When a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    PostInstall,
+    PreRemove,
+}
+
+impl Hook {
+    /// SKILL.md metadata key naming the hook's script
+    pub fn key(self) -> &'static str {
+        match self {
+            Hook::PostInstall => "postinstall",
+            Hook::PreRemove => "preremove",
+        }
+    }
+}
+
+/// What happened to a hook
+#[derive(Debug, PartialEq, Eq)]
+pub enum HookOutcome {
+    /// The skill declares no such hook
+    NotDeclared,
+    /// Consent wasn't given, so the hook was skipped
+    Declined,
+    /// The declared script can't be run, so the hook was skipped
+    Invalid,
+    Ran,
+}
+
+/// Script the skill at `skill_dir` declares for `hook`, if any
+fn declared_script(skill_dir: &Path, hook: Hook) -> Option<String> {
+    let skill = Skill::load(skill_dir).ok()?;
+    skill
+        .frontmatter
+        .metadata?
+        .remove(hook.key())
+        .filter(|script| !script.trim().is_empty())
+}
+
+/// Resolve a hook's `script` to a file inside `skill_dir`
+///
+/// Absolute paths, `..` components and symlinks leading out of the skill
+/// are rejected.
+fn resolve_script(skill_dir: &Path, script: &str) -> Result<PathBuf> {
+    let relative = Path::new(script.trim());
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!(
+            "Hook script '{}' must be a relative path inside the skill",
+            script
+        );
+    }
+
+    let root = skill_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", skill_dir.display()))?;
+    let path = skill_dir
+        .join(relative)
+        .canonicalize()
+        .with_context(|| format!("Hook script '{}' not found in the skill", script))?;
+    if !path.starts_with(&root) {
+        bail!("Hook script '{}' resolves outside of the skill", script);
+    }
+    if !path.is_file() {
+        bail!("Hook script '{}' is not a file", script);
+    }
+    Ok(path)
+}
+
+/// Run the skill's `hook`, if it declares one and consent is given
+///
+/// `run_hooks` is consent given up front; otherwise the user is asked, and
+/// without a terminal to ask on the hook is skipped.
+pub fn run(skill_dir: &Path, hook: Hook, run_hooks: bool) -> Result<HookOutcome> {
+    run_with(skill_dir, hook, run_hooks, prompt)
+}
+
+/// [`run`] with the consent prompt passed in
+fn run_with(
+    skill_dir: &Path,
+    hook: Hook,
+    run_hooks: bool,
+    confirm: impl FnOnce(Hook, &str) -> Result<bool>,
+) -> Result<HookOutcome> {
+    let Some(script) = declared_script(skill_dir, hook) else {
+        return Ok(HookOutcome::NotDeclared);
+    };
+
+    if !run_hooks && !confirm(hook, &script)? {
+        println!(
+            "  Skipped {} hook ({}); pass --run-hooks to run it",
+            hook.key(),
+            script
+        );
+        return Ok(HookOutcome::Declined);
+    }
+
+    // A broken hook mustn't get in the way of installing or removing the skill
+    let path = match resolve_script(skill_dir, &script) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("⚠ Warning: Skipped {} hook: {:#}", hook.key(), e);
+            return Ok(HookOutcome::Invalid);
+        }
+    };
+
+    println!("  Running {} hook ({})...", hook.key(), script);
+    let status = script_command(&path)
+        .current_dir(skill_dir)
+        .env("PAKS_SKILL_DIR", skill_dir)
+        .status()
+        .with_context(|| format!("Failed to run {} hook {}", hook.key(), script))?;
+    if !status.success() {
+        bail!("{} hook {} failed ({})", hook.key(), script, status);
+    }
+    Ok(HookOutcome::Ran)
+}
+
+/// Command running `path`: directly if executable, otherwise with `sh`
+fn script_command(path: &Path) -> Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let executable = path
+            .metadata()
+            .is_ok_and(|m| m.permissions().mode() & 0o111 != 0);
+        if !executable {
+            let mut cmd = Command::new("sh");
+            cmd.arg(path);
+            return cmd;
+        }
+    }
+    Command::new(path)
+}
+
+/// Ask whether to run a hook; declines without a terminal
+fn prompt(hook: Hook, script: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    Ok(Confirm::new()
+        .with_prompt(format!("Run the skill's {} hook ({})?", hook.key(), script))
+        .default(false)
+        .interact()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    /// Skill declaring `script` as its postinstall hook, with a script that
+    /// leaves a `ran` marker behind
+    fn skill_with_hook(script: &str) -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            format!(
+                "---\nname: hooked\ndescription: d\nmetadata:\n  postinstall: {}\n---\n",
+                script
+            ),
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("scripts/setup.sh"), "touch ran\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_hook_needs_consent() {
+        let skill = skill_with_hook("scripts/setup.sh");
+
+        let outcome = run_with(skill.path(), Hook::PostInstall, false, |hook, script| {
+            assert_eq!(hook, Hook::PostInstall);
+            assert_eq!(script, "scripts/setup.sh");
+            Ok(false)
+        })
+        .unwrap();
+        assert_eq!(outcome, HookOutcome::Declined);
+        assert!(!skill.path().join("ran").exists());
+
+        let outcome = run_with(skill.path(), Hook::PostInstall, false, |_, _| Ok(true)).unwrap();
+        assert_eq!(outcome, HookOutcome::Ran);
+        assert!(skill.path().join("ran").exists());
+    }
+
+    #[test]
+    fn test_run_hooks_skips_prompt() {
+        let skill = skill_with_hook("scripts/setup.sh");
+        let outcome = run_with(skill.path(), Hook::PostInstall, true, |_, _| {
+            panic!("--run-hooks should not prompt")
+        })
+        .unwrap();
+        assert_eq!(outcome, HookOutcome::Ran);
+        assert!(skill.path().join("ran").exists());
+
+        // Only the declared hook runs
+        let outcome = run_with(skill.path(), Hook::PreRemove, true, |_, _| Ok(true)).unwrap();
+        assert_eq!(outcome, HookOutcome::NotDeclared);
+    }
+
+    #[test]
+    fn test_hook_path_must_stay_in_skill() {
+        let outside = tempdir().unwrap();
+        let escape = outside.path().join("evil.sh");
+        std::fs::write(&escape, "touch pwned\n").unwrap();
+
+        for script in ["../evil.sh", escape.to_str().unwrap(), "scripts/missing.sh"] {
+            let skill = skill_with_hook(script);
+            let outcome = run_with(skill.path(), Hook::PostInstall, true, |_, _| Ok(true)).unwrap();
+            assert_eq!(outcome, HookOutcome::Invalid, "{}", script);
+            assert!(resolve_script(skill.path(), script).is_err());
+        }
+        assert!(
+            resolve_script(Path::new("."), "../evil.sh")
+                .unwrap_err()
+                .to_string()
+                .contains("relative path")
+        );
+
+        #[cfg(unix)]
+        {
+            let skill = skill_with_hook("scripts/link.sh");
+            std::os::unix::fs::symlink(&escape, skill.path().join("scripts/link.sh")).unwrap();
+            let outcome = run_with(skill.path(), Hook::PostInstall, true, |_, _| Ok(true)).unwrap();
+            assert_eq!(outcome, HookOutcome::Invalid);
+            let err = resolve_script(skill.path(), "scripts/link.sh").unwrap_err();
+            assert!(err.to_string().contains("outside of the skill"), "{}", err);
+        }
+        assert!(!outside.path().join("pwned").exists());
+    }
+
+    #[test]
+    fn test_consent_is_asked_before_resolving() {
+        // Declining a broken hook is just a decline, not an error
+        let skill = skill_with_hook("../evil.sh");
+        let outcome = run_with(skill.path(), Hook::PostInstall, false, |_, _| Ok(false)).unwrap();
+        assert_eq!(outcome, HookOutcome::Declined);
+    }
+
+    #[test]
+    fn test_failing_hook_is_an_error() {
+        let skill = skill_with_hook("scripts/fail.sh");
+        std::fs::write(skill.path().join("scripts/fail.sh"), "exit 3\n").unwrap();
+        let err = run_with(skill.path(), Hook::PostInstall, true, |_, _| Ok(true)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("postinstall hook scripts/fail.sh failed")
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Registry tokens in the OS keychain
+//!
+//! Tokens are stored under the service `paks` with the registry name as the
+//! account, and `config.toml` only records that the keychain holds them.
+//!
+//! Keychain access requires the `keychain` Cargo feature (enabled by default).
+//! Without it, or when no keychain service is running, every operation fails
+//! and tokens fall back to `config.toml` if `plaintext_token_fallback` allows.
+
+use anyhow::Result;
+#[cfg(not(feature = "keychain"))]
+use anyhow::bail;
+
+/// Keychain service name tokens are stored under
+#[cfg(feature = "keychain")]
+const SERVICE: &str = "paks";
+
+/// Where registry tokens are kept, keyed by registry name
+pub trait SecretStore {
+    /// The token for `account`, or `None` if none is stored
+    fn get(&self, account: &str) -> Result<Option<String>>;
+
+    fn set(&self, account: &str, secret: &str) -> Result<()>;
+
+    /// Remove the token for `account`; removing a missing token succeeds
+    fn delete(&self, account: &str) -> Result<()>;
+}
+
+/// The platform keychain (macOS Keychain, Windows Credential Manager or the
+/// Secret Service on Linux)
+pub struct OsKeychain;
+
+#[cfg(feature = "keychain")]
+impl SecretStore for OsKeychain {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        match keyring::Entry::new(SERVICE, account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        Ok(keyring::Entry::new(SERVICE, account)?.set_password(secret)?)
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        match keyring::Entry::new(SERVICE, account)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(not(feature = "keychain"))]
+impl SecretStore for OsKeychain {
+    fn get(&self, _account: &str) -> Result<Option<String>> {
+        unsupported()
+    }
+
+    fn set(&self, _account: &str, _secret: &str) -> Result<()> {
+        unsupported()
+    }
+
+    fn delete(&self, _account: &str) -> Result<()> {
+        unsupported()
+    }
+}
+
+#[cfg(not(feature = "keychain"))]
+fn unsupported<T>() -> Result<T> {
+    bail!("This build of paks does not support the OS keychain (enable the `keychain` feature)")
+}
+
+/// In-memory [`SecretStore`] for tests; `unavailable` makes every call fail
+/// like a missing keychain service
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    pub secrets: std::cell::RefCell<std::collections::HashMap<String, String>>,
+    pub unavailable: bool,
+}
+
+#[cfg(test)]
+impl MemoryStore {
+    pub fn unavailable() -> Self {
+        Self {
+            unavailable: true,
+            ..Self::default()
+        }
+    }
+
+    fn check(&self) -> Result<()> {
+        if self.unavailable {
+            anyhow::bail!("no keychain service");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl SecretStore for MemoryStore {
+    fn get(&self, account: &str) -> Result<Option<String>> {
+        self.check()?;
+        Ok(self.secrets.borrow().get(account).cloned())
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<()> {
+        self.check()?;
+        self.secrets
+            .borrow_mut()
+            .insert(account.to_string(), secret.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        self.check()?;
+        self.secrets.borrow_mut().remove(account);
+        Ok(())
+    }
+}
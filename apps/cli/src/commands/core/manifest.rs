@@ -0,0 +1,252 @@
+//! Project manifest (`paks.toml`) and lockfile (`paks.lock`)
+//!
+//! A project declares the skills it depends on in `paks.toml`:
+//!
+//! ```toml
+//! [skills]
+//! "acme/deploy" = { version = "^1.2.0", source = "registry" }
+//! ```
+//!
+//! `paks.lock` records exactly what was installed for each entry. Every
+//! skills directory also gets a `paks.lock` of its own, covering all the
+//! skills installed there, which `paks install --frozen` reinstalls from.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Project manifest file name
+pub const MANIFEST_FILE: &str = "paks.toml";
+
+/// Project lockfile file name
+pub const LOCK_FILE: &str = "paks.lock";
+
+/// A skill dependency as declared in `paks.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencySpec {
+    /// Version constraint (e.g. `^1.2.0`)
+    pub version: String,
+    /// Where the skill comes from: `registry`, a git URL, or a local path
+    pub source: String,
+}
+
+impl DependencySpec {
+    /// Whether the constraint holds the skill at one exact version (`=1.2.0`)
+    pub fn is_pinned(&self) -> bool {
+        self.version.trim_start().starts_with('=')
+    }
+}
+
+/// A resolved skill as recorded in `paks.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    pub name: String,
+    /// Exact installed version
+    pub version: String,
+    pub source: String,
+    /// Commit the skill was installed from, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Checksum of the installed files, as computed by `checksum::compute`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Lockfile {
+    #[serde(default, rename = "skill")]
+    skills: Vec<LockedSkill>,
+}
+
+/// Find the nearest directory at or above `start` containing `paks.toml`
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(MANIFEST_FILE).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Read the `[skills]` table of the `paks.toml` in `root`
+pub fn load_dependencies(root: &Path) -> Result<BTreeMap<String, DependencySpec>> {
+    #[derive(Deserialize)]
+    struct Manifest {
+        #[serde(default)]
+        skills: BTreeMap<String, DependencySpec>,
+    }
+
+    let path = root.join(MANIFEST_FILE);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest: Manifest =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(manifest.skills)
+}
+
+/// Read the entries of the `paks.lock` in `root`; empty if there is none
+pub fn load_lock(root: &Path) -> Result<Vec<LockedSkill>> {
+    let path = root.join(LOCK_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            let lockfile: Lockfile = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            Ok(lockfile.skills)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Create an empty `paks.toml` in `dir`
+pub fn init(dir: &Path) -> Result<PathBuf> {
+    let path = dir.join(MANIFEST_FILE);
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    std::fs::write(&path, "[skills]\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Add or update a dependency in `paks.toml` and its entry in `paks.lock`
+///
+/// Only the dependency's own entry changes: other keys, entries, comments
+/// and formatting are left as they are, and saving the same dependency again
+/// produces identical files.
+pub fn save_dependency(root: &Path, spec: &DependencySpec, locked: &LockedSkill) -> Result<()> {
+    let manifest_path = root.join(MANIFEST_FILE);
+    let content = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+    let mut manifest: toml_edit::DocumentMut = content
+        .parse()
+        .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+    let skills = manifest.entry("skills").or_insert_with(toml_edit::table);
+    let Some(skills) = skills.as_table_mut() else {
+        bail!("'skills' in {} must be a table", manifest_path.display());
+    };
+    let entry = toml_edit::ser::to_document(spec)?.as_table().clone();
+    skills.insert(&locked.name, toml_edit::Item::Table(entry));
+
+    std::fs::write(&manifest_path, manifest.to_string())
+        .with_context(|| format!("Failed to write {}", manifest_path.display()))?;
+
+    save_locked(root, locked)
+}
+
+/// Add or update one entry of the `paks.lock` in `dir`, creating it if needed
+pub fn save_locked(dir: &Path, locked: &LockedSkill) -> Result<()> {
+    let mut skills = load_lock(dir)?;
+    skills.retain(|s| s.name != locked.name);
+    skills.push(locked.clone());
+    write_lock(dir, skills)
+}
+
+/// Replace the `paks.lock` in `dir` with `skills`, sorted by name
+pub fn write_lock(dir: &Path, mut skills: Vec<LockedSkill>) -> Result<()> {
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    let lock_path = dir.join(LOCK_FILE);
+    std::fs::write(&lock_path, toml::to_string_pretty(&Lockfile { skills })?)
+        .with_context(|| format!("Failed to write {}", lock_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn dependency(name: &str, version: &str) -> (DependencySpec, LockedSkill) {
+        (
+            DependencySpec {
+                version: format!("^{}", version),
+                source: "registry".to_string(),
+            },
+            LockedSkill {
+                name: name.to_string(),
+                version: version.to_string(),
+                source: "registry".to_string(),
+                commit: Some("0123abcd".to_string()),
+                checksum: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_load_dependencies_pinned() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(MANIFEST_FILE),
+            "[skills]\n\"acme/deploy\" = { version = \"=1.2.0\", source = \"registry\" }\n\
+             \"acme/lint\" = { version = \"^2.0.0\", source = \"registry\" }\n",
+        )
+        .unwrap();
+
+        let deps = load_dependencies(dir.path()).unwrap();
+        assert!(deps["acme/deploy"].is_pinned());
+        assert!(!deps["acme/lint"].is_pinned());
+    }
+
+    #[test]
+    fn test_save_dependency_is_idempotent() {
+        let dir = tempdir().unwrap();
+        init(dir.path()).unwrap();
+        let (spec, locked) = dependency("acme/deploy", "1.2.0");
+
+        save_dependency(dir.path(), &spec, &locked).unwrap();
+        let manifest = std::fs::read_to_string(dir.path().join(MANIFEST_FILE)).unwrap();
+        let lock = std::fs::read_to_string(dir.path().join(LOCK_FILE)).unwrap();
+
+        save_dependency(dir.path(), &spec, &locked).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(MANIFEST_FILE)).unwrap(),
+            manifest
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(LOCK_FILE)).unwrap(),
+            lock
+        );
+        assert!(manifest.contains("[skills.\"acme/deploy\"]"));
+        assert!(manifest.contains("version = \"^1.2.0\""));
+        assert_eq!(load_lock(dir.path()).unwrap(), vec![locked]);
+    }
+
+    #[test]
+    fn test_save_dependency_preserves_existing_entries() {
+        let dir = tempdir().unwrap();
+        let original = "name = \"my-project\"\n\n\
+                        [skills]\n\
+                        # Pinned until the new rules land\n\
+                        \"acme/lint\"   =   {version=\"=0.3.0\",source=\"registry\"}\n";
+        std::fs::write(dir.path().join(MANIFEST_FILE), original).unwrap();
+
+        let (spec, locked) = dependency("acme/deploy", "1.2.0");
+        save_dependency(dir.path(), &spec, &locked).unwrap();
+        let (spec, locked) = dependency("acme/deploy", "1.3.0");
+        save_dependency(dir.path(), &spec, &locked).unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(MANIFEST_FILE)).unwrap();
+        assert_eq!(
+            content,
+            format!(
+                "{}\n[skills.\"acme/deploy\"]\nversion = \"^1.3.0\"\nsource = \"registry\"\n",
+                original
+            )
+        );
+
+        let lock: Lockfile =
+            toml::from_str(&std::fs::read_to_string(dir.path().join(LOCK_FILE)).unwrap()).unwrap();
+        assert_eq!(lock.skills.len(), 1);
+        assert_eq!(lock.skills[0].version, "1.3.0");
+    }
+
+    #[test]
+    fn test_find_project_root_walks_up() {
+        let dir = tempdir().unwrap();
+        init(dir.path()).unwrap();
+        let nested = dir.path().join("a/b");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_project_root(&nested).as_deref(), Some(dir.path()));
+        assert!(init(dir.path()).is_err());
+    }
+}
@@ -1,5 +1,27 @@
 //! Core types and configuration for paks CLI
 
+pub mod archive;
+pub mod cache;
+pub mod checksum;
+pub mod client;
 pub mod config;
+pub mod content;
+pub mod fanout;
 pub mod git;
+pub mod hooks;
+pub mod keychain;
+pub mod manifest;
+pub mod offline;
+pub mod output;
+pub mod paksignore;
+pub mod paths;
+pub mod signature;
+pub mod size;
 pub mod skill;
+pub mod spdx;
+pub mod style;
+pub mod suggest;
+pub mod token;
+pub mod update;
+
+pub use paks_core::spec;
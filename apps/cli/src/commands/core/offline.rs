@@ -0,0 +1,62 @@
+//! Offline mode
+//!
+//! With the global `--offline` flag or `PAKS_OFFLINE=1`, paks makes no
+//! network requests: registry clients fail every call with
+//! `ApiError::Offline`, and installs are served from the clone cache only.
+
+use anyhow::{Result, bail};
+use std::ffi::OsString;
+use std::sync::OnceLock;
+
+static OFFLINE: OnceLock<bool> = OnceLock::new();
+
+/// Decide whether to run offline from the `--offline` flag and the value of
+/// `PAKS_OFFLINE`
+///
+/// An empty value, `0` or `false` leaves offline mode off.
+pub fn resolve(offline_flag: bool, offline_env: Option<OsString>) -> bool {
+    let env_enabled = offline_env.is_some_and(|v| {
+        let v = v.to_string_lossy();
+        !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false")
+    });
+    offline_flag || env_enabled
+}
+
+/// Set offline mode for the process from the global `--offline` flag
+///
+/// Must be called before any client is built; later calls are ignored.
+pub fn init(offline_flag: bool) {
+    OFFLINE.get_or_init(|| resolve(offline_flag, std::env::var_os("PAKS_OFFLINE")));
+}
+
+/// Whether network access is disabled
+pub fn is_offline() -> bool {
+    *OFFLINE.get_or_init(|| resolve(false, std::env::var_os("PAKS_OFFLINE")))
+}
+
+/// Fail if offline, for commands that can't do anything without the network
+pub fn ensure_online(what: &str) -> Result<()> {
+    if is_offline() {
+        bail!(
+            "{} needs network access, which is disabled (--offline or PAKS_OFFLINE)",
+            what
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_honors_env() {
+        assert!(!resolve(false, None));
+        assert!(resolve(true, None));
+        assert!(resolve(false, Some("1".into())));
+        assert!(resolve(false, Some("yes".into())));
+        assert!(!resolve(false, Some("".into())));
+        assert!(!resolve(false, Some("0".into())));
+        assert!(!resolve(false, Some("FALSE".into())));
+    }
+}
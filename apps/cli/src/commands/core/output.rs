@@ -0,0 +1,19 @@
+//! Output formats shared by commands that can print structured data
+
+/// How a command prints its results
+///
+/// `Table` is for people and may be colored; `Json` and `Yaml` are for
+/// scripts and go to stdout without styling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Whether this is a machine-readable format
+    pub fn is_structured(self) -> bool {
+        !matches!(self, OutputFormat::Table)
+    }
+}
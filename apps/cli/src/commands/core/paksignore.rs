@@ -0,0 +1,86 @@
+//! `.paksignore` support
+//!
+//! Skills can list files that should not be treated as part of the skill in a
+//! `.paksignore` file using gitignore syntax. Ignored files are left out of
+//! installs, checksums and the publish checks.
+
+use ignore::WalkBuilder;
+use ignore::gitignore::Gitignore;
+use std::path::Path;
+
+/// Name of the per-skill ignore file
+pub const IGNORE_FILE: &str = ".paksignore";
+
+/// Walker over a skill directory that honors `.paksignore` and skips `.git`
+///
+/// Hidden files are included and `.gitignore` is not consulted, so the walk
+/// matches what a skill actually ships.
+pub fn walk_builder(root: &Path) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .sort_by_file_name(|a, b| a.cmp(b));
+    builder
+}
+
+/// The rules in a skill's root `.paksignore`, for code that walks the skill
+/// itself instead of using [`walk_builder`]
+pub struct IgnoreRules(Gitignore);
+
+impl IgnoreRules {
+    /// Rules from `root/.paksignore`; a missing file ignores nothing
+    pub fn load(root: &Path) -> Self {
+        let (rules, _) = Gitignore::new(root.join(IGNORE_FILE));
+        Self(rules)
+    }
+
+    /// Whether `rel`, relative to the skill root, is excluded by the rules
+    /// directly or through one of its parent directories
+    pub fn is_ignored(&self, rel: &Path, is_dir: bool) -> bool {
+        self.0.matched_path_or_any_parents(rel, is_dir).is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_walk_respects_paksignore() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(IGNORE_FILE), "*.log\nbuild/\n").unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "").unwrap();
+        std::fs::write(dir.path().join("debug.log"), "").unwrap();
+        std::fs::create_dir_all(dir.path().join("build")).unwrap();
+        std::fs::write(dir.path().join("build/out.txt"), "").unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "").unwrap();
+
+        let names: Vec<String> = walk_builder(dir.path())
+            .build()
+            .flatten()
+            .filter(|e| e.depth() > 0)
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+
+        assert_eq!(names, [IGNORE_FILE, "SKILL.md"]);
+    }
+
+    #[test]
+    fn test_rules_exclude_nested_directory() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(IGNORE_FILE), "assets/fixtures/\n").unwrap();
+        let rules = IgnoreRules::load(dir.path());
+
+        assert!(rules.is_ignored(Path::new("assets/fixtures"), true));
+        assert!(rules.is_ignored(Path::new("assets/fixtures/big/data.bin"), false));
+        assert!(!rules.is_ignored(Path::new("assets/logo.svg"), false));
+        assert!(!rules.is_ignored(Path::new("SKILL.md"), false));
+
+        let none = IgnoreRules::load(&dir.path().join("missing"));
+        assert!(!none.is_ignored(Path::new("assets/fixtures"), true));
+    }
+}
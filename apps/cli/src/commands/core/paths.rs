@@ -0,0 +1,103 @@
+//! Path helpers shared by commands
+
+use std::path::{Path, PathBuf};
+
+/// Expand a leading `~` and `$VAR`/`${VAR}` references in a user-supplied path
+///
+/// Unknown variables are left as written rather than failing.
+pub fn expand_path(path: &str) -> PathBuf {
+    expand_path_with(
+        path,
+        || dirs::home_dir().map(|h| h.to_string_lossy().into_owned()),
+        |var| std::env::var(var).ok(),
+    )
+}
+
+/// Write `path` with a leading home directory as `~`, the inverse of
+/// [`expand_path`] for paths shared between machines
+pub fn contract_home(path: &Path) -> String {
+    contract_home_with(path, dirs::home_dir().as_deref())
+}
+
+/// [`contract_home`] with an explicit home directory
+fn contract_home_with(path: &Path, home_dir: Option<&Path>) -> String {
+    match home_dir.and_then(|home| path.strip_prefix(home).ok()) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// [`expand_path`] with an explicit home directory and variable lookup
+fn expand_path_with(
+    path: &str,
+    home_dir: impl FnOnce() -> Option<String>,
+    lookup: impl FnMut(&str) -> Option<String>,
+) -> PathBuf {
+    PathBuf::from(shellexpand::full_with_context_no_errors(path, home_dir, lookup).as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(path: &str) -> PathBuf {
+        expand_path_with(
+            path,
+            || Some("/home/alice".to_string()),
+            |var| match var {
+                "SKILLS_ROOT" => Some("/opt/skills".to_string()),
+                _ => None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_expand_tilde() {
+        assert_eq!(
+            expand("~/.claude/skills"),
+            PathBuf::from("/home/alice/.claude/skills")
+        );
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        assert_eq!(
+            expand("$SKILLS_ROOT/cursor"),
+            PathBuf::from("/opt/skills/cursor")
+        );
+        assert_eq!(
+            expand("${SKILLS_ROOT}/cursor"),
+            PathBuf::from("/opt/skills/cursor")
+        );
+    }
+
+    #[test]
+    fn test_expand_unknown_var_left_as_is() {
+        assert_eq!(expand("$NOPE/skills"), PathBuf::from("$NOPE/skills"));
+    }
+
+    #[test]
+    fn test_contract_home() {
+        let home = Some(Path::new("/home/alice"));
+        assert_eq!(
+            contract_home_with(Path::new("/home/alice/.claude/skills"), home),
+            "~/.claude/skills"
+        );
+        assert_eq!(contract_home_with(Path::new("/home/alice"), home), "~");
+        assert_eq!(
+            contract_home_with(Path::new("/home/alicia/skills"), home),
+            "/home/alicia/skills"
+        );
+        assert_eq!(
+            expand(&contract_home_with(Path::new("/home/alice/x"), home)),
+            PathBuf::from("/home/alice/x")
+        );
+    }
+
+    #[test]
+    fn test_expand_literal_path() {
+        assert_eq!(expand("/var/lib/skills"), PathBuf::from("/var/lib/skills"));
+        assert_eq!(expand("relative/dir"), PathBuf::from("relative/dir"));
+    }
+}
@@ -0,0 +1,147 @@
+//! Skill signature verification
+//!
+//! Publishers can sign a skill's `SKILL.md` with [minisign]. The detached
+//! signature comes from the registry (`InstallVersionInfo.signature`) or a
+//! `SKILL.md.sig` file shipped with the skill, and is checked against the
+//! public key configured as `signature_public_key` in `~/.paks/config.toml`.
+//!
+//! Verification requires the `signatures` Cargo feature (enabled by default).
+//!
+//! [minisign]: https://jedisct1.github.io/minisign/
+
+use anyhow::{Result, bail};
+
+/// File name of a detached signature shipped alongside SKILL.md
+pub const SIGNATURE_FILE: &str = "SKILL.md.sig";
+
+/// Outcome of checking a skill's signature
+#[derive(Debug, PartialEq, Eq)]
+pub enum SignatureCheck {
+    /// Signature verified against the configured public key
+    Verified,
+    /// The skill carries no signature
+    Unsigned,
+    /// A signature is present but no public key is configured to check it
+    NoPublicKey,
+}
+
+/// Check `signature` over `data`
+///
+/// With `require`, anything short of a verified signature is an error.
+/// A signature that does not match always fails.
+pub fn check(
+    public_key: Option<&str>,
+    data: &[u8],
+    signature: Option<&str>,
+    require: bool,
+) -> Result<SignatureCheck> {
+    let outcome = match (signature, public_key) {
+        (None, _) => SignatureCheck::Unsigned,
+        (Some(_), None) => SignatureCheck::NoPublicKey,
+        (Some(signature), Some(public_key)) => {
+            verify(public_key, data, signature)?;
+            SignatureCheck::Verified
+        }
+    };
+
+    if require {
+        match outcome {
+            SignatureCheck::Verified => {}
+            SignatureCheck::Unsigned => {
+                bail!("Skill is not signed and --require-signature was given")
+            }
+            SignatureCheck::NoPublicKey => bail!(
+                "Skill is signed but no signature_public_key is configured in ~/.paks/config.toml"
+            ),
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Verify a minisign signature over `data`
+#[cfg(feature = "signatures")]
+fn verify(public_key: &str, data: &[u8], signature: &str) -> Result<()> {
+    use minisign_verify::{PublicKey, Signature};
+
+    let public_key = PublicKey::from_base64(public_key.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid signature_public_key: {}", e))?;
+    let signature =
+        Signature::decode(signature).map_err(|e| anyhow::anyhow!("Invalid signature: {}", e))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| anyhow::anyhow!("Signature verification failed: {}", e))
+}
+
+#[cfg(not(feature = "signatures"))]
+fn verify(_public_key: &str, _data: &[u8], _signature: &str) -> Result<()> {
+    bail!(
+        "This build of paks does not support signature verification (enable the `signatures` feature)"
+    )
+}
+
+#[cfg(test)]
+#[cfg(feature = "signatures")]
+mod tests {
+    use super::*;
+    use minisign::KeyPair;
+    use std::io::Cursor;
+
+    const SKILL_MD: &[u8] = b"---\nname: signed\ndescription: A signed skill\n---\n";
+
+    fn sign(keypair: &KeyPair, data: &[u8]) -> String {
+        minisign::sign(
+            Some(&keypair.pk),
+            &keypair.sk,
+            Cursor::new(data),
+            None,
+            None,
+        )
+        .unwrap()
+        .into_string()
+    }
+
+    #[test]
+    fn test_valid_signature_verifies() {
+        let keypair = KeyPair::generate_unencrypted_keypair().unwrap();
+        let signature = sign(&keypair, SKILL_MD);
+
+        let outcome = check(
+            Some(&keypair.pk.to_base64()),
+            SKILL_MD,
+            Some(&signature),
+            true,
+        )
+        .unwrap();
+        assert_eq!(outcome, SignatureCheck::Verified);
+    }
+
+    #[test]
+    fn test_tampered_content_fails() {
+        let keypair = KeyPair::generate_unencrypted_keypair().unwrap();
+        let signature = sign(&keypair, SKILL_MD);
+        let tampered = b"---\nname: signed\ndescription: Something else\n---\n";
+
+        let err = check(
+            Some(&keypair.pk.to_base64()),
+            tampered,
+            Some(&signature),
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Signature verification failed"));
+    }
+
+    #[test]
+    fn test_unsigned_skill() {
+        assert_eq!(
+            check(Some("key"), SKILL_MD, None, false).unwrap(),
+            SignatureCheck::Unsigned
+        );
+        assert!(check(Some("key"), SKILL_MD, None, true).is_err());
+        assert_eq!(
+            check(None, SKILL_MD, Some("sig"), false).unwrap(),
+            SignatureCheck::NoPublicKey
+        );
+    }
+}
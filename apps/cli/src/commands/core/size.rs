@@ -0,0 +1,34 @@
+//! Human-readable byte sizes
+
+/// Bytes in a megabyte, as used by size limits like `--max-size`
+pub const MB: u64 = 1024 * 1024;
+
+/// Format a byte count with B/KB/MB/GB units
+pub fn format_size(bytes: u64) -> String {
+    const GB: u64 = 1024 * MB;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KB");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * MB), "3.0 MB");
+        assert_eq!(format_size(5 * 1024 * MB / 2), "2.5 GB");
+    }
+}
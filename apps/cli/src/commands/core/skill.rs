@@ -4,154 +4,20 @@
 //! Paks extends the Agent Skills spec frontmatter with package management fields.
 
 use anyhow::{Context, Result, bail};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use paks_core::skill_md::parse_skill_md;
+use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
 
-/// SKILL.md frontmatter - combines Agent Skills spec with paks package fields
-///
-/// Required fields (Agent Skills spec):
-/// - name: Skill identifier (1-64 chars, lowercase + hyphens)
-/// - description: What the skill does (1-1024 chars)
-///
-/// Optional fields (Agent Skills spec):
-/// - license: License name or reference
-/// - compatibility: Environment requirements
-/// - metadata: Arbitrary key-value pairs (includes version for paks)
-/// - allowed-tools: Pre-approved tools (experimental)
-///
-/// Paks extensions (for package management):
-/// - metadata.version: Semantic version for publishing (inside metadata)
-/// - authors: List of authors
-/// - repository: Source repository URL
-/// - homepage: Project homepage
-/// - keywords: Search keywords
-/// - categories: Skill categories
-/// - dependencies: Other skills this depends on
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SkillFrontmatter {
-    // === Agent Skills spec required fields ===
-    /// Skill name (required, 1-64 chars, lowercase + hyphens)
-    pub name: String,
-
-    /// Description of what the skill does (required, 1-1024 chars)
-    pub description: String,
-
-    // === Agent Skills spec optional fields ===
-    /// License
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub license: Option<String>,
-
-    /// Compatibility notes (max 500 chars)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub compatibility: Option<String>,
-
-    /// Additional metadata (Agent Skills spec)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
-
-    /// Pre-approved tools (experimental, Agent Skills spec)
-    #[serde(
-        default,
-        rename = "allowed-tools",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub allowed_tools: Option<String>,
-
-    // === Paks package management extensions ===
-    /// Authors (paks extension)
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub authors: Vec<String>,
-
-    /// Repository URL (paks extension)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub repository: Option<String>,
-
-    /// Homepage URL (paks extension)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub homepage: Option<String>,
-
-    /// Keywords for search (paks extension)
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub keywords: Vec<String>,
-
-    /// Categories (paks extension)
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub categories: Vec<String>,
-
-    /// Dependencies on other skills (paks extension)
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub dependencies: Vec<SkillDependency>,
-}
-
-/// Skill dependency specification
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SkillDependency {
-    /// Dependency skill name
-    pub name: String,
+use super::paksignore::IgnoreRules;
 
-    /// Version requirement (semver range)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub version: Option<String>,
+pub use paks_core::frontmatter::{Diagnostic, SkillDependency, SkillFrontmatter};
+pub use paks_core::skill_md::generate_skill_md;
 
-    /// Git repository URL (alternative to registry)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub git: Option<String>,
+/// Version reported for a skill without `metadata.version`
+pub const DEFAULT_VERSION: &str = "0.1.0";
 
-    /// Git ref (branch/tag/commit)
-    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
-    pub git_ref: Option<String>,
-
-    /// Local path (for development)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub path: Option<String>,
-}
-
-impl SkillFrontmatter {
-    /// Validate the frontmatter according to Agent Skills spec
-    pub fn validate(&self) -> Result<Vec<String>> {
-        let mut warnings = Vec::new();
-
-        // Name validation
-        if self.name.is_empty() || self.name.len() > 64 {
-            bail!("name must be 1-64 characters");
-        }
-
-        if !self
-            .name
-            .chars()
-            .all(|c| c.is_ascii_lowercase() || c == '-' || c.is_ascii_digit())
-        {
-            bail!("name must contain only lowercase letters, numbers, and hyphens");
-        }
-
-        if self.name.starts_with('-') || self.name.ends_with('-') {
-            bail!("name must not start or end with a hyphen");
-        }
-
-        if self.name.contains("--") {
-            bail!("name must not contain consecutive hyphens");
-        }
-
-        // Description validation
-        if self.description.is_empty() || self.description.len() > 1024 {
-            bail!("description must be 1-1024 characters");
-        }
-
-        if self.description.len() < 20 {
-            warnings.push("description is very short; consider adding more detail".to_string());
-        }
-
-        // Compatibility validation
-        if let Some(compat) = &self.compatibility
-            && compat.len() > 500
-        {
-            bail!("compatibility must be at most 500 characters");
-        }
-
-        Ok(warnings)
-    }
-}
+/// Directories whose files SKILL.md instructions are expected to reference
+const RESOURCE_DIRS: &[&str] = &["scripts", "references", "assets"];
 
 /// Represents a complete skill on disk
 #[derive(Debug)]
@@ -177,10 +43,17 @@ impl Skill {
         let content = std::fs::read_to_string(&skill_md_path)
             .with_context(|| format!("Failed to read {}", skill_md_path.display()))?;
 
-        let (frontmatter, instructions) = parse_skill_md(&content)?;
+        Self::from_skill_md(skill_dir.to_path_buf(), &content)
+    }
+
+    /// Parse a skill from SKILL.md `content`, e.g. one fetched from the registry
+    ///
+    /// `path` is recorded as the skill's root but not read.
+    pub fn from_skill_md(path: PathBuf, content: &str) -> Result<Self> {
+        let (frontmatter, instructions) = parse_skill_md(content)?;
 
         Ok(Self {
-            path: skill_dir.to_path_buf(),
+            path,
             frontmatter,
             instructions,
         })
@@ -240,19 +113,59 @@ impl Skill {
         self.path.join("assets").is_dir()
     }
 
+    /// Check that files under `scripts/`, `references/` and `assets/` named in
+    /// the instructions exist, and that every file there is named somewhere
+    ///
+    /// Returns warnings: `structure.missing_file` for references to files that
+    /// don't exist and `structure.unreferenced_file` for files nothing
+    /// mentions. Naming a directory (`references/guides/`) covers its files.
+    pub fn validate_structure(&self) -> Vec<Diagnostic> {
+        let referenced = referenced_paths(&self.instructions);
+        let mut warnings = Vec::new();
+
+        for path in &referenced {
+            if !self.path.join(path).exists() {
+                warnings.push(Diagnostic::new(
+                    "structure.missing_file",
+                    format!("Instructions reference {}, which does not exist", path),
+                ));
+            }
+        }
+
+        let mut files = Vec::new();
+        for dir in RESOURCE_DIRS {
+            collect_files(&self.path.join(dir), dir, &mut files);
+        }
+        let rules = IgnoreRules::load(&self.path);
+        files.retain(|file| !rules.is_ignored(Path::new(file), false));
+        files.sort();
+        for file in files {
+            let covered = referenced
+                .iter()
+                .any(|r| *r == file || (r.ends_with('/') && file.starts_with(r.as_str())));
+            if !covered {
+                warnings.push(Diagnostic::new(
+                    "structure.unreferenced_file",
+                    format!("{} is not referenced in the instructions", file),
+                ));
+            }
+        }
+        warnings
+    }
+
     /// Get the skill name
     pub fn name(&self) -> &str {
         &self.frontmatter.name
     }
 
-    /// Get the skill version from metadata (defaults to "0.1.0" if not set)
+    /// Get the skill version from metadata (defaults to [`DEFAULT_VERSION`] if not set)
     pub fn version(&self) -> &str {
         self.frontmatter
             .metadata
             .as_ref()
             .and_then(|m| m.get("version"))
             .map(|s| s.as_str())
-            .unwrap_or("0.1.0")
+            .unwrap_or(DEFAULT_VERSION)
     }
 
     /// Get the skill version as Option (for publish checks)
@@ -263,36 +176,132 @@ impl Skill {
             .and_then(|m| m.get("version"))
             .map(|s| s.as_str())
     }
-}
 
-/// Parse SKILL.md content into frontmatter and body
-fn parse_skill_md(content: &str) -> Result<(SkillFrontmatter, String)> {
-    let content = content.trim();
+    /// The skill version parsed as semver
+    pub fn semver(&self) -> Result<semver::Version> {
+        parse_version(self.version())
+    }
 
-    if !content.starts_with("---") {
-        bail!("SKILL.md must start with YAML frontmatter (---)");
+    /// Set `metadata.version`, accepting an optional `v` prefix
+    pub fn set_version(&mut self, version: &str) -> Result<()> {
+        let version = version.strip_prefix('v').unwrap_or(version);
+        parse_version(version)?;
+        self.frontmatter
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert("version".to_string(), version.to_string());
+        Ok(())
     }
 
-    let rest = &content[3..];
-    let end_marker = rest
-        .find("\n---")
-        .context("SKILL.md frontmatter not properly closed (missing ---)")?;
+    /// Bump `metadata.version` at `level`, returning the new version
+    pub fn bump_version(&mut self, level: BumpLevel) -> Result<String> {
+        let version = self
+            .semver()
+            .and_then(|current| level.apply(&current))
+            .with_context(|| format!("Cannot bump version of {}", self.name()))?
+            .to_string();
+        self.set_version(&version)?;
+        Ok(version)
+    }
+}
 
-    let frontmatter_str = &rest[..end_marker].trim();
-    let body = rest[end_marker + 4..].trim();
+/// Which component of a `MAJOR.MINOR.PATCH` version to increment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BumpLevel {
+    Patch,
+    Minor,
+    Major,
+}
 
-    // Parse YAML frontmatter
-    let frontmatter: SkillFrontmatter = serde_yaml_ng::from_str(frontmatter_str)
-        .context("Failed to parse SKILL.md frontmatter as YAML")?;
+impl BumpLevel {
+    /// `version` bumped at this level, resetting the lower components
+    ///
+    /// Pre-release and build metadata are dropped. A pre-release that is
+    /// already at the bumped level is released rather than bumped again, so
+    /// a patch bump of `1.2.3-rc.1` gives `1.2.3`.
+    pub fn apply(self, version: &semver::Version) -> Result<semver::Version> {
+        let releases_pre = !version.pre.is_empty()
+            && match self {
+                BumpLevel::Patch => true,
+                BumpLevel::Minor => version.patch == 0,
+                BumpLevel::Major => version.minor == 0 && version.patch == 0,
+            };
+        let (major, minor, patch) = (version.major, version.minor, version.patch);
+        let bumped = if releases_pre {
+            Some((major, minor, patch))
+        } else {
+            match self {
+                BumpLevel::Patch => patch.checked_add(1).map(|p| (major, minor, p)),
+                BumpLevel::Minor => minor.checked_add(1).map(|m| (major, m, 0)),
+                BumpLevel::Major => major.checked_add(1).map(|m| (m, 0, 0)),
+            }
+        };
+        match bumped {
+            Some((major, minor, patch)) => Ok(semver::Version::new(major, minor, patch)),
+            None => bail!("Version {} can't be bumped any further", version),
+        }
+    }
+}
 
-    Ok((frontmatter, body.to_string()))
+/// Parse a semver version (`MAJOR.MINOR.PATCH[-PRE][+BUILD]`), with an
+/// optional `v` prefix
+pub fn parse_version(version: &str) -> Result<semver::Version> {
+    let v = version.strip_prefix('v').unwrap_or(version);
+    semver::Version::parse(v).with_context(|| {
+        format!(
+            "Invalid version format: {}. Expected MAJOR.MINOR.PATCH (e.g. 1.2.3 or 1.2.3-rc.1)",
+            version
+        )
+    })
 }
 
-/// Generate SKILL.md content from frontmatter and body
-pub fn generate_skill_md(frontmatter: &SkillFrontmatter, body: &str) -> Result<String> {
-    let yaml = serde_yaml_ng::to_string(frontmatter).context("Failed to serialize frontmatter")?;
+/// Relative paths under [`RESOURCE_DIRS`] mentioned in markdown, as links,
+/// code spans or plain text
+fn referenced_paths(markdown: &str) -> BTreeSet<String> {
+    let mut paths = BTreeSet::new();
+    for dir in RESOURCE_DIRS {
+        let prefix = format!("{}/", dir);
+        for (start, _) in markdown.match_indices(&prefix) {
+            // Must start a path, not be the tail of one like `foo/scripts/`
+            let before = markdown[..start].trim_end_matches("./");
+            if before
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || matches!(c, '/' | '-' | '_' | '.'))
+            {
+                continue;
+            }
+            let path: String = markdown[start..]
+                .chars()
+                .take_while(|c| !c.is_whitespace() && !"()[]<>`'\",;#?".contains(*c))
+                .collect();
+            let path = path.trim_end_matches(['.', ':', '!']);
+            if path.len() > prefix.len() {
+                paths.insert(path.to_string());
+            }
+        }
+    }
+    paths
+}
 
-    Ok(format!("---\n{}---\n\n{}", yaml, body))
+/// Add the files under `dir` to `files` as `/`-separated paths starting at
+/// `prefix`, skipping dotfiles such as `.gitkeep`
+fn collect_files(dir: &Path, prefix: &str, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let rel = format!("{}/{}", prefix, name);
+        if entry.path().is_dir() {
+            collect_files(&entry.path(), &rel, files);
+        } else {
+            files.push(rel);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -300,53 +309,112 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_frontmatter_validation() {
-        let valid = SkillFrontmatter {
-            name: "my-skill".to_string(),
-            description: "A skill that does something useful for users".to_string(),
-            license: None,
-            compatibility: None,
-            metadata: None,
-            allowed_tools: None,
-            authors: Vec::new(),
-            repository: None,
-            homepage: None,
-            keywords: Vec::new(),
-            categories: Vec::new(),
-            dependencies: Vec::new(),
-        };
-        assert!(valid.validate().is_ok());
-
-        let invalid_name = SkillFrontmatter {
-            name: "My-Skill".to_string(), // uppercase not allowed
-            description: "A skill".to_string(),
-            license: None,
-            compatibility: None,
-            metadata: None,
-            allowed_tools: None,
-            authors: Vec::new(),
-            repository: None,
-            homepage: None,
-            keywords: Vec::new(),
-            categories: Vec::new(),
-            dependencies: Vec::new(),
+    fn test_bump_version() {
+        let mut skill = Skill::new(PathBuf::from("demo"), "demo", "Demo skill");
+        skill.set_version("v1.2.3").unwrap();
+        assert_eq!(skill.version(), "1.2.3");
+
+        assert_eq!(skill.bump_version(BumpLevel::Patch).unwrap(), "1.2.4");
+        assert_eq!(skill.bump_version(BumpLevel::Minor).unwrap(), "1.3.0");
+        assert_eq!(skill.bump_version(BumpLevel::Major).unwrap(), "2.0.0");
+        assert_eq!(skill.version(), "2.0.0");
+    }
+
+    #[test]
+    fn test_bump_version_rejects_invalid_versions() {
+        let mut skill = Skill::new(PathBuf::from("demo"), "demo", "Demo skill");
+        for invalid in ["1.2", "1.2.3.4", "one.2.3", "1.2.3-", "01.2.3", ""] {
+            assert!(skill.set_version(invalid).is_err(), "{}", invalid);
+        }
+
+        skill
+            .frontmatter
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .insert("version".to_string(), "latest".to_string());
+        let err = skill.bump_version(BumpLevel::Patch).unwrap_err();
+        assert!(err.to_string().contains("Cannot bump version of demo"));
+        assert_eq!(skill.version(), "latest");
+
+        assert!(
+            BumpLevel::Patch
+                .apply(&semver::Version::new(1, 0, u64::MAX))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_prerelease_versions() {
+        let mut skill = Skill::new(PathBuf::from("demo"), "demo", "Demo skill");
+        skill.set_version("v1.0.0-beta.1+build.5").unwrap();
+        assert_eq!(skill.version(), "1.0.0-beta.1+build.5");
+
+        let version = skill.semver().unwrap();
+        assert_eq!(version.pre.as_str(), "beta.1");
+        assert_eq!(version.build.as_str(), "build.5");
+        assert!(version < parse_version("1.0.0").unwrap());
+
+        let bump = |level: BumpLevel, v: &str| {
+            level.apply(&parse_version(v).unwrap()).unwrap().to_string()
         };
-        assert!(invalid_name.validate().is_err());
+        assert_eq!(bump(BumpLevel::Patch, "1.2.3-rc.1"), "1.2.3");
+        assert_eq!(bump(BumpLevel::Minor, "1.2.3-rc.1"), "1.3.0");
+        assert_eq!(bump(BumpLevel::Minor, "1.3.0-rc.1"), "1.3.0");
+        assert_eq!(bump(BumpLevel::Major, "2.0.0-alpha"), "2.0.0");
+        assert_eq!(bump(BumpLevel::Major, "2.1.0-alpha"), "3.0.0");
+    }
+
+    #[test]
+    fn test_referenced_paths() {
+        let paths = referenced_paths(
+            "Run [the script](scripts/deploy.sh) or `./scripts/check.sh --all`.\n\
+             See references/api.md. Images live in assets/img/, not in docs/assets/x.png.\n\
+             Mention scripts/ alone.",
+        );
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            [
+                "assets/img/",
+                "references/api.md",
+                "scripts/check.sh",
+                "scripts/deploy.sh"
+            ]
+        );
     }
 
     #[test]
-    fn test_parse_skill_md() {
-        let content = r#"---
-name: test-skill
-description: A test skill for unit testing
----
-
-# Test Skill
-
-Instructions go here.
-"#;
-        let (fm, body) = parse_skill_md(content).unwrap();
-        assert_eq!(fm.name, "test-skill");
-        assert!(body.contains("# Test Skill"));
+    fn test_validate_structure() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        std::fs::create_dir_all(dir.path().join("assets/img")).unwrap();
+        std::fs::write(dir.path().join("scripts/run.sh"), "").unwrap();
+        std::fs::write(dir.path().join("scripts/unused.sh"), "").unwrap();
+        std::fs::write(dir.path().join("scripts/.gitkeep"), "").unwrap();
+        std::fs::write(dir.path().join("assets/img/logo.png"), "").unwrap();
+
+        let mut skill = Skill::new(dir.path().to_path_buf(), "demo", "A demo skill");
+        skill.instructions =
+            "Run `scripts/run.sh`, then `scripts/missing.sh`. Logos are in assets/img/.\n"
+                .to_string();
+
+        let warnings = skill.validate_structure();
+        let codes: Vec<(&str, &str)> = warnings
+            .iter()
+            .map(|w| (w.code, w.message.as_str()))
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                (
+                    "structure.missing_file",
+                    "Instructions reference scripts/missing.sh, which does not exist"
+                ),
+                (
+                    "structure.unreferenced_file",
+                    "scripts/unused.sh is not referenced in the instructions"
+                ),
+            ]
+        );
     }
 }
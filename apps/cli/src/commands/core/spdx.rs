@@ -0,0 +1,95 @@
+//! SPDX license identifiers
+//!
+//! The SPDX license list is bundled at compile time (`spdx-ids.txt`) so
+//! license checks never need the network.
+
+/// License ids, a blank line, then exception ids
+const SPDX_IDS: &str = include_str!("spdx-ids.txt");
+
+/// Version of the bundled SPDX license list
+pub fn list_version() -> &'static str {
+    SPDX_IDS
+        .lines()
+        .next()
+        .and_then(|header| header.strip_prefix("# SPDX license list "))
+        .and_then(|rest| rest.split(':').next())
+        .unwrap_or("unknown")
+}
+
+fn sections() -> (&'static str, &'static str) {
+    let body = SPDX_IDS.split_once('\n').map_or("", |(_, body)| body);
+    body.split_once("\n\n").unwrap_or((body, ""))
+}
+
+fn contains(list: &str, id: &str) -> bool {
+    list.lines().any(|known| known.eq_ignore_ascii_case(id))
+}
+
+/// Whether `id` is an SPDX license id, `id+`, or a `LicenseRef-` reference
+pub fn is_license_id(id: &str) -> bool {
+    if id.starts_with("LicenseRef-") || id.starts_with("DocumentRef-") {
+        return true;
+    }
+    let id = id.strip_suffix('+').unwrap_or(id);
+    contains(sections().0, id)
+}
+
+/// Whether `id` is an SPDX license exception id (the right side of `WITH`)
+pub fn is_exception_id(id: &str) -> bool {
+    contains(sections().1, id)
+}
+
+/// Identifiers in an SPDX license expression that aren't on the bundled list
+///
+/// Parentheses and the `AND`, `OR` and `WITH` operators are accepted; an
+/// empty result means the expression only uses known ids.
+pub fn unknown_ids(expression: &str) -> Vec<String> {
+    let spaced = expression.replace(['(', ')'], " ");
+    let mut unknown = Vec::new();
+    let mut after_with = false;
+
+    for token in spaced.split_whitespace() {
+        match token.to_ascii_uppercase().as_str() {
+            "AND" | "OR" => after_with = false,
+            "WITH" => after_with = true,
+            _ => {
+                let known = if after_with {
+                    is_exception_id(token)
+                } else {
+                    is_license_id(token)
+                };
+                if !known {
+                    unknown.push(token.to_string());
+                }
+                after_with = false;
+            }
+        }
+    }
+    unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_list_is_loaded() {
+        assert_ne!(list_version(), "unknown");
+        assert!(is_license_id("MIT"));
+        assert!(is_license_id("apache-2.0"));
+        assert!(is_license_id("GPL-2.0+"));
+        assert!(is_license_id("LicenseRef-acme"));
+        assert!(is_exception_id("Classpath-exception-2.0"));
+        assert!(!is_license_id("Classpath-exception-2.0"));
+        assert!(!is_license_id("Proprietary"));
+    }
+
+    #[test]
+    fn test_unknown_ids_in_expressions() {
+        assert!(unknown_ids("MIT").is_empty());
+        assert!(unknown_ids("(Apache-2.0 OR MIT) AND BSD-3-Clause").is_empty());
+        assert!(unknown_ids("GPL-2.0-or-later WITH Classpath-exception-2.0").is_empty());
+        assert_eq!(unknown_ids("MIT OR Acme-1.0"), vec!["Acme-1.0"]);
+        assert_eq!(unknown_ids("GPL-2.0-only WITH MIT"), vec!["MIT"]);
+    }
+}
@@ -0,0 +1,115 @@
+//! Terminal styling
+//!
+//! ANSI styling that turns into a no-op when color is disabled, either with
+//! `--no-color`, the `NO_COLOR` environment variable (https://no-color.org),
+//! or because stdout is not a terminal.
+
+use std::ffi::OsString;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+static CURRENT: OnceLock<Style> = OnceLock::new();
+
+/// Applies ANSI styles to text when color is enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    enabled: bool,
+}
+
+impl Style {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Decide whether to use color from the `--no-color` flag, the value of
+    /// `NO_COLOR` and whether stdout is a terminal
+    ///
+    /// As per the NO_COLOR convention, an empty value does not disable color.
+    pub fn resolve(no_color_flag: bool, no_color_env: Option<OsString>, is_tty: bool) -> Self {
+        let env_disabled = no_color_env.is_some_and(|v| !v.is_empty());
+        Self::new(!no_color_flag && !env_disabled && is_tty)
+    }
+
+    /// Style for the current process and terminal
+    pub fn detect(no_color_flag: bool) -> Self {
+        Self::resolve(
+            no_color_flag,
+            std::env::var_os("NO_COLOR"),
+            std::io::stdout().is_terminal(),
+        )
+    }
+
+    /// Style chosen at startup with [`init`], or detected from the environment
+    pub fn current() -> Self {
+        *CURRENT.get_or_init(|| Self::detect(false))
+    }
+
+    pub fn bold(&self, text: impl AsRef<str>) -> String {
+        self.paint("1", text.as_ref())
+    }
+
+    pub fn dim(&self, text: impl AsRef<str>) -> String {
+        self.paint("2", text.as_ref())
+    }
+
+    pub fn red(&self, text: impl AsRef<str>) -> String {
+        self.paint("31", text.as_ref())
+    }
+
+    pub fn green(&self, text: impl AsRef<str>) -> String {
+        self.paint("32", text.as_ref())
+    }
+
+    pub fn yellow(&self, text: impl AsRef<str>) -> String {
+        self.paint("33", text.as_ref())
+    }
+
+    pub fn cyan(&self, text: impl AsRef<str>) -> String {
+        self.paint("36", text.as_ref())
+    }
+
+    pub fn bold_cyan(&self, text: impl AsRef<str>) -> String {
+        self.paint("1;36", text.as_ref())
+    }
+
+    pub fn bold_yellow(&self, text: impl AsRef<str>) -> String {
+        self.paint("1;33", text.as_ref())
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Set the process-wide style from the global `--no-color` flag
+///
+/// Must be called before anything is printed; later calls are ignored.
+pub fn init(no_color_flag: bool) {
+    CURRENT.get_or_init(|| Style::detect(no_color_flag));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_honors_no_color() {
+        assert!(Style::resolve(false, None, true).enabled);
+        assert!(!Style::resolve(true, None, true).enabled);
+        assert!(!Style::resolve(false, Some("1".into()), true).enabled);
+        assert!(!Style::resolve(false, None, false).enabled);
+        // An empty NO_COLOR is treated as unset
+        assert!(Style::resolve(false, Some("".into()), true).enabled);
+    }
+
+    #[test]
+    fn test_disabled_style_is_plain() {
+        let style = Style::new(false);
+        assert_eq!(style.bold_cyan("acme"), "acme");
+        assert_eq!(Style::new(true).dim("x"), "\x1b[2mx\x1b[0m");
+    }
+}
@@ -0,0 +1,139 @@
+//! "Did you mean" suggestions for registry misses
+
+use paks_api::{PaksClient, SearchPaksQuery};
+
+/// Maximum number of suggestions returned
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Candidates within this many edits (scaled by query length) are suggested
+fn max_distance(query: &str) -> usize {
+    (query.chars().count() / 3).clamp(1, 3)
+}
+
+/// Close matches for `query` among `candidates` (`owner/name` URIs), best first
+///
+/// A candidate matches if either its full URI or just its name is within a
+/// small edit distance of the query.
+pub fn suggest(query: &str, candidates: &[String]) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    let limit = max_distance(&query);
+
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .filter(|c| c.to_lowercase() != query)
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let name = lower.rsplit('/').next().unwrap_or(&lower);
+            let distance = levenshtein(&query, &lower).min(levenshtein(&query, name));
+            (distance <= limit).then_some((distance, candidate))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c.clone())
+        .collect()
+}
+
+/// Search the registry for paks resembling `intended` and suggest close ones
+///
+/// Best-effort: network errors simply produce no suggestions.
+pub async fn did_you_mean(client: &PaksClient, intended: &str) -> Vec<String> {
+    let name = intended.rsplit('/').next().unwrap_or(intended);
+    // The exact name already missed, so fall back to a short prefix
+    let prefix: String = name.chars().take(3).collect();
+
+    for query in [name.to_string(), prefix] {
+        let search = SearchPaksQuery {
+            query: Some(query),
+            limit: Some(20),
+            ..Default::default()
+        };
+        let Ok(results) = client.search_paks(search).await else {
+            return Vec::new();
+        };
+        let candidates: Vec<String> = results.iter().map(|p| p.uri.clone()).collect();
+        let suggestions = suggest(intended, &candidates);
+        if !suggestions.is_empty() {
+            return suggestions;
+        }
+    }
+
+    Vec::new()
+}
+
+/// Format suggestions as a hint line, if there are any
+pub fn format_hint(suggestions: &[String]) -> Option<String> {
+    (!suggestions.is_empty()).then(|| format!("Did you mean: {}?", suggestions.join(", ")))
+}
+
+/// Edit distance between two strings
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates() -> Vec<String> {
+        [
+            "stakpak/kubernetes-deploy",
+            "stakpak/kubernetes-debug",
+            "acme/terraform",
+            "acme/name",
+            "acme/names",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_suggest_typo_in_full_uri() {
+        assert_eq!(
+            suggest("acme/naem", &candidates()),
+            ["acme/name", "acme/names"]
+        );
+        assert_eq!(
+            suggest("stakpak/kubernetes-deplyo", &candidates()),
+            ["stakpak/kubernetes-deploy"]
+        );
+    }
+
+    #[test]
+    fn test_suggest_matches_name_only_query() {
+        assert_eq!(suggest("terrafrom", &candidates()), ["acme/terraform"]);
+    }
+
+    #[test]
+    fn test_suggest_ignores_distant_and_caps_results() {
+        assert!(suggest("completely-unrelated", &candidates()).is_empty());
+
+        let many: Vec<String> = (0..10).map(|i| format!("acme/skil{}", i)).collect();
+        assert_eq!(suggest("skill", &many).len(), MAX_SUGGESTIONS);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+}
@@ -0,0 +1,218 @@
+//! Auth token checks
+//!
+//! Token expiry is compared against the local clock with some tolerance for
+//! clock skew. When the local clock says a token has expired, the registry
+//! gets the final say: a token it still accepts is not blocked.
+
+use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, TimeDelta, Utc};
+use paks_api::{ApiError, PaksClient, VerifyTokenResponse};
+
+use super::client;
+use super::config::Config;
+use super::keychain::OsKeychain;
+
+/// How far past its expiry a token is still considered valid locally
+pub const CLOCK_SKEW_TOLERANCE: TimeDelta = TimeDelta::seconds(60);
+
+/// How long before a token expires commands start warning about it
+pub const EXPIRY_WARNING_WINDOW: TimeDelta = TimeDelta::days(7);
+
+/// Checks a token against the registry
+pub trait TokenVerifier {
+    async fn verify(&self, token: &str) -> Result<VerifyTokenResponse, ApiError>;
+}
+
+impl TokenVerifier for PaksClient {
+    async fn verify(&self, token: &str) -> Result<VerifyTokenResponse, ApiError> {
+        let mut client = self.clone();
+        client.set_token(token);
+        client.verify_token().await
+    }
+}
+
+/// Whether a token expiring at `expires_at` has expired at `now`, allowing
+/// for [`CLOCK_SKEW_TOLERANCE`]
+pub fn is_expired(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    expires_at.is_some_and(|expires_at| now > expires_at + CLOCK_SKEW_TOLERANCE)
+}
+
+/// Warning to show for a token expiring at `expires_at`, if that is within
+/// [`EXPIRY_WARNING_WINDOW`] of `now`
+///
+/// Tokens without an expiry never warn.
+pub fn expiry_warning(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<String> {
+    let expires_at = expires_at?;
+    let remaining = expires_at - now;
+    if remaining > EXPIRY_WARNING_WINDOW {
+        return None;
+    }
+
+    let date = expires_at.format("%Y-%m-%d %H:%M UTC");
+    Some(if remaining <= TimeDelta::zero() {
+        format!("your token expired on {}. Run 'paks login' again.", date)
+    } else if remaining.num_days() >= 1 {
+        format!(
+            "your token expires in {} day(s), on {}. Run 'paks login' to renew it.",
+            remaining.num_days(),
+            date
+        )
+    } else {
+        format!(
+            "your token expires in {} hour(s), on {}. Run 'paks login' to renew it.",
+            remaining.num_hours().max(1),
+            date
+        )
+    })
+}
+
+/// Ask the registry when the client's token expires and warn if that's soon
+///
+/// Errors are ignored; the command's own requests will report them.
+pub async fn warn_if_expiring(client: &PaksClient) {
+    if let Ok(expires_at) = client.token_expiry().await
+        && let Some(warning) = expiry_warning(expires_at, Utc::now())
+    {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+}
+
+/// Fail if the token has expired
+///
+/// A token that looks expired locally is checked with the registry; if the
+/// registry still accepts it the local clock is assumed to be off and only a
+/// warning is shown. Network errors don't block either, so the request that
+/// follows can report the real problem.
+pub async fn check_expiry(
+    expires_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    verifier: &impl TokenVerifier,
+    token: &str,
+) -> Result<()> {
+    let Some(expires_at) = expires_at.filter(|_| is_expired(expires_at, now)) else {
+        return Ok(());
+    };
+
+    match verifier.verify(token).await {
+        Ok(_) => {
+            let skew = (now - expires_at).num_minutes();
+            eprintln!(
+                "⚠ Warning: your token expired {} minute(s) ago by this machine's clock, \
+                 but the registry still accepts it. Check your system clock.",
+                skew
+            );
+            Ok(())
+        }
+        Err(ApiError::InvalidToken) => bail!(
+            "Your token expired at {}. Run 'paks login' again.",
+            expires_at.to_rfc3339()
+        ),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Registry client authenticated with the configured token
+///
+/// Fails if not logged in or if the token has expired.
+pub async fn authenticated_client(config: &Config) -> Result<PaksClient> {
+    let token = config
+        .get_auth_token(&OsKeychain)?
+        .ok_or_else(|| anyhow!("Not authenticated. Run 'paks login' first."))?;
+
+    let mut client = client::builder(config.resolve_registry_url(None)?).build()?;
+    client.set_token(&token);
+    check_expiry(config.get_auth_token_expiry(), Utc::now(), &client, &token).await?;
+
+    Ok(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Verifier that counts calls and answers as the registry would
+    struct FakeRegistry {
+        accepts: bool,
+        calls: Cell<usize>,
+    }
+
+    impl FakeRegistry {
+        fn new(accepts: bool) -> Self {
+            Self {
+                accepts,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl TokenVerifier for FakeRegistry {
+        async fn verify(&self, _token: &str) -> Result<VerifyTokenResponse, ApiError> {
+            self.calls.set(self.calls.get() + 1);
+            if !self.accepts {
+                return Err(ApiError::InvalidToken);
+            }
+            Ok(serde_json::from_value(serde_json::json!({
+                "valid": true,
+                "user": {"id": "1", "username": "alice", "email": "alice@example.com"},
+            }))
+            .unwrap())
+        }
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_is_expired_with_skew() {
+        assert!(!is_expired(None, at(0)));
+        assert!(!is_expired(Some(at(0)), at(-10)));
+        // Just past expiry, within the tolerance
+        assert!(!is_expired(Some(at(0)), at(30)));
+        assert!(is_expired(Some(at(0)), at(61)));
+    }
+
+    #[test]
+    fn test_expiry_warning() {
+        let day = 86_400;
+        // No expiry, or plenty of time left
+        assert_eq!(expiry_warning(None, at(0)), None);
+        assert_eq!(expiry_warning(Some(at(8 * day)), at(0)), None);
+
+        let warning = expiry_warning(Some(at(3 * day)), at(0)).unwrap();
+        assert!(warning.contains("expires in 3 day(s)"));
+        let warning = expiry_warning(Some(at(5 * 3600)), at(0)).unwrap();
+        assert!(warning.contains("expires in 5 hour(s)"));
+        let warning = expiry_warning(Some(at(0)), at(60)).unwrap();
+        assert!(warning.contains("expired on"));
+    }
+
+    #[tokio::test]
+    async fn test_just_expired_within_skew_skips_server() {
+        let registry = FakeRegistry::new(false);
+        check_expiry(Some(at(0)), at(45), &registry, "pak_token")
+            .await
+            .unwrap();
+        assert_eq!(registry.calls.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_clearly_expired_is_refused() {
+        let registry = FakeRegistry::new(false);
+        let err = check_expiry(Some(at(0)), at(3600), &registry, "pak_token")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("paks login"));
+        assert_eq!(registry.calls.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_acceptance_overrides_local_clock() {
+        let registry = FakeRegistry::new(true);
+        check_expiry(Some(at(0)), at(3600), &registry, "pak_token")
+            .await
+            .unwrap();
+        assert_eq!(registry.calls.get(), 1);
+    }
+}
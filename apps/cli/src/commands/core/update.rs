@@ -0,0 +1,209 @@
+//! Update planning shared by `update` and `outdated`
+//!
+//! Both commands compare installed registry skills against the versions the
+//! registry currently resolves to. [`plan_updates`] makes that comparison
+//! without touching the network, so the two commands always agree.
+
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use super::skill::parse_version;
+
+/// An installed registry skill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledVersion {
+    /// Registry name (`owner/name`)
+    pub name: String,
+    pub version: String,
+    /// Held at its version by an exact (`=x.y.z`) constraint in `paks.toml`
+    pub pinned: bool,
+}
+
+/// What to do with one installed skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum UpdateDecision {
+    /// A newer version is available
+    Upgrade { available: String },
+    /// Already at the latest version
+    Unchanged,
+    /// A newer version is available but the skill is pinned
+    Pinned { available: String },
+    /// The registry no longer knows this skill
+    NotInRegistry,
+}
+
+/// Planned transition for one installed skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UpdatePlan {
+    pub name: String,
+    pub installed: String,
+    #[serde(flatten)]
+    pub decision: UpdateDecision,
+}
+
+impl std::fmt::Display for UpdatePlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.decision {
+            UpdateDecision::Upgrade { available } => {
+                write!(f, "{} {} → {}", self.name, self.installed, available)
+            }
+            UpdateDecision::Unchanged => write!(f, "{} {} (up to date)", self.name, self.installed),
+            UpdateDecision::Pinned { available } => write!(
+                f,
+                "{} {} (pinned, {} available)",
+                self.name, self.installed, available
+            ),
+            UpdateDecision::NotInRegistry => {
+                write!(f, "{} {} (not in registry)", self.name, self.installed)
+            }
+        }
+    }
+}
+
+/// Decide what to do with each installed skill
+///
+/// `registry` maps `owner/name` to the version the registry resolves to;
+/// skills missing from it are reported as not in the registry.
+pub fn plan_updates(
+    installed: &[InstalledVersion],
+    registry: &HashMap<String, String>,
+) -> Vec<UpdatePlan> {
+    installed
+        .iter()
+        .map(|skill| {
+            let decision = match registry.get(&skill.name) {
+                None => UpdateDecision::NotInRegistry,
+                Some(latest) if !is_newer(latest, &skill.version) => UpdateDecision::Unchanged,
+                Some(latest) if skill.pinned => UpdateDecision::Pinned {
+                    available: latest.clone(),
+                },
+                Some(latest) => UpdateDecision::Upgrade {
+                    available: latest.clone(),
+                },
+            };
+            UpdatePlan {
+                name: skill.name.clone(),
+                installed: skill.version.clone(),
+                decision,
+            }
+        })
+        .collect()
+}
+
+/// Registry name for an installed skill directory (`owner--name` → `owner/name`)
+pub fn registry_name(dir_name: &str) -> Option<String> {
+    let (owner, name) = dir_name.split_once("--")?;
+    (!owner.is_empty() && !name.is_empty()).then(|| format!("{}/{}", owner, name))
+}
+
+/// How installing `target` over `installed` changes the version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionChange {
+    Same,
+    Upgrade,
+    Downgrade,
+    /// The versions differ but can't be ordered
+    Different,
+}
+
+/// Compare an installed version with the one about to be installed
+///
+/// Versions are ordered by semver precedence, so build metadata is ignored.
+/// Versions that don't parse as semver are only compared for equality.
+pub fn compare_versions(installed: &str, target: &str) -> VersionChange {
+    match (parse_version(installed).ok(), parse_version(target).ok()) {
+        (Some(installed), Some(target)) => match target.cmp_precedence(&installed) {
+            Ordering::Equal => VersionChange::Same,
+            Ordering::Greater => VersionChange::Upgrade,
+            Ordering::Less => VersionChange::Downgrade,
+        },
+        _ if installed == target => VersionChange::Same,
+        _ => VersionChange::Different,
+    }
+}
+
+/// Whether `available` is a newer version than `installed`
+///
+/// Versions that don't parse as semver are only compared for equality.
+fn is_newer(available: &str, installed: &str) -> bool {
+    match (parse_version(available), parse_version(installed)) {
+        (Ok(available), Ok(installed)) => available.cmp_precedence(&installed) == Ordering::Greater,
+        _ => available != installed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        use VersionChange::*;
+
+        assert_eq!(compare_versions("1.2.0", "1.2.0"), Same);
+        assert_eq!(compare_versions("1.2.0", "v1.2.0"), Same);
+        assert_eq!(compare_versions("1.2.0", "1.10.0"), Upgrade);
+        assert_eq!(compare_versions("2.0.0", "1.9.9"), Downgrade);
+        assert_eq!(compare_versions("1.0.0-rc.1", "1.0.0"), Upgrade);
+        assert_eq!(compare_versions("1.0.0", "1.0.0-rc.1"), Downgrade);
+        assert_eq!(compare_versions("1.0.0+build.1", "1.0.0+build.2"), Same);
+        assert_eq!(compare_versions("latest", "latest"), Same);
+        assert_eq!(compare_versions("latest", "1.0.0"), Different);
+    }
+
+    #[test]
+    fn test_plan_updates() {
+        use UpdateDecision::*;
+
+        let upgrade = |v: &str| Upgrade {
+            available: v.to_string(),
+        };
+        let pinned = |v: &str| Pinned {
+            available: v.to_string(),
+        };
+
+        // (installed, pinned, registry version, expected decision)
+        let cases = [
+            ("1.0.0", false, Some("1.2.0"), upgrade("1.2.0")),
+            ("1.2.0", false, Some("1.2.0"), Unchanged),
+            ("1.9.0", false, Some("1.10.0"), upgrade("1.10.0")),
+            ("2.0.0", false, Some("1.5.0"), Unchanged),
+            ("1.0.0", true, Some("1.2.0"), pinned("1.2.0")),
+            ("1.2.0", true, Some("1.2.0"), Unchanged),
+            ("1.0.0", false, None, NotInRegistry),
+            ("v1.0.0", false, Some("1.0.0"), Unchanged),
+            ("1.0.0-rc.1", false, Some("1.0.1"), upgrade("1.0.1")),
+            ("1.0.0-rc.1", false, Some("1.0.0"), upgrade("1.0.0")),
+            ("1.0.0", false, Some("1.0.0-rc.2"), Unchanged),
+            ("nightly", false, Some("1.0.0"), upgrade("1.0.0")),
+        ];
+
+        for (i, (installed, is_pinned, available, expected)) in cases.into_iter().enumerate() {
+            let name = format!("acme/skill-{}", i);
+            let registry: HashMap<_, _> = available
+                .map(|v| (name.clone(), v.to_string()))
+                .into_iter()
+                .collect();
+            let skill = InstalledVersion {
+                name: name.clone(),
+                version: installed.to_string(),
+                pinned: is_pinned,
+            };
+
+            let plan = plan_updates(&[skill], &registry);
+            assert_eq!(plan[0].decision, expected, "case {}: {}", i, installed);
+        }
+    }
+
+    #[test]
+    fn test_registry_name_from_dir() {
+        assert_eq!(
+            registry_name("acme--deploy").as_deref(),
+            Some("acme/deploy")
+        );
+        assert_eq!(registry_name("deploy"), None);
+        assert_eq!(registry_name("--deploy"), None);
+    }
+}
@@ -1,14 +1,17 @@
 //! Create command - scaffold a new skill from template
 
-use anyhow::{Result, bail};
-use std::path::PathBuf;
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
 
 use super::core::skill::Skill;
+use super::install;
 
 pub struct CreateArgs {
     pub name: String,
     pub output: Option<String>,
     pub template: String,
+    /// Git URL of a template repository to scaffold from instead of `template`
+    pub template_url: Option<String>,
     pub with_scripts: bool,
     pub with_references: bool,
     pub with_assets: bool,
@@ -38,11 +41,16 @@ pub async fn run(args: CreateArgs) -> Result<()> {
     // Validate the skill before creating
     skill.frontmatter.validate()?;
 
-    // Create the output directory
-    std::fs::create_dir_all(&output_dir)?;
+    if let Some(url) = &args.template_url {
+        println!("Using template: {}", url);
+        scaffold_from_template(url, &output_dir, &args.name, &description).await?;
+    } else {
+        // Create the output directory
+        std::fs::create_dir_all(&output_dir)?;
 
-    // Save the SKILL.md
-    skill.save()?;
+        // Save the SKILL.md
+        skill.save()?;
+    }
 
     println!(
         "✓ Created skill '{}' in '{}'",
@@ -96,3 +104,147 @@ pub async fn run(args: CreateArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Scaffold `output_dir` from a template repository
+///
+/// The repository (or the `path` given in its URL fragment) must contain a
+/// SKILL.md. `{{name}}` and `{{description}}` are substituted in every text
+/// file, and the rendered SKILL.md must be a valid skill.
+async fn scaffold_from_template(
+    url: &str,
+    output_dir: &Path,
+    name: &str,
+    description: &str,
+) -> Result<()> {
+    let (url, git_ref, subpath) = install::parse_git_url(url);
+    let (template_dir, clone) = install::clone_git_repo(
+        &url,
+        git_ref.as_deref(),
+        None,
+        subpath.as_deref(),
+        None,
+        install::CloneOptions::default(),
+    )
+    .await
+    .with_context(|| format!("Failed to fetch template from {}", url))?;
+
+    install::copy_skill_to_target(&template_dir, output_dir)?;
+    drop(clone);
+
+    if let Err(e) = render_placeholders(output_dir, name, description)
+        .and_then(|()| Skill::load(output_dir).context("Rendered template is not a valid skill"))
+    {
+        // Don't leave a half-rendered skill behind
+        std::fs::remove_dir_all(output_dir).ok();
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Replace template placeholders in every UTF-8 file below `dir`
+fn render_placeholders(dir: &Path, name: &str, description: &str) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            render_placeholders(&path, name, description)?;
+            continue;
+        }
+        // Binary files are copied as-is
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        if content.contains("{{") {
+            let rendered = content
+                .replace("{{name}}", name)
+                .replace("{{description}}", description);
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::core::git;
+
+    /// Template repository with a placeholder SKILL.md and a helper script
+    fn template_repo(skill_md: &str) -> tempfile::TempDir {
+        let repo = tempfile::tempdir().unwrap();
+        let root = repo.path();
+        std::fs::write(root.join("SKILL.md"), skill_md).unwrap();
+        std::fs::create_dir_all(root.join("scripts")).unwrap();
+        std::fs::write(root.join("scripts/run.sh"), "echo {{name}}\n").unwrap();
+
+        for args in [
+            &["init", "-q"][..],
+            &["add", "."],
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "init",
+            ],
+        ] {
+            git::git_cmd(args, root).unwrap();
+        }
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_scaffold_from_template_repo() {
+        let repo =
+            template_repo("---\nname: {{name}}\ndescription: {{description}}\n---\n# {{name}}\n");
+        let out = tempfile::tempdir().unwrap();
+        let output_dir = out.path().join("my-skill");
+        let url = format!("file://{}", repo.path().display());
+
+        scaffold_from_template(&url, &output_dir, "my-skill", "Does things")
+            .await
+            .unwrap();
+
+        let skill = Skill::load(&output_dir).unwrap();
+        assert_eq!(skill.name(), "my-skill");
+        assert_eq!(
+            std::fs::read_to_string(output_dir.join("scripts/run.sh")).unwrap(),
+            "echo my-skill\n"
+        );
+        assert!(!output_dir.join(".git").exists());
+    }
+
+    #[tokio::test]
+    async fn test_scaffold_rejects_template_without_skill_md() {
+        let repo = template_repo("unused");
+        std::fs::remove_file(repo.path().join("SKILL.md")).unwrap();
+        git::git_cmd(&["add", "-A"], repo.path()).unwrap();
+        git::git_cmd(
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "rm",
+            ],
+            repo.path(),
+        )
+        .unwrap();
+        let out = tempfile::tempdir().unwrap();
+        let url = format!("file://{}", repo.path().display());
+
+        let err = scaffold_from_template(&url, &out.path().join("x"), "x", "d")
+            .await
+            .unwrap_err();
+
+        assert!(format!("{:#}", err).contains("No SKILL.md found"));
+        assert!(!out.path().join("x").exists());
+    }
+}
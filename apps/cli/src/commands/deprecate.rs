@@ -0,0 +1,67 @@
+//! Deprecate command - mark a published pak as deprecated (or undo it)
+
+use anyhow::{Result, bail};
+use paks_api::{ApiError, PakStatus, SkillRef};
+
+use super::core::config::Config;
+use super::core::token;
+
+pub struct DeprecateArgs {
+    /// Pak reference (owner/name)
+    pub pak: String,
+    /// Message shown to users installing the pak
+    pub message: Option<String>,
+    /// Deprecate (true) or restore to active (false)
+    pub deprecated: bool,
+}
+
+pub async fn run(args: DeprecateArgs) -> Result<()> {
+    let skill_ref = SkillRef::parse(&args.pak)?;
+    if skill_ref.version().is_some() {
+        bail!("Deprecation applies to a whole pak, not a single version");
+    }
+
+    let config = Config::load()?;
+    let client = token::authenticated_client(&config).await?;
+
+    let status = if args.deprecated {
+        PakStatus::Deprecated
+    } else {
+        PakStatus::Active
+    };
+
+    let uri = skill_ref.to_uri();
+    match client
+        .set_pak_status(
+            skill_ref.account(),
+            skill_ref.name(),
+            status,
+            args.message.as_deref(),
+        )
+        .await
+    {
+        Ok(()) => {}
+        Err(ApiError::NotFound(_)) => bail!("Pak '{}' not found in registry", uri),
+        Err(ApiError::Api { status: 403, .. }) => {
+            bail!(
+                "You don't have permission to change the status of '{}'",
+                uri
+            )
+        }
+        Err(ApiError::AuthRequired | ApiError::InvalidToken) => {
+            bail!("Not authenticated. Run 'paks login' first.")
+        }
+        Err(e) => bail!("Failed to update '{}': {}", uri, e),
+    }
+
+    if args.deprecated {
+        println!("✓ Marked {} as deprecated", uri);
+        if let Some(message) = &args.message {
+            println!("  Message: {}", message);
+        }
+    } else {
+        println!("✓ Marked {} as active", uri);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,318 @@
+//! Diff command - show what changed between an installed skill and the
+//! latest registry version, or between two registry versions
+
+use anyhow::{Result, bail};
+use paks_api::{ApiError, PakContent, SkillRef};
+use paks_core::skill_md::{parse_skill_md, split_skill_md};
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::core::client::registry_client;
+use super::core::config::Config;
+use super::core::content::ContentSource;
+use super::core::style::Style;
+use super::install::install_target;
+use super::tree::{TreeEntry, local_entries, registry_entries};
+
+pub struct DiffArgs {
+    /// Registry reference (owner/name)
+    pub skill: String,
+    /// Registry version to diff from (defaults to the installed copy)
+    pub from: Option<String>,
+    /// Registry version to diff to (defaults to the latest)
+    pub to: Option<String>,
+    /// Agent whose installed copy to diff
+    pub agent: Option<String>,
+}
+
+/// One side of the diff
+struct Snapshot {
+    label: String,
+    skill_md: String,
+    /// File paths relative to the skill root, with their sizes when known
+    files: BTreeMap<String, Option<u64>>,
+}
+
+/// How a file differs between the two sides
+#[derive(Debug, PartialEq, Eq)]
+enum FileChange {
+    Added(String),
+    Removed(String),
+    Changed(String),
+}
+
+impl FileChange {
+    fn path(&self) -> &str {
+        match self {
+            FileChange::Added(path) | FileChange::Removed(path) | FileChange::Changed(path) => path,
+        }
+    }
+}
+
+pub async fn run(args: DiffArgs) -> Result<()> {
+    let skill_ref = SkillRef::parse(&args.skill)?;
+    if let Some(version) = skill_ref.version() {
+        bail!(
+            "Pass versions with --from and --to.\n\
+             Hint: Use 'paks diff {}/{} --to {}'",
+            skill_ref.account(),
+            skill_ref.name(),
+            version
+        );
+    }
+    let base = skill_ref.to_uri();
+
+    let config = Config::load()?;
+    let client = registry_client(&config)?;
+
+    let from = match &args.from {
+        Some(version) => registry_snapshot(&client, &base, Some(version)).await?,
+        None => {
+            let (skills_dir, _) = install_target(None, args.agent.as_deref())?;
+            let dir = skills_dir.join(format!("{}--{}", skill_ref.account(), skill_ref.name()));
+            if !dir.join("SKILL.md").is_file() {
+                bail!(
+                    "{} is not installed in {}.\n\
+                     Hint: Pass --from <version> to compare two registry versions",
+                    base,
+                    skills_dir.display()
+                );
+            }
+            local_snapshot(&dir)?
+        }
+    };
+    let to = registry_snapshot(&client, &base, args.to.as_deref()).await?;
+
+    let style = Style::current();
+    println!("{} {} → {}", style.bold(&base), from.label, to.label);
+
+    let body = render_body_diff(&from.skill_md, &to.skill_md, &from.label, &to.label);
+    let changes = file_changes(&from.files, &to.files);
+    if body.is_empty() && changes.is_empty() {
+        println!("No changes.");
+        return Ok(());
+    }
+
+    if !body.is_empty() {
+        println!();
+        for line in body.lines() {
+            println!("{}", colorize(line, &style));
+        }
+    }
+    if !changes.is_empty() {
+        println!();
+        println!("{}", style.bold("Files:"));
+        print!("{}", render_file_changes(&changes));
+    }
+
+    Ok(())
+}
+
+/// The installed copy of a skill
+fn local_snapshot(dir: &Path) -> Result<Snapshot> {
+    let skill_md = std::fs::read_to_string(dir.join("SKILL.md"))?;
+    let version = skill_md_version(&skill_md).unwrap_or_else(|| "unknown".to_string());
+    Ok(Snapshot {
+        label: format!("{} (installed)", version),
+        skill_md,
+        files: file_sizes(local_entries(dir, None)?),
+    })
+}
+
+/// A registry version of a pak, or its latest version when `version` is `None`
+async fn registry_snapshot(
+    source: &impl ContentSource,
+    base: &str,
+    version: Option<&str>,
+) -> Result<Snapshot> {
+    let uri = match version {
+        Some(version) => format!("{}@{}", base, version),
+        None => base.to_string(),
+    };
+
+    let skill_md_uri = format!("{}/SKILL.md", uri);
+    let skill_md = match source.fetch(&skill_md_uri).await {
+        Ok(response) => match response.content {
+            PakContent::File { content } => content,
+            PakContent::Directory { .. } => bail!("Expected a file for {}", skill_md_uri),
+        },
+        Err(ApiError::NotFound(_)) => bail!("'{}' not found in registry", uri),
+        Err(e) => bail!("Failed to fetch {}: {}", skill_md_uri, e),
+    };
+
+    let label = match version {
+        Some(version) => version.to_string(),
+        None => match skill_md_version(&skill_md) {
+            Some(version) => format!("{} (latest)", version),
+            None => "latest".to_string(),
+        },
+    };
+    let files = file_sizes(registry_entries(source, &uri, None).await?);
+    Ok(Snapshot {
+        label,
+        skill_md,
+        files,
+    })
+}
+
+fn skill_md_version(skill_md: &str) -> Option<String> {
+    let (frontmatter, _) = parse_skill_md(skill_md).ok()?;
+    frontmatter.metadata?.get("version").cloned()
+}
+
+fn file_sizes(entries: Vec<TreeEntry>) -> BTreeMap<String, Option<u64>> {
+    entries
+        .into_iter()
+        .filter(|entry| !entry.is_dir)
+        .map(|entry| (entry.components.join("/"), entry.size))
+        .collect()
+}
+
+/// Unified diff of the instructions in two SKILL.md files, empty when they
+/// match
+///
+/// Only the body is compared; a SKILL.md without frontmatter is compared
+/// whole.
+fn render_body_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let body = |content: &str| match split_skill_md(content) {
+        Ok((_, body)) => format!("{}\n", body),
+        Err(_) => content.to_string(),
+    };
+    let (old, new) = (body(old), body(new));
+    if old == new {
+        return String::new();
+    }
+
+    TextDiff::from_lines(&old, &new)
+        .unified_diff()
+        .context_radius(3)
+        .header(
+            &format!("SKILL.md {}", old_label),
+            &format!("SKILL.md {}", new_label),
+        )
+        .to_string()
+}
+
+/// Files added, removed or changed between two listings, sorted by path
+///
+/// A file counts as changed when both sizes are known and differ; SKILL.md
+/// is left out since its instructions are diffed in full.
+fn file_changes(
+    old: &BTreeMap<String, Option<u64>>,
+    new: &BTreeMap<String, Option<u64>>,
+) -> Vec<FileChange> {
+    let mut changes = Vec::new();
+    for (path, size) in old {
+        if path == "SKILL.md" {
+            continue;
+        }
+        match new.get(path) {
+            None => changes.push(FileChange::Removed(path.clone())),
+            Some(new_size) => {
+                if let (Some(a), Some(b)) = (size, new_size)
+                    && a != b
+                {
+                    changes.push(FileChange::Changed(path.clone()));
+                }
+            }
+        }
+    }
+    for path in new.keys() {
+        if path != "SKILL.md" && !old.contains_key(path) {
+            changes.push(FileChange::Added(path.clone()));
+        }
+    }
+    changes.sort_by(|a, b| a.path().cmp(b.path()));
+    changes
+}
+
+fn render_file_changes(changes: &[FileChange]) -> String {
+    let mut out = String::new();
+    for change in changes {
+        let (marker, path) = match change {
+            FileChange::Added(path) => ("+", path),
+            FileChange::Removed(path) => ("-", path),
+            FileChange::Changed(path) => ("~", path),
+        };
+        out.push_str(&format!("  {} {}\n", marker, path));
+    }
+    out
+}
+
+/// Color a line of unified diff output
+fn colorize(line: &str, style: &Style) -> String {
+    if line.starts_with("+++") || line.starts_with("---") {
+        style.bold(line)
+    } else if line.starts_with('+') {
+        style.green(line)
+    } else if line.starts_with('-') {
+        style.red(line)
+    } else if line.starts_with("@@") {
+        style.cyan(line)
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "---\nname: deploy\ndescription: Deploy things\nmetadata:\n  version: \"1.0.0\"\n---\n\n# Deploy\n\nRun the deploy script.\n\nCheck the logs.\n";
+    const NEW: &str = "---\nname: deploy\ndescription: Deploy things safely\nmetadata:\n  version: \"1.1.0\"\n---\n\n# Deploy\n\nRun the deploy script with --dry-run first.\n\nCheck the logs.\n";
+
+    #[test]
+    fn test_render_body_diff() {
+        let diff = render_body_diff(OLD, NEW, "1.0.0 (installed)", "1.1.0 (latest)");
+        assert_eq!(
+            diff,
+            "--- SKILL.md 1.0.0 (installed)\n\
+             +++ SKILL.md 1.1.0 (latest)\n\
+             @@ -1,5 +1,5 @@\n \
+             # Deploy\n \n\
+             -Run the deploy script.\n\
+             +Run the deploy script with --dry-run first.\n \n \
+             Check the logs.\n"
+        );
+    }
+
+    #[test]
+    fn test_render_body_diff_ignores_frontmatter() {
+        let bumped = OLD.replace("1.0.0", "1.0.1");
+        assert_eq!(render_body_diff(OLD, &bumped, "a", "b"), "");
+        assert_eq!(skill_md_version(&bumped).as_deref(), Some("1.0.1"));
+    }
+
+    #[test]
+    fn test_file_changes() {
+        let old = BTreeMap::from([
+            ("SKILL.md".to_string(), Some(100)),
+            ("scripts/deploy.sh".to_string(), Some(40)),
+            ("references/api.md".to_string(), Some(10)),
+            ("references/old.md".to_string(), Some(5)),
+            ("assets/logo.png".to_string(), None),
+        ]);
+        let new = BTreeMap::from([
+            ("SKILL.md".to_string(), Some(120)),
+            ("scripts/deploy.sh".to_string(), Some(55)),
+            ("references/api.md".to_string(), Some(10)),
+            ("references/new.md".to_string(), Some(8)),
+            ("assets/logo.png".to_string(), Some(2048)),
+        ]);
+
+        let changes = file_changes(&old, &new);
+        assert_eq!(
+            changes,
+            [
+                FileChange::Added("references/new.md".to_string()),
+                FileChange::Removed("references/old.md".to_string()),
+                FileChange::Changed("scripts/deploy.sh".to_string()),
+            ]
+        );
+        assert_eq!(
+            render_file_changes(&changes),
+            "  + references/new.md\n  - references/old.md\n  ~ scripts/deploy.sh\n"
+        );
+    }
+}
@@ -0,0 +1,692 @@
+//! Doctor command - check the environment, then find and repair problems
+//! with installed skills
+
+use anyhow::{Context, Result, bail};
+use paks_api::ApiError;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::core::checksum;
+use super::core::client;
+use super::core::config::Config;
+use super::core::git;
+use super::core::keychain::OsKeychain;
+use super::core::manifest::{self, LockedSkill};
+use super::core::skill::Skill;
+use super::core::update;
+use super::install::{self, InstallArgs, install_target};
+
+pub struct DoctorArgs {
+    pub agent: Option<String>,
+    /// Offer to repair each problem found
+    pub fix: bool,
+    /// Apply every fix without prompting
+    pub yes: bool,
+}
+
+/// A problem with the installed skills
+#[derive(Debug, PartialEq, Eq)]
+enum Issue {
+    /// Directory in the skills dir that doesn't hold a skill
+    Orphaned(PathBuf),
+    /// Script without execute permission
+    NotExecutable(PathBuf),
+    /// Registry skill installed as `owner/name` instead of `owner--name`
+    NestedLayout { from: PathBuf, to: PathBuf },
+    /// Installed version differs from the one recorded in paks.lock
+    LockMismatch {
+        name: String,
+        installed: String,
+        locked: String,
+    },
+    /// Installed files no longer match the checksum recorded in paks.lock
+    Modified { name: String, version: String },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Orphaned(path) => write!(f, "{} is not a skill (no SKILL.md)", path.display()),
+            Issue::NotExecutable(path) => write!(f, "{} is not executable", path.display()),
+            Issue::NestedLayout { from, to } => write!(
+                f,
+                "{} uses a nested layout; expected {}",
+                from.display(),
+                to.display()
+            ),
+            Issue::LockMismatch {
+                name,
+                installed,
+                locked,
+            } => write!(
+                f,
+                "{} is at {} but paks.lock has {}",
+                name, installed, locked
+            ),
+            Issue::Modified { name, .. } => write!(
+                f,
+                "{} was modified after install; its checksum differs from paks.lock",
+                name
+            ),
+        }
+    }
+}
+
+impl Issue {
+    /// What fixing the issue does, for the confirmation prompt
+    fn fix_description(&self) -> String {
+        match self {
+            Issue::Orphaned(path) => format!("Remove {}", path.display()),
+            Issue::NotExecutable(path) => format!("Make {} executable", path.display()),
+            Issue::NestedLayout { from, to } => {
+                format!("Move {} to {}", from.display(), to.display())
+            }
+            Issue::LockMismatch {
+                name,
+                locked: version,
+                ..
+            }
+            | Issue::Modified { name, version } => format!("Reinstall {}@{}", name, version),
+        }
+    }
+}
+
+/// Outcome of an environment check; a failure makes the command exit non-zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One line of the environment checklist
+#[derive(Debug)]
+struct Check {
+    status: Status,
+    message: String,
+}
+
+impl Check {
+    fn new(status: Status, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.status {
+            Status::Pass => "✓",
+            Status::Warn => "⚠",
+            Status::Fail => "✗",
+        };
+        write!(f, "{} {}", marker, self.message)
+    }
+}
+
+pub async fn run(args: DoctorArgs) -> Result<()> {
+    println!("Checking environment");
+    let checks = check_environment().await;
+    for check in &checks {
+        println!("  {}", check);
+    }
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    println!();
+
+    check_skills(&args).await?;
+
+    if failed > 0 {
+        bail!("{} environment check(s) failed", failed);
+    }
+    Ok(())
+}
+
+/// Run the environment checklist
+///
+/// A config that fails to load is reported, and the remaining checks use
+/// the defaults.
+async fn check_environment() -> Vec<Check> {
+    let mut checks = vec![check_git()];
+
+    let config = match Config::load() {
+        Ok(config) => {
+            let path = Config::path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| "~/.paks/config.toml".to_string());
+            checks.push(Check::new(
+                Status::Pass,
+                format!("Config {} is valid", path),
+            ));
+            config
+        }
+        Err(e) => {
+            checks.push(Check::new(Status::Fail, format!("Config: {:#}", e)));
+            Config::default()
+        }
+    };
+
+    checks.extend(check_skills_dirs(&config));
+    checks.extend(check_registry(&config).await);
+    checks
+}
+
+fn check_git() -> Check {
+    match git::git_version() {
+        Some((major, minor)) if git::supports_sparse_checkout() => {
+            Check::new(Status::Pass, format!("git {}.{}", major, minor))
+        }
+        Some((major, minor)) => Check::new(
+            Status::Warn,
+            format!(
+                "git {}.{} is older than 2.27; skills in monorepos are cloned in full",
+                major, minor
+            ),
+        ),
+        None => Check::new(
+            Status::Fail,
+            "git is not installed or not on PATH (needed to install and publish skills)",
+        ),
+    }
+}
+
+/// Each agent's skills directory must be a writable directory
+///
+/// A missing directory is created on first install, so it's only mentioned
+/// for the default agent.
+fn check_skills_dirs(config: &Config) -> Vec<Check> {
+    let default_dir = config
+        .get_default_agent()
+        .map(|a| a.skills_dir.clone())
+        .unwrap_or_else(Config::default_skills_dir);
+
+    let mut dirs: Vec<(String, PathBuf)> = config
+        .agents
+        .iter()
+        .map(|(name, agent)| (name.clone(), agent.skills_dir.clone()))
+        .collect();
+    if config.get_default_agent().is_none() {
+        dirs.push(("default".to_string(), default_dir.clone()));
+    }
+
+    dirs.into_iter()
+        .filter_map(|(name, dir)| {
+            let label = format!("Skills dir for {} ({})", name, dir.display());
+            if !dir.exists() {
+                return (dir == default_dir).then(|| {
+                    Check::new(
+                        Status::Warn,
+                        format!(
+                            "{} does not exist yet; it's created on first install",
+                            label
+                        ),
+                    )
+                });
+            }
+            Some(if !dir.is_dir() {
+                Check::new(Status::Fail, format!("{} is not a directory", label))
+            } else if !is_writable(&dir) {
+                Check::new(Status::Fail, format!("{} is not writable", label))
+            } else {
+                Check::new(Status::Pass, label)
+            })
+        })
+        .collect()
+}
+
+/// Whether a file can be created in `dir`
+fn is_writable(dir: &Path) -> bool {
+    tempfile::Builder::new()
+        .prefix(".paks-doctor-")
+        .tempfile_in(dir)
+        .is_ok()
+}
+
+/// The default registry must be reachable; a configured token should be valid
+async fn check_registry(config: &Config) -> Vec<Check> {
+    let client = match config
+        .resolve_registry_url(None)
+        .and_then(|url| Ok(client::builder(url).build()?))
+    {
+        Ok(client) => client,
+        Err(e) => return vec![Check::new(Status::Fail, format!("Registry: {:#}", e))],
+    };
+    let url = client.base_url().to_string();
+
+    let reachable = match client.health().await {
+        Ok(true) => Check::new(Status::Pass, format!("Registry {} is reachable", url)),
+        Ok(false) => Check::new(
+            Status::Warn,
+            format!("Registry {} reports it is unhealthy", url),
+        ),
+        Err(ApiError::Unreachable(_)) => {
+            Check::new(Status::Fail, format!("Registry {} is unreachable", url))
+        }
+        Err(e) => Check::new(Status::Fail, format!("Registry {}: {}", url, e)),
+    };
+    if reachable.status == Status::Fail {
+        return vec![reachable];
+    }
+
+    let token = match config.get_auth_token(&OsKeychain) {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            return vec![
+                reachable,
+                Check::new(Status::Warn, "Not logged in (run 'paks login' to publish)"),
+            ];
+        }
+        Err(e) => return vec![reachable, Check::new(Status::Fail, format!("{:#}", e))],
+    };
+
+    let mut client = client;
+    client.set_token(token);
+    let auth = match client.verify_token().await {
+        Ok(verified) => Check::new(
+            Status::Pass,
+            format!("Logged in as {}", verified.user.username),
+        ),
+        Err(ApiError::InvalidToken | ApiError::AuthRequired) => Check::new(
+            Status::Fail,
+            "Token was rejected by the registry (run 'paks login' again)",
+        ),
+        Err(e) => Check::new(Status::Warn, format!("Could not verify token: {}", e)),
+    };
+    vec![reachable, auth]
+}
+
+/// Diagnose the skills installed for `args.agent` and offer fixes
+async fn check_skills(args: &DoctorArgs) -> Result<()> {
+    let (skills_dir, _) = install_target(None, args.agent.as_deref())?;
+
+    let locked = match manifest::find_project_root(&std::env::current_dir()?) {
+        Some(root) => manifest::load_lock(&root)?,
+        None => Vec::new(),
+    };
+
+    println!("Checking {}", skills_dir.display());
+    let issues = diagnose(&skills_dir, &locked)?;
+    if issues.is_empty() {
+        println!("✓ No problems found");
+        return Ok(());
+    }
+
+    for issue in &issues {
+        println!("  ⚠ {}", issue);
+    }
+
+    if !args.fix {
+        println!();
+        println!(
+            "Found {} {}. Run 'paks doctor --fix' to repair.",
+            issues.len(),
+            if issues.len() == 1 {
+                "problem"
+            } else {
+                "problems"
+            }
+        );
+        return Ok(());
+    }
+
+    println!();
+    let mut fixed = 0;
+    for issue in &issues {
+        if !confirm_fix(issue, args.yes)? {
+            println!("  - Skipped: {}", issue);
+            continue;
+        }
+        match apply_fix(issue, &skills_dir, args.yes).await {
+            Ok(()) => {
+                println!("  ✓ {}", issue.fix_description());
+                fixed += 1;
+            }
+            Err(e) => println!("  ✗ {}: {:#}", issue.fix_description(), e),
+        }
+    }
+
+    println!();
+    println!("Fixed {} of {} problems", fixed, issues.len());
+    Ok(())
+}
+
+/// Problems with the skills installed in `skills_dir`
+///
+/// `locked` are the entries of the current project's paks.lock, if any.
+fn diagnose(skills_dir: &Path, locked: &[LockedSkill]) -> Result<Vec<Issue>> {
+    if !skills_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(skills_dir)
+        .with_context(|| format!("Failed to read {}", skills_dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    entries.sort();
+
+    let mut issues = Vec::new();
+    for dir in entries {
+        let dir_name = dir.file_name().map(|n| n.to_string_lossy().into_owned());
+        let Some(dir_name) = dir_name.filter(|n| !n.starts_with('.')) else {
+            continue;
+        };
+
+        if !dir.join("SKILL.md").is_file() {
+            let nested = nested_skills(&dir);
+            if nested.is_empty() {
+                issues.push(Issue::Orphaned(dir));
+                continue;
+            }
+            for name in nested {
+                issues.push(Issue::NestedLayout {
+                    from: dir.join(&name),
+                    to: skills_dir.join(format!("{}--{}", dir_name, name)),
+                });
+            }
+            continue;
+        }
+
+        issues.extend(
+            non_executable_scripts(&dir.join("scripts"))
+                .into_iter()
+                .map(Issue::NotExecutable),
+        );
+
+        if let Some(name) = update::registry_name(&dir_name)
+            && let Some(entry) = locked.iter().find(|l| l.name == name)
+            && let Ok(skill) = Skill::load(&dir)
+        {
+            if skill.version() != entry.version {
+                issues.push(Issue::LockMismatch {
+                    name,
+                    installed: skill.version().to_string(),
+                    locked: entry.version.clone(),
+                });
+            } else if let Some(expected) = &entry.checksum
+                && let Ok(actual) = checksum::compute(&dir)
+                && !checksum::same(&actual, expected)
+            {
+                issues.push(Issue::Modified {
+                    name,
+                    version: entry.version.clone(),
+                });
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Names of subdirectories of `dir` that hold a skill
+fn nested_skills(dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().join("SKILL.md").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Files in `scripts_dir` that nobody can execute
+#[cfg(unix)]
+fn non_executable_scripts(scripts_dir: &Path) -> Vec<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(entries) = std::fs::read_dir(scripts_dir) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .metadata()
+                .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 == 0)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+#[cfg(not(unix))]
+fn non_executable_scripts(_scripts_dir: &Path) -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Ask whether to apply the fix for `issue` (unless --yes)
+fn confirm_fix(issue: &Issue, skip_confirm: bool) -> Result<bool> {
+    if skip_confirm {
+        return Ok(true);
+    }
+
+    print!("{}? [y/N] ", issue.fix_description());
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y") || input.trim().eq_ignore_ascii_case("yes"))
+}
+
+/// Repair `issue`; with `yes`, reinstalls don't ask before large downloads
+async fn apply_fix(issue: &Issue, skills_dir: &Path, yes: bool) -> Result<()> {
+    match issue {
+        Issue::Orphaned(path) => std::fs::remove_dir_all(path)
+            .with_context(|| format!("Failed to remove {}", path.display())),
+        Issue::NotExecutable(path) => make_executable(path),
+        Issue::NestedLayout { from, to } => {
+            if to.exists() {
+                bail!("{} already exists", to.display());
+            }
+            std::fs::rename(from, to)
+                .with_context(|| format!("Failed to move {}", from.display()))?;
+            // Drop the owner directory once its last skill has moved out
+            if let Some(owner_dir) = from.parent()
+                && std::fs::read_dir(owner_dir).is_ok_and(|mut e| e.next().is_none())
+            {
+                std::fs::remove_dir(owner_dir).ok();
+            }
+            Ok(())
+        }
+        Issue::LockMismatch {
+            name,
+            locked: version,
+            ..
+        }
+        | Issue::Modified { name, version } => {
+            install::run(InstallArgs {
+                sources: vec![format!("{}@{}", name, version)],
+                dir: Some(skills_dir.to_string_lossy().into_owned()),
+                force: true,
+                allow_yanked: true,
+                // paks.lock is authoritative, even if it's behind
+                allow_downgrade: true,
+                yes,
+                git_depth: 1,
+                ..Default::default()
+            })
+            .await
+        }
+    }
+}
+
+/// Add execute permission wherever the file is readable
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    let mode = permissions.mode();
+    permissions.set_mode(mode | ((mode & 0o444) >> 2));
+    std::fs::set_permissions(path, permissions)
+        .with_context(|| format!("Failed to update permissions of {}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::core::config::AgentConfig;
+    use crate::commands::test_support::install_fake_skill;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_check_skills_dirs() {
+        let writable = tempdir().unwrap();
+        let not_a_dir = writable.path().join("file");
+        std::fs::write(&not_a_dir, "").unwrap();
+
+        let mut config = Config::default();
+        for (name, dir) in [
+            ("ok", writable.path().to_path_buf()),
+            ("file", not_a_dir),
+            ("missing", writable.path().join("missing")),
+        ] {
+            config.agents.insert(
+                name.to_string(),
+                AgentConfig {
+                    name: name.to_string(),
+                    skills_dir: dir,
+                    description: None,
+                },
+            );
+        }
+
+        let statuses: Vec<Status> = check_skills_dirs(&config)
+            .iter()
+            .filter(|c| !c.message.contains("for default"))
+            .map(|c| c.status)
+            .collect();
+        // A missing dir is only reported for the default agent
+        assert_eq!(statuses, [Status::Pass, Status::Fail]);
+
+        config.default_agent = Some("missing".to_string());
+        let checks = check_skills_dirs(&config);
+        assert_eq!(checks.len(), 3);
+        assert_eq!(checks[2].status, Status::Warn);
+    }
+
+    #[tokio::test]
+    async fn test_fix_orphaned_dir() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "deploy", "0.1.0");
+        std::fs::create_dir(skills.path().join("leftover")).unwrap();
+
+        let issues = diagnose(skills.path(), &[]).unwrap();
+        assert_eq!(
+            issues,
+            vec![Issue::Orphaned(skills.path().join("leftover"))]
+        );
+
+        apply_fix(&issues[0], skills.path(), false).await.unwrap();
+        assert!(!skills.path().join("leftover").exists());
+        assert!(skills.path().join("deploy").exists());
+        assert!(diagnose(skills.path(), &[]).unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_fix_execute_bit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let skills = tempdir().unwrap();
+        let skill = skills.path().join("deploy");
+        install_fake_skill(skills.path(), "deploy", "0.1.0");
+        std::fs::create_dir(skill.join("scripts")).unwrap();
+        let script = skill.join("scripts").join("run.sh");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        let issues = diagnose(skills.path(), &[]).unwrap();
+        assert_eq!(issues, vec![Issue::NotExecutable(script.clone())]);
+
+        apply_fix(&issues[0], skills.path(), false).await.unwrap();
+        let mode = std::fs::metadata(&script).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+        assert!(diagnose(skills.path(), &[]).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fix_nested_layout() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(&skills.path().join("acme"), "deploy", "0.1.0");
+
+        let issues = diagnose(skills.path(), &[]).unwrap();
+        assert_eq!(
+            issues,
+            vec![Issue::NestedLayout {
+                from: skills.path().join("acme").join("deploy"),
+                to: skills.path().join("acme--deploy"),
+            }]
+        );
+
+        apply_fix(&issues[0], skills.path(), false).await.unwrap();
+        assert!(
+            skills
+                .path()
+                .join("acme--deploy")
+                .join("SKILL.md")
+                .is_file()
+        );
+        assert!(!skills.path().join("acme").exists());
+    }
+
+    #[test]
+    fn test_diagnose_lock_mismatch() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "acme--deploy", "0.1.0");
+        let locked = vec![LockedSkill {
+            name: "acme/deploy".to_string(),
+            version: "1.2.0".to_string(),
+            source: "registry".to_string(),
+            commit: None,
+            checksum: None,
+        }];
+
+        assert_eq!(
+            diagnose(skills.path(), &locked).unwrap(),
+            vec![Issue::LockMismatch {
+                name: "acme/deploy".to_string(),
+                installed: "0.1.0".to_string(),
+                locked: "1.2.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diagnose_modified_skill() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "acme--deploy", "1.2.0");
+        let dir = skills.path().join("acme--deploy");
+        let mut locked = vec![LockedSkill {
+            name: "acme/deploy".to_string(),
+            version: "1.2.0".to_string(),
+            source: "registry".to_string(),
+            commit: None,
+            checksum: Some(checksum::compute(&dir).unwrap()),
+        }];
+        assert!(diagnose(skills.path(), &locked).unwrap().is_empty());
+
+        std::fs::write(dir.join("notes.md"), "local edit").unwrap();
+        let modified = Issue::Modified {
+            name: "acme/deploy".to_string(),
+            version: "1.2.0".to_string(),
+        };
+        assert_eq!(diagnose(skills.path(), &locked).unwrap(), vec![modified]);
+
+        // Without a recorded checksum there is nothing to compare
+        locked[0].checksum = None;
+        assert!(diagnose(skills.path(), &locked).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+//! External subcommands - run `paks-<name>` executables from PATH
+//!
+//! Like git, `paks foo args...` runs `paks-foo args...` when `foo` isn't a
+//! built-in command, so the CLI can be extended without modifying it.
+
+use anyhow::{Context, Result};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+/// Prefix of external subcommand executables
+const PREFIX: &str = "paks-";
+
+/// Find the `paks-<name>` executable in the directories of `path_var`
+pub fn find_executable(name: &str, path_var: &OsStr) -> Option<PathBuf> {
+    let file_name = format!("{}{}{}", PREFIX, name, std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(path_var)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Run an external subcommand with the remaining arguments
+pub fn run(program: &Path, args: &[OsString]) -> Result<ExitStatus> {
+    Command::new(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to run {}", program.display()))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_dispatches_to_path_executable_with_args() {
+        let bin = tempdir().unwrap();
+        let out = bin.path().join("args.txt");
+        let stub = bin.path().join("paks-foo");
+        std::fs::write(
+            &stub,
+            format!("#!/bin/sh\nprintf '%s\\n' \"$@\" > '{}'\n", out.display()),
+        )
+        .unwrap();
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let path_var = std::env::join_paths([Path::new("/nonexistent"), bin.path()]).unwrap();
+        let program = find_executable("foo", &path_var).unwrap();
+        assert_eq!(program, stub);
+
+        let status = run(&program, &["--flag".into(), "two words".into()]).unwrap();
+        assert!(status.success());
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "--flag\ntwo words\n"
+        );
+    }
+
+    #[test]
+    fn test_non_executable_is_ignored() {
+        let bin = tempdir().unwrap();
+        std::fs::write(bin.path().join("paks-bar"), "not a program").unwrap();
+
+        assert_eq!(find_executable("bar", bin.path().as_os_str()), None);
+        assert_eq!(find_executable("baz", bin.path().as_os_str()), None);
+    }
+}
@@ -1,13 +1,130 @@
 //! Info command - show details about a skill
+//!
+//! Works on a local skill directory, a skill installed for the default agent
+//! (by directory name or an alias left by `paks rename`), or a registry
+//! reference (`owner/name[@version]`). A registry reference may be followed by a path
+//! inside the pak (`owner/name@1.0.0/scripts`) to show that file or
+//! directory instead.
+//!
+//! Registry skills are shown with their registry stats; `--remote` adds them
+//! for installed skills too, along with whether an update is available.
 
-use anyhow::{Result, bail};
-use std::path::Path;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use paks_api::{
+    ApiError, ContentItem, ContentItemType, Pak, PakContent, PakVersion, PakVersionStatus,
+    PakVisibility, PaksClient, SkillRef,
+};
+use std::path::{Path, PathBuf};
 
+use super::core::client::registry_client;
+use super::core::config::Config;
+use super::core::content::ContentSource;
 use super::core::skill::Skill;
+use super::core::update::{self, VersionChange};
+use super::search::format_count;
 
 pub struct InfoArgs {
     pub skill: String,
     pub full: bool,
+    /// Also show registry stats for a local or installed skill
+    pub remote: bool,
+}
+
+/// Registry metadata shown alongside a skill
+#[derive(Debug, PartialEq)]
+struct RegistryStats {
+    total_downloads: i64,
+    total_usages: i64,
+    visibility: PakVisibility,
+    license: Option<String>,
+    /// Newest version that wasn't rejected, if any
+    latest: Option<String>,
+    /// When the shown version was published, or the latest if it isn't known
+    published_at: Option<DateTime<Utc>>,
+}
+
+impl RegistryStats {
+    fn new(pak: &Pak, versions: &[PakVersion], shown: &str) -> Self {
+        let published: Vec<&PakVersion> = versions
+            .iter()
+            .filter(|v| v.status != PakVersionStatus::Rejected)
+            .collect();
+        let latest = published.iter().copied().reduce(|latest, v| {
+            match update::compare_versions(&latest.version, &v.version) {
+                VersionChange::Upgrade => v,
+                _ => latest,
+            }
+        });
+        let published_at = published
+            .iter()
+            .find(|v| v.version == shown)
+            .or(latest.as_ref())
+            .map(|v| v.published_at);
+
+        Self {
+            total_downloads: pak.total_downloads,
+            total_usages: pak.total_usages,
+            visibility: pak.visibility,
+            license: pak.license.clone(),
+            latest: latest.map(|v| v.version.clone()),
+            published_at,
+        }
+    }
+
+    /// Lines of the registry section, comparing against the `installed`
+    /// version when the skill is on disk
+    ///
+    /// The registry's license is only listed when it differs from the
+    /// `license` the shown SKILL.md declares.
+    fn lines(&self, installed: Option<&str>, license: Option<&str>) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(installed) = installed {
+            lines.push(format!("Installed:  {}", installed));
+        }
+        match (&self.latest, installed) {
+            (Some(latest), Some(installed))
+                if update::compare_versions(installed, latest) == VersionChange::Upgrade =>
+            {
+                lines.push(format!("Latest:     {} (update available)", latest));
+            }
+            (Some(latest), _) => lines.push(format!("Latest:     {}", latest)),
+            (None, _) => lines.push("Latest:     (no published versions)".to_string()),
+        }
+        if let Some(published_at) = self.published_at {
+            lines.push(format!("Published:  {}", published_at.format("%Y-%m-%d")));
+        }
+        lines.push(format!(
+            "Downloads:  {}",
+            format_count(self.total_downloads)
+        ));
+        lines.push(format!("Usages:     {}", format_count(self.total_usages)));
+        lines.push(format!(
+            "Visibility: {}",
+            self.visibility.to_string().to_lowercase()
+        ));
+        if let Some(registry_license) = self.license.as_deref()
+            && Some(registry_license) != license
+        {
+            lines.push(format!("License:    {}", registry_license));
+        }
+        lines
+    }
+}
+
+/// What the registry holds at the requested reference
+#[derive(Debug)]
+enum RemoteInfo {
+    /// A pak: its parsed SKILL.md, the raw file, and its top-level listing
+    Skill {
+        skill: Box<Skill>,
+        skill_md: String,
+        items: Vec<ContentItem>,
+    },
+    /// A file inside a pak
+    File(String),
+    /// A directory inside a pak
+    Directory(Vec<ContentItem>),
 }
 
 pub async fn run(args: InfoArgs) -> Result<()> {
@@ -15,20 +132,224 @@ pub async fn run(args: InfoArgs) -> Result<()> {
 
     // Check if it's a local path
     if skill_path.exists() {
-        let skill = Skill::load(skill_path)?;
-        print_skill_info(&skill, args.full);
-    } else {
-        // TODO: Check registry for skill by name
-        bail!(
-            "Skill '{}' not found locally. Registry lookup not yet implemented.",
+        return print_local_skill(skill_path, args.full, args.remote).await;
+    }
+
+    let config = Config::load()?;
+    if let Some(installed) = installed_skill_dir(&config, &args.skill) {
+        return print_local_skill(&installed, args.full, args.remote).await;
+    }
+
+    let (reference, path) = split_content_path(&args.skill);
+    let skill_ref = SkillRef::parse(reference).with_context(|| {
+        format!(
+            "Skill '{}' not found locally and is not a registry reference",
             args.skill
-        );
+        )
+    })?;
+    let client = registry_client(&config)?;
+
+    match fetch_remote(&client, &skill_ref, path).await? {
+        RemoteInfo::Skill {
+            skill,
+            skill_md,
+            items,
+        } => {
+            let structure: Vec<String> = items.iter().map(item_label).collect();
+            let full = args.full.then_some(skill_md.as_str());
+            let name = format!("{}/{}", skill_ref.account(), skill_ref.name());
+            let registry = registry_stats(&client, &name, skill.version())
+                .await
+                .map(|stats| stats.lines(None, skill.frontmatter.license.as_deref()));
+            print_skill_info(&skill, &structure, full, registry.as_deref());
+        }
+        RemoteInfo::File(content) => print!("{}", content),
+        RemoteInfo::Directory(items) => {
+            for item in &items {
+                match item.size {
+                    Some(size) if item.item_type == ContentItemType::File => {
+                        println!("  {}  ({} bytes)", item_label(item), size)
+                    }
+                    _ => println!("  {}", item_label(item)),
+                }
+            }
+        }
     }
 
     Ok(())
 }
 
-fn print_skill_info(skill: &Skill, full: bool) {
+/// Show a skill on disk, with its registry stats if `remote`
+async fn print_local_skill(skill_path: &Path, full: bool, remote: bool) -> Result<()> {
+    let skill = Skill::load(skill_path)?;
+    let mut structure = vec!["SKILL.md".to_string()];
+    for (present, dir) in [
+        (skill.has_scripts(), "scripts/"),
+        (skill.has_references(), "references/"),
+        (skill.has_assets(), "assets/"),
+    ] {
+        if present {
+            structure.push(dir.to_string());
+        }
+    }
+    let registry = if remote {
+        local_registry_stats(skill_path, skill.version())
+            .await?
+            .map(|stats| stats.lines(Some(skill.version()), skill.frontmatter.license.as_deref()))
+    } else {
+        None
+    };
+    let full = full.then_some(skill.instructions.as_str());
+    print_skill_info(&skill, &structure, full, registry.as_deref());
+    Ok(())
+}
+
+/// Registry stats for the skill on disk at `skill_path`, found by its
+/// `owner--name` install directory
+async fn local_registry_stats(skill_path: &Path, version: &str) -> Result<Option<RegistryStats>> {
+    let dir_name = skill_path
+        .canonicalize()
+        .ok()
+        .and_then(|path| path.file_name().map(|n| n.to_string_lossy().into_owned()));
+    let Some(name) = dir_name.as_deref().and_then(update::registry_name) else {
+        eprintln!(
+            "⚠ Warning: {} wasn't installed from the registry, so it has no registry stats",
+            skill_path.display()
+        );
+        return Ok(None);
+    };
+    let client = registry_client(&Config::load()?)?;
+    Ok(registry_stats(&client, &name, version).await)
+}
+
+/// Registry stats for `name` (`owner/name`), warning instead of failing when
+/// they can't be fetched
+async fn registry_stats(client: &PaksClient, name: &str, shown: &str) -> Option<RegistryStats> {
+    let (owner, pak_name) = name.split_once('/')?;
+    let stats = async {
+        let Some(pak) = client.get_pak(owner, pak_name).await? else {
+            return Ok(None);
+        };
+        let versions = client.list_pak_versions(owner, pak_name).await?;
+        Ok::<_, ApiError>(Some(RegistryStats::new(&pak, &versions, shown)))
+    };
+    match stats.await {
+        Ok(Some(stats)) => Some(stats),
+        Ok(None) => {
+            eprintln!("⚠ Warning: {} is not in the registry", name);
+            None
+        }
+        Err(e) => {
+            eprintln!(
+                "⚠ Warning: Couldn't fetch registry stats for {}: {}",
+                name, e
+            );
+            None
+        }
+    }
+}
+
+/// Directory of the skill installed for the default agent as `name`, or
+/// under a name it had before `paks rename`
+fn installed_skill_dir(config: &Config, name: &str) -> Option<PathBuf> {
+    if name.contains('/') || name.contains('\\') {
+        return None;
+    }
+    let skills_dir = config
+        .get_default_agent()
+        .map(|agent| agent.skills_dir.clone())
+        .unwrap_or_else(Config::default_skills_dir);
+    let scope = config.skills_scope(None);
+    let dir = skills_dir.join(config.resolve_skill_alias(scope, &skills_dir, name));
+    dir.join("SKILL.md").is_file().then_some(dir)
+}
+
+/// Split `owner/name[@version][/path]` into the reference and the path
+fn split_content_path(input: &str) -> (&str, Option<&str>) {
+    let mut slashes = input.match_indices('/').map(|(i, _)| i);
+    match slashes.nth(1) {
+        Some(i) => {
+            let path = input[i + 1..].trim_matches('/');
+            (&input[..i], (!path.is_empty()).then_some(path))
+        }
+        None => (input, None),
+    }
+}
+
+/// Fetch what `info` shows for `skill_ref`, or for `path` inside it
+async fn fetch_remote(
+    source: &impl ContentSource,
+    skill_ref: &SkillRef,
+    path: Option<&str>,
+) -> Result<RemoteInfo> {
+    let uri = skill_ref.to_uri();
+    if let Some(path) = path {
+        let item_uri = format!("{}/{}", uri, path);
+        return Ok(match fetch(source, &uri, &item_uri).await? {
+            PakContent::File { content } => RemoteInfo::File(content),
+            PakContent::Directory { items } => RemoteInfo::Directory(items),
+        });
+    }
+
+    let PakContent::Directory { items } = fetch(source, &uri, &uri).await? else {
+        bail!("Expected a directory listing for {}", uri);
+    };
+    let skill_md_uri = format!("{}/SKILL.md", uri);
+    let PakContent::File { content } = fetch(source, &uri, &skill_md_uri).await? else {
+        bail!("SKILL.md in {} is not a file", uri);
+    };
+    let skill = Skill::from_skill_md(PathBuf::from(&uri), &content)
+        .with_context(|| format!("Invalid SKILL.md in {}", uri))?;
+
+    Ok(RemoteInfo::Skill {
+        skill: Box::new(skill),
+        skill_md: content,
+        items,
+    })
+}
+
+/// Fetch `item_uri` from the pak `uri`, turning lookup failures into hints
+async fn fetch(source: &impl ContentSource, uri: &str, item_uri: &str) -> Result<PakContent> {
+    match source.fetch(item_uri).await {
+        Ok(response) => Ok(response.content),
+        Err(ApiError::NotFound(_)) if item_uri == uri => bail!(
+            "Skill '{}' not found in registry.\n\
+             Hint: Check the skill name or search with 'paks search {}'",
+            uri,
+            uri.split(['/', '@']).nth(1).unwrap_or(uri)
+        ),
+        Err(ApiError::NotFound(_)) => bail!(
+            "'{}' does not exist in {}",
+            item_uri
+                .strip_prefix(&format!("{}/", uri))
+                .unwrap_or(item_uri),
+            uri
+        ),
+        Err(ApiError::Api { status: 403, .. }) => bail!(
+            "Access denied to skill '{}'.\n\
+             Hint: This may be a private skill. Try 'paks login' first.",
+            uri
+        ),
+        Err(e) => bail!("Failed to fetch {}: {}", item_uri, e),
+    }
+}
+
+/// A content item's name, with a trailing `/` for directories
+fn item_label(item: &ContentItem) -> String {
+    match item.item_type {
+        ContentItemType::Dir => format!("{}/", item.name),
+        _ => item.name.clone(),
+    }
+}
+
+/// Print a skill's metadata, its `registry` stats, its top-level
+/// `structure`, and optionally its `full` SKILL.md content
+fn print_skill_info(
+    skill: &Skill,
+    structure: &[String],
+    full: Option<&str>,
+    registry: Option<&[String]>,
+) {
     let fm = &skill.frontmatter;
 
     println!("╭─────────────────────────────────────────╮");
@@ -81,24 +402,258 @@ fn print_skill_info(skill: &Skill, full: bool) {
         }
     }
 
+    if let Some(registry) = registry {
+        println!("\nRegistry:");
+        for line in registry {
+            println!("  {}", line);
+        }
+    }
+
     // Directory structure
     println!("\nStructure:");
-    println!("  SKILL.md");
-    if skill.has_scripts() {
-        println!("  scripts/");
-    }
-    if skill.has_references() {
-        println!("  references/");
-    }
-    if skill.has_assets() {
-        println!("  assets/");
+    for entry in structure {
+        println!("  {}", entry);
     }
 
     // Full content
-    if full {
+    if let Some(content) = full {
         println!("\n─────────────────────────────────────────");
         println!("SKILL.md Content:");
         println!("─────────────────────────────────────────");
-        println!("{}", skill.instructions);
+        println!("{}", content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paks_api::PakContentResponse;
+    use std::collections::HashMap;
+
+    /// In-memory content tree keyed by URI
+    struct MockContent(HashMap<&'static str, PakContent>);
+
+    impl ContentSource for MockContent {
+        async fn fetch(&self, uri: &str) -> Result<PakContentResponse, ApiError> {
+            self.0
+                .get(uri)
+                .cloned()
+                .map(|content| PakContentResponse {
+                    uri: uri.to_string(),
+                    content,
+                })
+                .ok_or_else(|| ApiError::NotFound(uri.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_installed_skill_found_by_alias() {
+        let skills = tempfile::tempdir().unwrap();
+        let dir = skills.path().join("deploy");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: deploy\ndescription: d\n---\n",
+        )
+        .unwrap();
+
+        let mut config = Config {
+            default_agent: Some("cursor".to_string()),
+            ..Config::default()
+        };
+        config.agents.insert(
+            "cursor".to_string(),
+            crate::commands::core::config::AgentConfig {
+                name: "Cursor".to_string(),
+                skills_dir: skills.path().to_path_buf(),
+                description: None,
+            },
+        );
+        config.record_rename("cursor", "acme--deploy", "deploy");
+
+        assert_eq!(installed_skill_dir(&config, "deploy"), Some(dir.clone()));
+        assert_eq!(installed_skill_dir(&config, "acme--deploy"), Some(dir));
+        assert_eq!(installed_skill_dir(&config, "other"), None);
+        assert_eq!(installed_skill_dir(&config, "acme/deploy"), None);
+    }
+
+    fn item(name: &str, item_type: ContentItemType) -> ContentItem {
+        ContentItem {
+            name: name.to_string(),
+            uri: format!("acme/deploy/{}", name),
+            item_type,
+            size: Some(12),
+            content: None,
+        }
+    }
+
+    fn mock_pak() -> MockContent {
+        MockContent(HashMap::from([
+            (
+                "acme/deploy",
+                PakContent::Directory {
+                    items: vec![
+                        item("SKILL.md", ContentItemType::File),
+                        item("scripts", ContentItemType::Dir),
+                    ],
+                },
+            ),
+            (
+                "acme/deploy/SKILL.md",
+                PakContent::File {
+                    content: "---\nname: deploy\ndescription: Deploys things\n---\n\n# Deploy\n"
+                        .to_string(),
+                },
+            ),
+            (
+                "acme/deploy/scripts",
+                PakContent::Directory {
+                    items: vec![item("run.sh", ContentItemType::File)],
+                },
+            ),
+        ]))
+    }
+
+    fn registry_pak() -> Pak {
+        serde_json::from_value(serde_json::json!({
+            "id": "00000000-0000-0000-0000-000000000001",
+            "name": "deploy",
+            "owner_name": "acme",
+            "uri": "acme/deploy",
+            "full_uri": "stakpak://acme/deploy",
+            "path": null,
+            "repository_url": "https://github.com/acme/skills",
+            "description": null,
+            "tags": null,
+            "license": "MIT",
+            "visibility": "UNLISTED",
+            "status": "ACTIVE",
+            "download_count": 10,
+            "usage_count": 2,
+            "total_downloads": 1234,
+            "total_usages": 56,
+            "created_at": "2025-01-01T00:00:00Z",
+            "updated_at": "2025-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    fn version(version: &str, status: &str, published_at: &str) -> PakVersion {
+        serde_json::from_value(serde_json::json!({
+            "id": "00000000-0000-0000-0000-000000000002",
+            "version": version,
+            "git_tag": format!("v{}", version),
+            "checksum": "abc",
+            "size_bytes": null,
+            "manifest": "",
+            "status": status,
+            "downloads": 0,
+            "usages": 0,
+            "published_at": published_at,
+            "created_at": published_at,
+            "updated_at": published_at,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_registry_stats() {
+        let versions = [
+            version("1.10.0", "APPROVED", "2025-03-01T00:00:00Z"),
+            version("1.2.0", "APPROVED", "2025-01-15T00:00:00Z"),
+            version("2.0.0", "REJECTED", "2025-04-01T00:00:00Z"),
+        ];
+
+        // Latest by semver, never a rejected version; dated by the shown version
+        let stats = RegistryStats::new(&registry_pak(), &versions, "1.2.0");
+        assert_eq!(stats.latest.as_deref(), Some("1.10.0"));
+        assert_eq!(
+            stats.published_at.unwrap().to_rfc3339(),
+            "2025-01-15T00:00:00+00:00"
+        );
+
+        let unknown = RegistryStats::new(&registry_pak(), &versions, "0.9.0");
+        assert_eq!(
+            unknown.published_at.unwrap().to_rfc3339(),
+            "2025-03-01T00:00:00+00:00"
+        );
+
+        let unpublished = RegistryStats::new(&registry_pak(), &[], "0.1.0");
+        assert_eq!(unpublished.latest, None);
+        assert_eq!(unpublished.published_at, None);
+    }
+
+    #[test]
+    fn test_registry_lines() {
+        let versions = [version("1.10.0", "APPROVED", "2025-03-01T00:00:00Z")];
+        let stats = RegistryStats::new(&registry_pak(), &versions, "1.10.0");
+        assert_eq!(
+            stats.lines(None, Some("MIT")),
+            [
+                "Latest:     1.10.0",
+                "Published:  2025-03-01",
+                "Downloads:  1.2K",
+                "Usages:     56",
+                "Visibility: unlisted",
+            ]
+        );
+
+        let lines = stats.lines(Some("1.2.0"), None);
+        assert_eq!(lines[0], "Installed:  1.2.0");
+        assert_eq!(lines[1], "Latest:     1.10.0 (update available)");
+        assert_eq!(lines.last().unwrap(), "License:    MIT");
+        assert_eq!(stats.lines(Some("1.10.0"), None)[1], "Latest:     1.10.0");
+    }
+
+    #[test]
+    fn test_split_content_path() {
+        assert_eq!(split_content_path("acme/deploy"), ("acme/deploy", None));
+        assert_eq!(
+            split_content_path("acme/deploy@1.0.0/scripts/run.sh"),
+            ("acme/deploy@1.0.0", Some("scripts/run.sh"))
+        );
+        assert_eq!(split_content_path("acme/deploy/"), ("acme/deploy", None));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_remote_skill() {
+        let source = mock_pak();
+        let skill_ref = SkillRef::parse("acme/deploy").unwrap();
+
+        let RemoteInfo::Skill {
+            skill,
+            skill_md,
+            items,
+        } = fetch_remote(&source, &skill_ref, None).await.unwrap()
+        else {
+            panic!("expected a skill");
+        };
+        assert_eq!(skill.name(), "deploy");
+        assert!(skill_md.starts_with("---\nname: deploy"));
+        let labels: Vec<String> = items.iter().map(item_label).collect();
+        assert_eq!(labels, ["SKILL.md", "scripts/"]);
+
+        let RemoteInfo::Directory(items) = fetch_remote(&source, &skill_ref, Some("scripts"))
+            .await
+            .unwrap()
+        else {
+            panic!("expected a directory");
+        };
+        assert_eq!(items[0].name, "run.sh");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_remote_not_found() {
+        let source = mock_pak();
+
+        let missing = SkillRef::parse("acme/missing").unwrap();
+        let err = fetch_remote(&source, &missing, None).await.unwrap_err();
+        assert!(err.to_string().contains("not found in registry"));
+
+        let skill_ref = SkillRef::parse("acme/deploy").unwrap();
+        let err = fetch_remote(&source, &skill_ref, Some("nope.md"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "'nope.md' does not exist in acme/deploy");
     }
 }
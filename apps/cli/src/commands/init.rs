@@ -0,0 +1,24 @@
+//! Init command - create a project manifest (paks.toml)
+
+use anyhow::Result;
+
+use super::core::manifest;
+use super::core::paths::expand_path;
+
+pub struct InitArgs {
+    /// Project directory (defaults to the current directory)
+    pub dir: Option<String>,
+}
+
+pub async fn run(args: InitArgs) -> Result<()> {
+    let dir = match args.dir {
+        Some(dir) => expand_path(&dir),
+        None => std::env::current_dir()?,
+    };
+
+    let path = manifest::init(&dir)?;
+    println!("✓ Created {}", path.display());
+    println!("  Add skills with: paks install <owner>/<skill> --save");
+
+    Ok(())
+}
@@ -1,28 +1,152 @@
 //! Install command - install a skill to an agent's skills directory
 
+use super::core::archive;
+use super::core::cache::{CachedCheckout, CloneCache, MetadataCache};
+use super::core::checksum;
+use super::core::client::registry_client;
 use super::core::config::Config;
+use super::core::content;
+use super::core::git;
+use super::core::hooks::{self, Hook};
+use super::core::manifest;
+use super::core::offline;
+use super::core::paksignore::IgnoreRules;
+use super::core::paths::expand_path;
+use super::core::signature::{self, SignatureCheck};
+use super::core::size::{MB, format_size};
 use super::core::skill::Skill;
+use super::core::style::Style;
+use super::core::suggest;
+use super::core::update::{self, VersionChange};
+use super::remove::validate_skill_name;
 use anyhow::{Context, Result, bail};
-use paks_api::{ApiError, PaksClient};
+use dialoguer::Confirm;
+use paks_api::{ApiError, InstallPakInfo, PakInstallResponse, PakStatus, PaksClient, SkillRef};
+use std::ffi::OsString;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+#[derive(Default)]
 pub struct InstallArgs {
-    pub source: String,
+    /// Skills to install, in order
+    pub sources: Vec<String>,
     pub agent: Option<String>,
     pub dir: Option<String>,
     pub force: bool,
+    /// Only install these files/subtrees of a registry pak (SKILL.md is always included)
+    ///
+    /// The registry checksum can't be verified for a partial install.
+    pub only: Vec<String>,
+    /// Allow installing a specific version that has been yanked
+    pub allow_yanked: bool,
+    /// Refuse to install deprecated paks instead of warning
+    pub no_deprecated: bool,
+    /// Allow replacing an installed skill with an older version
+    pub allow_downgrade: bool,
+    /// Clone registry skills over SSH instead of HTTPS
+    pub ssh: bool,
+    /// Record the installed skill in the project's paks.toml and paks.lock
+    pub save: bool,
+    /// Fail unless the skill carries a valid signature
+    pub require_signature: bool,
+    /// Always clone afresh instead of reusing a cached clone
+    pub no_cache: bool,
+    /// Don't check installed files against the registry's checksum
+    pub skip_checksum: bool,
+    /// Stop at the first failed install instead of continuing with the rest
+    pub fail_fast: bool,
+    /// Don't ask before installing paks over `confirm_install_over_mb`
+    pub yes: bool,
+    /// Refuse registry paks larger than this many megabytes
+    pub max_size: Option<u64>,
+    /// Download registry paks as a tarball instead of cloning their git repository
+    pub from_archive: bool,
+    /// Commits of history to clone for git installs; 0 for the full history
+    pub git_depth: u32,
+    /// Clone the repository's submodules too
+    pub recurse_submodules: bool,
+    /// Run a skill's postinstall hook without asking
+    pub run_hooks: bool,
+    /// Install exactly what the skills directory's paks.lock pins
+    pub frozen: bool,
 }
 
-/// Parsed skill reference from user input
+impl InstallArgs {
+    fn clone_options(&self) -> CloneOptions {
+        CloneOptions {
+            depth: self.git_depth,
+            recurse_submodules: self.recurse_submodules,
+        }
+    }
+}
+
+/// How much of a repository to clone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct CloneOptions {
+    /// Commits of history to fetch; 0 for the full history
+    pub depth: u32,
+    /// Also clone the repository's submodules
+    pub recurse_submodules: bool,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            recurse_submodules: false,
+        }
+    }
+}
+
+/// Metadata cache namespace for registry install info, kept for offline installs
+const INSTALL_CACHE_NAMESPACE: &str = "install";
+
+/// Clone cache for this install, unless disabled with --no-cache
+///
+/// Cached clones are shallow and without submodules, so asking for more
+/// history or for submodules always clones afresh.
+fn clone_cache(args: &InstallArgs) -> Result<Option<CloneCache>> {
+    if args.no_cache || args.clone_options() != CloneOptions::default() {
+        return Ok(None);
+    }
+    Ok(Some(CloneCache::new(CloneCache::default_dir()?)))
+}
+
+/// What an install put on disk, used to record it in the project manifest
 #[derive(Debug)]
-struct SkillRef {
-    /// Account/owner name (e.g., "stakpak")
-    account: String,
-    /// Skill name (e.g., "kubernetes-deploy")
+struct InstalledSkill {
+    /// Manifest key: `owner/name` for registry skills, the skill name otherwise
     name: String,
-    /// Optional version (e.g., "1.2.3")
-    version: Option<String>,
+    version: String,
+    /// `registry`, a git URL, or a local path
+    source: String,
+    commit: Option<String>,
+    /// Checksum the registry reported, if any
+    checksum: Option<String>,
+    /// Where the skill now lives
+    dir: PathBuf,
+}
+
+impl InstalledSkill {
+    /// Lockfile entry for this install, checksumming the installed files
+    /// when the registry didn't report a checksum
+    fn locked(&self) -> manifest::LockedSkill {
+        // Registry skills are locked to the registry's checksum only, since
+        // that's what a frozen install is checked against
+        let computed = || {
+            (self.source != "registry")
+                .then(|| checksum::compute(&self.dir).ok())
+                .flatten()
+        };
+        manifest::LockedSkill {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            source: self.source.clone(),
+            commit: self.commit.clone(),
+            checksum: self.checksum.clone().or_else(computed),
+        }
+    }
 }
 
 /// Source type for skill installation
@@ -36,77 +160,23 @@ enum SourceType {
         git_ref: Option<String>,
         path: Option<String>,
     },
+    /// GitHub Gist holding a SKILL.md, optionally at a specific revision
+    Gist {
+        id: String,
+        revision: Option<String>,
+    },
     /// Local filesystem path
     Local(PathBuf),
 }
 
-impl SkillRef {
-    /// Parse a skill reference from input string
-    /// Format: account/skill[@version]
-    fn parse(input: &str) -> Result<Self> {
-        let (identifier, version) = if let Some(at_pos) = input.rfind('@') {
-            let id = &input[..at_pos];
-            let ver = &input[at_pos + 1..];
-            if ver.is_empty() {
-                bail!("Version cannot be empty after @");
-            }
-            (id, Some(ver.to_string()))
-        } else {
-            (input, None)
-        };
-
-        let parts: Vec<&str> = identifier.split('/').collect();
-        if parts.len() != 2 {
-            bail!(
-                "Invalid skill reference '{}'. Expected format: account/skill[@version]",
-                input
-            );
-        }
-
-        let account = parts[0].to_string();
-        let name = parts[1].to_string();
-
-        // Validate account name (lowercase alphanumeric + hyphens, 1-39 chars)
-        if account.is_empty() || account.len() > 39 {
-            bail!("Account name must be 1-39 characters");
-        }
-        if !account
-            .chars()
-            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
-        {
-            bail!("Account name must contain only lowercase letters, numbers, and hyphens");
-        }
-
-        // Validate skill name (lowercase alphanumeric + hyphens, 1-64 chars)
-        if name.is_empty() || name.len() > 64 {
-            bail!("Skill name must be 1-64 characters");
-        }
-        if !name
-            .chars()
-            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
-        {
-            bail!("Skill name must contain only lowercase letters, numbers, and hyphens");
-        }
-
-        Ok(Self {
-            account,
-            name,
-            version,
-        })
-    }
-
-    /// Get the URI for API calls (account/name[@version])
-    fn to_uri(&self) -> String {
-        match &self.version {
-            Some(v) => format!("{}/{}@{}", self.account, self.name, v),
-            None => format!("{}/{}", self.account, self.name),
-        }
-    }
-}
-
 /// Detect the source type from user input
 fn detect_source_type(source: &str) -> SourceType {
-    // Check for git URLs first
+    // Gists are git repositories too, but have their own URL layout
+    if let Some((id, revision)) = parse_gist_url(source) {
+        return SourceType::Gist { id, revision };
+    }
+
+    // Check for git URLs
     if source.starts_with("https://")
         || source.starts_with("http://")
         || source.starts_with("git@")
@@ -151,6 +221,35 @@ fn detect_source_type(source: &str) -> SourceType {
     SourceType::Local(PathBuf::from(source))
 }
 
+/// Parse a GitHub Gist URL into its id and optional revision
+/// Supports:
+/// - https://gist.github.com/<id>
+/// - https://gist.github.com/<user>/<id>
+/// - https://gist.github.com/<user>/<id>/<revision>
+/// - https://gist.github.com/<id>.git (the gist clone URL)
+fn parse_gist_url(url: &str) -> Option<(String, Option<String>)> {
+    let path = url.strip_prefix("https://gist.github.com/")?;
+    let path = path.split(['#', '?']).next()?.trim_end_matches('/');
+    let is_hex = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit());
+
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [id] | [_, id] => {
+            let id = id.strip_suffix(".git").unwrap_or(id);
+            is_hex(id).then(|| (id.to_string(), None))
+        }
+        [_, id, revision] if is_hex(id) && is_hex(revision) => {
+            Some((id.to_string(), Some(revision.to_string())))
+        }
+        _ => None,
+    }
+}
+
+/// Clone URL for a gist
+fn gist_clone_url(id: &str) -> String {
+    format!("https://gist.github.com/{}.git", id)
+}
+
 /// Parsed git URL components
 struct GitUrlParts {
     /// Base repository URL (e.g., "https://github.com/user/repo.git")
@@ -290,7 +389,7 @@ fn split_url_fragment(url: &str) -> (String, Option<&str>) {
 /// - Fragment syntax: url#ref=v1.0.0&path=skills/my-skill
 /// - URL path syntax: https://github.com/user/repo.git/path/to/skill
 /// - URL path syntax: git@github.com:user/repo.git/path/to/skill
-fn parse_git_url(url: &str) -> (String, Option<String>, Option<String>) {
+pub(super) fn parse_git_url(url: &str) -> (String, Option<String>, Option<String>) {
     let parts = parse_git_url_parts(url);
     (parts.url, parts.git_ref, parts.path)
 }
@@ -327,133 +426,513 @@ fn parse_git_url_parts(url: &str) -> GitUrlParts {
     }
 }
 
-pub async fn run(args: InstallArgs) -> Result<()> {
-    // Determine install directory
-    let install_dir = if let Some(dir) = &args.dir {
-        PathBuf::from(shellexpand::tilde(dir).as_ref())
+/// Skills directory to install into, and the agent it belongs to
+pub(super) fn install_target(
+    dir: Option<&str>,
+    agent: Option<&str>,
+) -> Result<(PathBuf, Option<String>)> {
+    if let Some(dir) = dir {
+        return Ok((expand_path(dir), None));
+    }
+    let config = Config::load()?;
+    let agent_name = agent
+        .map(str::to_string)
+        .or_else(|| config.default_agent_id().map(str::to_string));
+
+    let dir = if let Some(name) = &agent_name {
+        config
+            .get_agent(name)
+            .map(|a| a.skills_dir.clone())
+            .unwrap_or_else(Config::default_skills_dir)
     } else {
-        let config = Config::load()?;
-        let agent_name = args.agent.as_ref().or(config.default_agent.as_ref());
-
-        if let Some(name) = agent_name {
-            config
-                .get_agent(name)
-                .map(|a| a.skills_dir.clone())
-                .unwrap_or_else(Config::default_skills_dir)
+        // No agent specified, no default and no fallback - use ~/.agents/skills
+        Config::default_skills_dir()
+    };
+    Ok((dir, agent_name))
+}
+
+/// Sources to install with `--frozen`: the given ones, or every registry
+/// skill in the lockfile when none are given
+fn frozen_sources(sources: &[String], lock: &[manifest::LockedSkill]) -> Result<Vec<String>> {
+    if lock.is_empty() {
+        bail!(
+            "--frozen needs a {} in the skills directory.\n\
+             Hint: Run 'paks lock' to create one from what's installed.",
+            manifest::LOCK_FILE
+        );
+    }
+    if !sources.is_empty() {
+        return Ok(sources.to_vec());
+    }
+    let mut frozen = Vec::new();
+    for locked in lock {
+        if locked.source == "registry" {
+            frozen.push(locked.name.clone());
         } else {
-            // No agent specified and no default - use ~/.paks/skills
-            Config::default_skills_dir()
+            eprintln!(
+                "⚠ Warning: Skipping {}: only registry skills can be installed with --frozen",
+                locked.name
+            );
+        }
+    }
+    Ok(frozen)
+}
+
+pub async fn run(args: InstallArgs) -> Result<()> {
+    let (install_dir, agent) = install_target(args.dir.as_deref(), args.agent.as_deref())?;
+
+    let sources = if args.frozen {
+        frozen_sources(&args.sources, &manifest::load_lock(&install_dir)?)?
+    } else {
+        args.sources.clone()
+    };
+
+    // Check flags against every source before installing anything
+    for source in &sources {
+        let is_registry = matches!(detect_source_type(source), SourceType::Registry(_));
+        if args.frozen && !is_registry {
+            bail!("--frozen is only supported when installing from the registry");
+        }
+        if !args.only.is_empty() && !is_registry {
+            bail!("--only is only supported when installing from the registry");
+        }
+        if args.from_archive && !is_registry {
+            bail!("--from-archive is only supported when installing from the registry");
+        }
+        if args.require_signature && !is_registry {
+            bail!("--require-signature is only supported when installing from the registry");
+        }
+        if args.ssh && !is_registry {
+            bail!(
+                "--ssh is only supported when installing from the registry; pass a git@ URL instead"
+            );
+        }
+    }
+
+    // Fail before installing anything if there's nowhere to save to
+    let project_root = if args.save {
+        let cwd = std::env::current_dir()?;
+        match manifest::find_project_root(&cwd) {
+            Some(root) => Some(root),
+            None => bail!(
+                "No {} found in {} or any parent directory.\n\
+                 Hint: Run 'paks init' to create one for this project.",
+                manifest::MANIFEST_FILE,
+                cwd.display()
+            ),
         }
+    } else {
+        None
     };
 
-    // Detect source type
-    let source_type = detect_source_type(&args.source);
+    // A single install reports its error as is
+    if let [source] = sources.as_slice() {
+        return install_one(
+            source,
+            &install_dir,
+            agent.as_deref(),
+            project_root.as_deref(),
+            &args,
+        )
+        .await;
+    }
+
+    let mut failed = Vec::new();
+    let mut attempted = 0;
+    for source in &sources {
+        if attempted > 0 {
+            println!();
+        }
+        attempted += 1;
+        if let Err(e) = install_one(
+            source,
+            &install_dir,
+            agent.as_deref(),
+            project_root.as_deref(),
+            &args,
+        )
+        .await
+        {
+            eprintln!("✗ Failed to install {}: {:#}", source, e);
+            failed.push(source.as_str());
+            if args.fail_fast {
+                break;
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} installed, {} failed{}",
+        attempted - failed.len(),
+        failed.len(),
+        match sources.len() - attempted {
+            0 => String::new(),
+            skipped => format!(", {} skipped (--fail-fast)", skipped),
+        }
+    );
+    if !failed.is_empty() {
+        bail!("Failed to install: {}", failed.join(", "));
+    }
+    Ok(())
+}
 
-    match source_type {
+/// Install a single `source` into `install_dir`
+async fn install_one(
+    source: &str,
+    install_dir: &Path,
+    agent: Option<&str>,
+    project_root: Option<&Path>,
+    args: &InstallArgs,
+) -> Result<()> {
+    let installed = match detect_source_type(source) {
         SourceType::Registry(skill_ref) => {
-            install_from_registry(skill_ref, &install_dir, args.force).await
+            install_from_registry(skill_ref, install_dir, args).await
         }
         SourceType::Git { url, git_ref, path } => {
             install_from_git(
                 &url,
                 git_ref.as_deref(),
                 path.as_deref(),
-                &install_dir,
+                install_dir,
                 args.force,
+                clone_cache(args)?.as_ref(),
+                args.clone_options(),
             )
             .await
         }
-        SourceType::Local(path) => install_from_local(&path, &install_dir, args.force).await,
+        SourceType::Gist { id, revision } => {
+            install_from_gist(&id, revision.as_deref(), install_dir, args.force).await
+        }
+        SourceType::Local(path) => install_from_local(&path, install_dir, args.force).await,
+    }?;
+
+    // Compatibility is advisory: the skill stays installed either way
+    if let Ok(skill) = Skill::load(&installed.dir)
+        && let Some(compat) = &skill.frontmatter.compatibility
+    {
+        for warning in compat.install_warnings(agent, env!("CARGO_PKG_VERSION")) {
+            eprintln!("⚠ Warning: {}", warning);
+        }
+    }
+
+    hooks::run(&installed.dir, Hook::PostInstall, args.run_hooks).with_context(|| {
+        format!(
+            "{} is installed at {}, but its postinstall hook did not complete",
+            installed.name,
+            installed.dir.display()
+        )
+    })?;
+
+    // The skills directory's lockfile is a record, so failing to update it
+    // doesn't fail the install
+    if let Err(e) = manifest::save_locked(install_dir, &installed.locked()) {
+        eprintln!(
+            "⚠ Warning: Failed to update {}: {:#}",
+            install_dir.join(manifest::LOCK_FILE).display(),
+            e
+        );
+    }
+
+    if let Some(root) = project_root {
+        save_to_manifest(root, &installed)?;
+        println!(
+            "  Saved {} to {}",
+            installed.name,
+            root.join(manifest::MANIFEST_FILE).display()
+        );
+    }
+
+    Ok(())
+}
+
+/// The lockfile entry pinning `skill_ref`, for `--frozen`
+fn frozen_entry(skill_ref: &SkillRef, install_dir: &Path) -> Result<manifest::LockedSkill> {
+    let name = format!("{}/{}", skill_ref.account(), skill_ref.name());
+    let locked = manifest::load_lock(install_dir)?
+        .into_iter()
+        .find(|s| s.name == name)
+        .with_context(|| {
+            format!(
+                "{} is not in {}, so it can't be installed with --frozen",
+                name,
+                install_dir.join(manifest::LOCK_FILE).display()
+            )
+        })?;
+    if let Some(version) = skill_ref.version()
+        && version != locked.version
+    {
+        bail!(
+            "{}@{} was requested, but {} pins {}",
+            name,
+            version,
+            manifest::LOCK_FILE,
+            locked.version
+        );
+    }
+    Ok(locked)
+}
+
+/// Check the registry's resolution of a frozen install against its lockfile
+/// entry
+fn check_frozen(
+    locked: &manifest::LockedSkill,
+    version: &str,
+    commit: &str,
+    checksum: Option<&str>,
+) -> Result<()> {
+    let mut differences = Vec::new();
+    if locked.version != version {
+        differences.push(format!("version {} (locked {})", version, locked.version));
+    }
+    if let Some(locked_commit) = &locked.commit
+        && locked_commit != commit
+    {
+        differences.push(format!("commit {} (locked {})", commit, locked_commit));
+    }
+    if let (Some(locked_checksum), Some(checksum)) = (&locked.checksum, checksum)
+        && !checksum::same(locked_checksum, checksum)
+    {
+        differences.push(format!(
+            "checksum {} (locked {})",
+            checksum, locked_checksum
+        ));
+    }
+    if differences.is_empty() {
+        return Ok(());
     }
+    bail!(
+        "The registry now resolves {} differently from {}: {}.\n\
+         Hint: Install without --frozen, then run 'paks lock' to accept the change.",
+        locked.name,
+        manifest::LOCK_FILE,
+        differences.join(", ")
+    )
+}
+
+/// Record an installed skill in the project manifest and lockfile
+fn save_to_manifest(root: &Path, installed: &InstalledSkill) -> Result<()> {
+    let spec = manifest::DependencySpec {
+        version: format!("^{}", installed.version),
+        source: installed.source.clone(),
+    };
+    manifest::save_dependency(root, &spec, &installed.locked())
 }
 
 /// Install a skill from the paks registry
-async fn install_from_registry(skill_ref: SkillRef, install_dir: &Path, force: bool) -> Result<()> {
+async fn install_from_registry(
+    skill_ref: SkillRef,
+    install_dir: &Path,
+    args: &InstallArgs,
+) -> Result<InstalledSkill> {
+    let force = args.force;
     println!("Installing {} from registry...", skill_ref.to_uri());
 
     // Create API client
-    let client = PaksClient::builder()
-        .base_url("https://apiv2.stakpak.dev")
-        .build()
-        .context("Failed to create API client")?;
-
-    // Fetch install metadata from registry
-    let uri = skill_ref.to_uri();
-    let install_info = match client.get_pak_install(&uri).await {
-        Ok(info) => info,
-        Err(ApiError::NotFound(_)) => {
-            bail!(
-                "Skill '{}' not found in registry.\n\
-                 Hint: Check the skill name or search with 'paks search {}'",
-                uri,
-                skill_ref.name
-            );
-        }
-        Err(ApiError::Api { status: 403, .. }) => {
-            bail!(
-                "Access denied to skill '{}'.\n\
-                 Hint: This may be a private skill. Try 'paks login' first.",
-                uri
-            );
-        }
-        Err(e) => {
-            bail!("Failed to fetch skill info: {}", e);
+    let config = Config::load()?;
+    let client = registry_client(&config)?;
+
+    // With --frozen, resolve exactly the version the lockfile pins
+    let locked = if args.frozen {
+        Some(frozen_entry(&skill_ref, install_dir)?)
+    } else {
+        None
+    };
+    let uri = match &locked {
+        Some(locked) => format!("{}@{}", locked.name, locked.version),
+        None => skill_ref.to_uri(),
+    };
+
+    // Fetch install metadata from registry, or from what an earlier install
+    // cached when offline
+    let install_cache = MetadataCache::new(client.base_url(), config.cache_ttl()).ok();
+    let install_info = if offline::is_offline() {
+        offline_install_info(install_cache.as_ref(), &uri, args)?
+    } else {
+        let info = match client.get_pak_install(&uri).await {
+            Ok(info) => info,
+            Err(ApiError::NotFound(_)) => {
+                let intended = format!("{}/{}", skill_ref.account(), skill_ref.name());
+                let suggestions = suggest::did_you_mean(&client, &intended).await;
+                if let Some(hint) = suggest::format_hint(&suggestions) {
+                    bail!("Skill '{}' not found in registry.\n{}", uri, hint);
+                }
+                bail!(
+                    "Skill '{}' not found in registry.\n\
+                     Hint: Check the skill name or search with 'paks search {}'",
+                    uri,
+                    skill_ref.name()
+                );
+            }
+            Err(ApiError::Api { status: 403, .. }) => {
+                bail!(
+                    "Access denied to skill '{}'.\n\
+                     Hint: This may be a private skill. Try 'paks login' first.",
+                    uri
+                );
+            }
+            Err(e) => {
+                bail!("Failed to fetch skill info: {}", e);
+            }
+        };
+        if let Some(cache) = &install_cache {
+            // Caching is best-effort
+            cache.put(INSTALL_CACHE_NAMESPACE, &uri, &info).ok();
         }
+        info
     };
 
     println!(
         "  Found: {}/{}@{}",
         install_info.pak.owner, install_info.pak.name, install_info.version.version
     );
+    if let Some(locked) = &locked {
+        check_frozen(
+            locked,
+            &install_info.version.version,
+            &install_info.version.commit_hash,
+            install_info.version.checksum.as_deref(),
+        )?;
+    }
 
     // Determine target directory (flat: owner--skill to avoid nesting)
     let target_dir = install_dir.join(format!(
         "{}--{}",
         install_info.pak.owner, install_info.pak.name
     ));
+    let installed = InstalledSkill {
+        name: format!("{}/{}", install_info.pak.owner, install_info.pak.name),
+        version: install_info.version.version.clone(),
+        source: "registry".to_string(),
+        commit: Some(install_info.version.commit_hash.clone()),
+        checksum: install_info.version.checksum.clone(),
+        dir: target_dir.clone(),
+    };
+
+    for warning in check_install_status(
+        &install_info,
+        skill_ref.version().is_some() || locked.is_some(),
+        args.allow_yanked,
+        args.no_deprecated,
+    )? {
+        eprintln!(
+            "{}",
+            Style::current().bold_yellow(format!("⚠ Warning: {}", warning))
+        );
+    }
+
+    let size = install_info
+        .version
+        .size_bytes
+        .and_then(|s| u64::try_from(s).ok());
+    if let Some(size) = size {
+        println!("  Size: {}", format_size(size));
+    }
+    let needs_confirm = check_size(
+        &installed.name,
+        size,
+        config.confirm_install_over_mb,
+        args.max_size,
+        args.yes,
+    )?;
+    if needs_confirm && !confirm_large_install(&installed.name)? {
+        bail!("Install of {} cancelled", installed.name);
+    }
 
     // Check if already installed
     if target_dir.exists() {
-        if !force {
-            // Check installed version
-            if let Ok(existing) = Skill::load(&target_dir) {
-                let installed_version = existing.version();
-                if installed_version == install_info.version.version {
-                    println!(
-                        "✓ Already installed: {}/{}@{}",
-                        install_info.pak.owner, install_info.pak.name, installed_version
-                    );
-                    return Ok(());
-                }
-                println!(
-                    "  Installed version: {} → {}",
-                    installed_version, install_info.version.version
-                );
-            }
-            bail!(
-                "Skill already exists at {}.\n\
-                 Use --force to reinstall.",
-                target_dir.display()
+        let existing = Skill::load(&target_dir).ok();
+        let existing_version = existing.as_ref().map(Skill::version);
+        let decision = check_existing_install(
+            &target_dir,
+            existing_version,
+            &install_info.version.version,
+            force,
+            args.allow_downgrade,
+        )?;
+        if decision == ExistingInstall::Keep {
+            println!(
+                "✓ Already installed: {}@{}",
+                installed.name, installed.version
             );
+            return Ok(installed);
         }
         println!("  Removing existing installation...");
         std::fs::remove_dir_all(&target_dir)
             .with_context(|| format!("Failed to remove {}", target_dir.display()))?;
     }
 
-    // Clone from git at the specific tag, installing to account/skill path
-    install_from_git_to_target(
-        &install_info.repository.clone_url,
-        Some(&install_info.version.tag),
-        if install_info.install.path == "." {
-            None
+    if !args.only.is_empty() {
+        // Fetch just the requested parts through the content API
+        let base_uri = format!(
+            "{}/{}@{}",
+            install_info.pak.owner, install_info.pak.name, install_info.version.version
+        );
+        println!("  Fetching {}...", args.only.join(", "));
+        if let Err(e) = content::download_paths(&client, &base_uri, &args.only, &target_dir).await {
+            // Don't leave a partial install behind
+            std::fs::remove_dir_all(&target_dir).ok();
+            return Err(e);
+        }
+        Skill::load(&target_dir).context("Downloaded SKILL.md is not a valid skill")?;
+        // The checksum covers every file of the pak, not the parts fetched
+        if install_info.version.checksum.is_some() {
+            println!("  ⚠ Skipping checksum verification (--only installs part of the pak)");
+        }
+        verify_installed_signature(
+            &target_dir,
+            install_info.version.signature.as_deref(),
+            args.require_signature,
+        )?;
+
+        println!(
+            "✓ Installed {}/{}@{} (only: {})",
+            install_info.pak.owner,
+            install_info.pak.name,
+            install_info.version.version,
+            args.only.join(", ")
+        );
+        println!("  Location: {}", target_dir.display());
+        return Ok(installed);
+    }
+
+    if args.from_archive {
+        let uri = format!(
+            "{}/{}@{}",
+            install_info.pak.owner, install_info.pak.name, install_info.version.version
+        );
+        install_from_archive(&client, &uri, &target_dir).await?;
+    } else {
+        // Clone from git at the specific tag, installing to account/skill path
+        let clone_url = if args.ssh || config.prefer_ssh.unwrap_or(false) {
+            &install_info.repository.ssh_url
         } else {
-            Some(&install_info.install.path)
-        },
+            &install_info.repository.clone_url
+        };
+        install_from_git_to_target(
+            clone_url,
+            Some(&install_info.version.tag),
+            if install_info.install.path == "." {
+                None
+            } else {
+                Some(&install_info.install.path)
+            },
+            Some(&install_info.version.commit_hash),
+            &target_dir,
+            args,
+        )
+        .await?;
+    }
+    verify_installed_checksum(
         &target_dir,
-        force,
-    )
-    .await?;
+        install_info.version.checksum.as_deref(),
+        args.skip_checksum,
+    )?;
+    verify_installed_signature(
+        &target_dir,
+        install_info.version.signature.as_deref(),
+        args.require_signature,
+    )?;
 
     println!(
         "✓ Installed {}/{}@{}",
@@ -461,9 +940,217 @@ async fn install_from_registry(skill_ref: SkillRef, install_dir: &Path, force: b
     );
     println!("  Location: {}", target_dir.display());
 
+    Ok(installed)
+}
+
+/// Install metadata for `uri` cached by an earlier online install
+fn offline_install_info(
+    cache: Option<&MetadataCache>,
+    uri: &str,
+    args: &InstallArgs,
+) -> Result<PakInstallResponse> {
+    if !args.only.is_empty() {
+        bail!("--only fetches files from the registry and can't be used offline");
+    }
+    if args.from_archive {
+        bail!("--from-archive downloads from the registry and can't be used offline");
+    }
+    cache
+        .and_then(|c| c.get_stale(INSTALL_CACHE_NAMESPACE, uri))
+        .with_context(|| {
+            format!(
+                "Skill '{}' is not cached and offline mode is on.\n\
+                 Hint: Install it once without --offline to cache it.",
+                uri
+            )
+        })
+}
+
+/// Download the registry's archive of `uri` and unpack it into `target_dir`
+async fn install_from_archive(client: &PaksClient, uri: &str, target_dir: &Path) -> Result<()> {
+    println!("  Downloading archive...");
+    let staging = tempfile::tempdir().context("Failed to create temp directory")?;
+    let archive = staging.path().join("pak.tar.gz");
+    match client.download_pak_tarball(uri, &archive).await {
+        Ok(()) => {}
+        Err(ApiError::NotFound(_)) => bail!(
+            "The registry has no archive for {}.\n\
+             Hint: Install without --from-archive to clone it from git.",
+            uri
+        ),
+        Err(e) => return Err(e).context("Failed to download pak archive"),
+    }
+
+    let file = std::fs::File::open(&archive).context("Failed to open pak archive")?;
+    let unpacked = staging.path().join("pak");
+    std::fs::create_dir(&unpacked)?;
+    let skill_root = archive::unpack(std::io::BufReader::new(file), &unpacked)?;
+    copy_skill_to_target(&skill_root, target_dir)
+}
+
+/// Check an installed skill against the registry's checksum, removing it if
+/// they differ
+fn verify_installed_checksum(target_dir: &Path, expected: Option<&str>, skip: bool) -> Result<()> {
+    match expected {
+        _ if skip => println!("  ⚠ Skipping checksum verification"),
+        None => {}
+        Some(expected) => {
+            if let Err(e) = checksum::verify(target_dir, expected) {
+                std::fs::remove_dir_all(target_dir).ok();
+                return Err(e);
+            }
+            println!("  ✓ Checksum verified");
+        }
+    }
+    Ok(())
+}
+
+/// Check the signature of an installed skill, removing it if the check fails
+///
+/// The registry-provided signature takes precedence over a `SKILL.md.sig`
+/// shipped with the skill.
+fn verify_installed_signature(
+    target_dir: &Path,
+    registry_signature: Option<&str>,
+    require: bool,
+) -> Result<()> {
+    let config = Config::load()?;
+    let skill_md = std::fs::read(target_dir.join("SKILL.md"))
+        .with_context(|| format!("Failed to read SKILL.md in {}", target_dir.display()))?;
+    let shipped_signature =
+        std::fs::read_to_string(target_dir.join(signature::SIGNATURE_FILE)).ok();
+    let signature_text = registry_signature.or(shipped_signature.as_deref());
+
+    match signature::check(
+        config.signature_public_key.as_deref(),
+        &skill_md,
+        signature_text,
+        require,
+    ) {
+        Ok(SignatureCheck::Verified) => println!("  Signature verified"),
+        Ok(SignatureCheck::NoPublicKey) => eprintln!(
+            "⚠ Warning: skill is signed but no signature_public_key is configured; not verified"
+        ),
+        Ok(SignatureCheck::Unsigned) => {}
+        Err(e) => {
+            // Don't leave an untrusted skill installed
+            std::fs::remove_dir_all(target_dir).ok();
+            return Err(e);
+        }
+    }
+
     Ok(())
 }
 
+/// Warning to show when installing a deprecated pak
+fn deprecation_warning(pak: &InstallPakInfo) -> Option<String> {
+    if pak.status != PakStatus::Deprecated {
+        return None;
+    }
+    let mut warning = format!("{}/{} is deprecated", pak.owner, pak.name);
+    if let Some(message) = &pak.deprecation_message {
+        warning.push_str(": ");
+        warning.push_str(message);
+    }
+    if let Some(successor) = successor(pak) {
+        warning.push_str(&format!("\n  Hint: Install {} instead", successor));
+    }
+    Some(warning)
+}
+
+/// The pak a deprecated pak points its users to, if its deprecation message
+/// or description names one (`use acme/new`, `replaced by acme/new`, ...)
+///
+/// Markers only count at the start of a word, so "because acme/x" doesn't
+/// read as "use acme/x".
+fn successor(pak: &InstallPakInfo) -> Option<String> {
+    const MARKERS: &[&str] = &[
+        "use ",
+        "replaced by ",
+        "superseded by ",
+        "moved to ",
+        "successor: ",
+        "see ",
+    ];
+    let own = format!("{}/{}", pak.owner, pak.name);
+    [
+        pak.deprecation_message.as_deref(),
+        pak.description.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|text| {
+        // ASCII lowercasing keeps byte offsets valid for slicing `text`
+        let lower = text.to_ascii_lowercase();
+        MARKERS.iter().find_map(|marker| {
+            lower.match_indices(marker).find_map(|(at, _)| {
+                if lower[..at]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric)
+                {
+                    return None;
+                }
+                let candidate = text[at + marker.len()..]
+                    .split_whitespace()
+                    .next()?
+                    .trim_matches(|c: char| "`'\"()[],.;:".contains(c));
+                let skill_ref = SkillRef::parse(candidate).ok()?;
+                let name = format!("{}/{}", skill_ref.account(), skill_ref.name());
+                (candidate.contains('/') && name != own).then(|| candidate.to_string())
+            })
+        })
+    })
+}
+
+/// Check the resolved pak and version before installing
+///
+/// Returns warnings to show for a deprecated pak or an explicitly allowed
+/// yanked version. A deprecated pak is refused with `no_deprecated`. A
+/// yanked version is refused unless it was requested by version (`pinned`)
+/// and `allow_yanked` is set; latest resolution never installs a yanked
+/// version.
+fn check_install_status(
+    info: &PakInstallResponse,
+    pinned: bool,
+    allow_yanked: bool,
+    no_deprecated: bool,
+) -> Result<Vec<String>> {
+    let mut warnings = Vec::new();
+    if let Some(warning) = deprecation_warning(&info.pak) {
+        if no_deprecated {
+            bail!("{} (refusing to install with --no-deprecated)", warning);
+        }
+        warnings.push(warning);
+    }
+
+    if info.version.yanked {
+        let uri = format!(
+            "{}/{}@{}",
+            info.pak.owner, info.pak.name, info.version.version
+        );
+        if !pinned {
+            bail!(
+                "Registry resolved {} to a yanked version.\n\
+                 Hint: Install a specific version with {}/{}@<version>",
+                uri,
+                info.pak.owner,
+                info.pak.name
+            );
+        }
+        if !allow_yanked {
+            bail!(
+                "{} has been yanked.\n\
+                 Hint: Pass --allow-yanked if you really need this version",
+                uri
+            );
+        }
+        warnings.push(format!("{} has been yanked", uri));
+    }
+
+    Ok(warnings)
+}
+
 /// Install a skill from a git repository (standalone, not from registry)
 async fn install_from_git(
     url: &str,
@@ -471,7 +1158,9 @@ async fn install_from_git(
     subpath: Option<&str>,
     install_dir: &Path,
     force: bool,
-) -> Result<()> {
+    cache: Option<&CloneCache>,
+    options: CloneOptions,
+) -> Result<InstalledSkill> {
     println!("Installing from git: {}", url);
     if let Some(r) = git_ref {
         println!("  Ref: {}", r);
@@ -481,16 +1170,113 @@ async fn install_from_git(
     }
 
     // Clone and get skill info
-    let (source_path, temp_dir) = clone_git_repo(url, git_ref, subpath).await?;
+    let (source_path, clone) = clone_git_repo(url, git_ref, None, subpath, cache, options).await?;
+    let skill = install_cloned_skill(&source_path, install_dir, force)?;
+
+    println!("✓ Installed {} from git", skill.name());
+    println!("  Location: {}", install_dir.join(skill.name()).display());
+
+    // Done with the clone: clean it up or release its cache entry
+    drop(clone);
+    Ok(InstalledSkill {
+        name: skill.name().to_string(),
+        version: skill.version().to_string(),
+        source: url.to_string(),
+        commit: None,
+        checksum: None,
+        dir: install_dir.join(skill.name()),
+    })
+}
 
-    // Load skill to get metadata
-    let skill = Skill::load(&source_path).context("Failed to load skill from repository")?;
-    let skill_name = skill.name().to_string();
+/// Install a skill from a GitHub Gist
+///
+/// Gists are flat, so every file in the gist is installed next to its
+/// SKILL.md. With no directory to take a name from, the skill is installed
+/// under the `name` from its SKILL.md frontmatter.
+async fn install_from_gist(
+    id: &str,
+    revision: Option<&str>,
+    install_dir: &Path,
+    force: bool,
+) -> Result<InstalledSkill> {
+    offline::ensure_online("Installing from a gist")?;
+    let url = gist_clone_url(id);
+    println!("Installing from gist: {}", id);
+    if let Some(r) = revision {
+        println!("  Revision: {}", r);
+    }
 
-    // For standalone git installs, use just the skill name (no account prefix)
-    let target_dir = install_dir.join(&skill_name);
+    let temp_dir = tempfile::tempdir().context("Failed to create temp directory")?;
+    println!("  Cloning gist...");
+    clone_gist(&url, revision, temp_dir.path())?;
 
-    // Check if already installed
+    if !temp_dir.path().join("SKILL.md").exists() {
+        bail!(
+            "Gist {} has no SKILL.md.\n\
+             Only gists containing a SKILL.md can be installed as skills.",
+            id
+        );
+    }
+    let skill = install_cloned_skill(temp_dir.path(), install_dir, force)?;
+
+    println!("✓ Installed {} from gist", skill.name());
+    println!("  Location: {}", install_dir.join(skill.name()).display());
+
+    Ok(InstalledSkill {
+        name: skill.name().to_string(),
+        version: skill.version().to_string(),
+        source: url,
+        commit: revision.map(str::to_string),
+        checksum: None,
+        dir: install_dir.join(skill.name()),
+    })
+}
+
+/// Clone a gist, checking out `revision` if given
+fn clone_gist(url: &str, revision: Option<&str>, clone_path: &Path) -> Result<()> {
+    let Some(revision) = revision else {
+        return shallow_clone(url, None, clone_path, CloneOptions::default());
+    };
+
+    // Revisions are commit ids, which `clone --branch` can't check out
+    let output = Command::new("git")
+        .arg("clone")
+        .arg("--quiet")
+        .arg(url)
+        .arg(clone_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to execute git clone")?;
+    if !output.status.success() {
+        return Err(clone_failure(url, &String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(clone_path)
+        .args(["checkout", "--quiet", revision])
+        .output()
+        .context("Failed to execute git checkout")?;
+    if !output.status.success() {
+        bail!("Revision {} not found in {}", revision, url);
+    }
+
+    Ok(())
+}
+
+/// Copy a cloned skill into `install_dir`, named after its SKILL.md
+fn install_cloned_skill(source_path: &Path, install_dir: &Path, force: bool) -> Result<Skill> {
+    // Load skill to get metadata
+    let skill = Skill::load(source_path).context("Failed to load skill from repository")?;
+    let skill_name = skill.name().to_string();
+    // The name comes from someone else's SKILL.md; it must not reach outside
+    // of install_dir
+    validate_skill_name(&skill_name)?;
+
+    // For standalone git installs, use just the skill name (no account prefix)
+    let target_dir = install_dir.join(&skill_name);
+
+    // Check if already installed
     if target_dir.exists() {
         if !force {
             bail!(
@@ -506,26 +1292,33 @@ async fn install_from_git(
     }
 
     // Copy to target
-    copy_skill_to_target(&source_path, &target_dir)?;
-
-    println!("✓ Installed {} from git", skill_name);
-    println!("  Location: {}", target_dir.display());
+    copy_skill_to_target(source_path, &target_dir)?;
 
-    // temp_dir is dropped here, cleaning up the clone
-    drop(temp_dir);
-    Ok(())
+    Ok(skill)
 }
 
 /// Install a skill from git to a specific target directory (used by registry install)
+///
+/// `--force`, the clone cache and the clone options come from `args`.
 async fn install_from_git_to_target(
     url: &str,
     git_ref: Option<&str>,
     subpath: Option<&str>,
+    commit: Option<&str>,
     target_dir: &Path,
-    force: bool,
+    args: &InstallArgs,
 ) -> Result<()> {
     // Clone and get skill info
-    let (source_path, temp_dir) = clone_git_repo(url, git_ref, subpath).await?;
+    let cache = clone_cache(args)?;
+    let (source_path, clone) = clone_git_repo(
+        url,
+        git_ref,
+        commit,
+        subpath,
+        cache.as_ref(),
+        args.clone_options(),
+    )
+    .await?;
 
     // Validate skill structure
     if !source_path.join("SKILL.md").exists() {
@@ -537,7 +1330,7 @@ async fn install_from_git_to_target(
     }
 
     // Check if already installed (should be handled by caller, but double-check)
-    if target_dir.exists() && !force {
+    if target_dir.exists() && !args.force {
         bail!(
             "Target directory already exists: {}.\n\
              Use --force to reinstall.",
@@ -548,47 +1341,178 @@ async fn install_from_git_to_target(
     // Copy to target
     copy_skill_to_target(&source_path, target_dir)?;
 
-    // temp_dir is dropped here, cleaning up the clone
-    drop(temp_dir);
+    // Done with the clone: clean it up or release its cache entry
+    drop(clone);
     Ok(())
 }
 
-/// Clone a git repository and return the path to the skill source
-async fn clone_git_repo(
-    url: &str,
-    git_ref: Option<&str>,
-    subpath: Option<&str>,
-) -> Result<(PathBuf, tempfile::TempDir)> {
-    // Create temp directory for clone
-    let temp_dir = tempfile::tempdir().context("Failed to create temp directory")?;
-    let clone_path = temp_dir.path();
+/// What to do about a registry skill that is already installed
+#[derive(Debug, PartialEq, Eq)]
+enum ExistingInstall {
+    /// The requested version is already there
+    Keep,
+    Replace,
+}
 
-    // Build git clone command
-    let mut cmd = Command::new("git");
-    cmd.arg("clone")
-        .arg("--depth")
-        .arg("1")
-        .arg("--single-branch");
+/// Decide whether an existing install at `installed` may be replaced by `target`
+///
+/// Replacing needs `--force`; replacing with an older version additionally
+/// needs `--allow-downgrade`. `installed` is `None` if the existing SKILL.md
+/// can't be read.
+fn check_existing_install(
+    target_dir: &Path,
+    installed: Option<&str>,
+    target: &str,
+    force: bool,
+    allow_downgrade: bool,
+) -> Result<ExistingInstall> {
+    if let Some(installed) = installed {
+        match update::compare_versions(installed, target) {
+            VersionChange::Same if !force => return Ok(ExistingInstall::Keep),
+            VersionChange::Same => {}
+            VersionChange::Downgrade => {
+                println!("  Downgrading {} → {}", installed, target);
+                if !allow_downgrade {
+                    bail!(
+                        "Version {} is older than the installed {} at {}.\n\
+                         Use --force --allow-downgrade to downgrade.",
+                        target,
+                        installed,
+                        target_dir.display()
+                    );
+                }
+            }
+            VersionChange::Upgrade | VersionChange::Different => {
+                println!("  Installed version: {} → {}", installed, target);
+            }
+        }
+    }
 
-    if let Some(r) = git_ref {
-        cmd.arg("--branch").arg(r);
+    if !force {
+        bail!(
+            "Skill already exists at {}.\n\
+             Use --force to reinstall.",
+            target_dir.display()
+        );
     }
+    Ok(ExistingInstall::Replace)
+}
 
-    cmd.arg(url).arg(clone_path);
+/// Keeps a clone usable until the skill has been copied out of it
+///
+/// Dropping it removes an uncached clone, or unlocks the cache entry.
+pub(super) struct CloneGuard {
+    _temp_dir: Option<tempfile::TempDir>,
+    _checkout: Option<CachedCheckout>,
+}
 
-    println!("  Cloning repository...");
-    let output = cmd.output().context("Failed to execute git clone")?;
+/// Clone a git repository and return the path to the skill source
+///
+/// With a cache the clone is reused across installs; otherwise it lives in a
+/// temp directory. Either way it stays in place while the guard is held.
+/// `commit`, if known, is the commit `git_ref` should resolve to and lets a
+/// cached clone be checked without asking the remote. Offline, only a cached
+/// clone will do. `options` only apply to fresh clones.
+///
+/// Waiting for a cache entry's lock and running git both block, so the work
+/// happens on a blocking thread.
+pub(super) async fn clone_git_repo(
+    url: &str,
+    git_ref: Option<&str>,
+    commit: Option<&str>,
+    subpath: Option<&str>,
+    cache: Option<&CloneCache>,
+    options: CloneOptions,
+) -> Result<(PathBuf, CloneGuard)> {
+    let url = url.to_string();
+    let git_ref = git_ref.map(str::to_string);
+    let commit = commit.map(str::to_string);
+    let subpath = subpath.map(str::to_string);
+    let cache = cache.cloned();
+    let offline = offline::is_offline();
+    tokio::task::spawn_blocking(move || {
+        checkout_repo(
+            &url,
+            git_ref.as_deref(),
+            commit.as_deref(),
+            subpath.as_deref(),
+            cache.as_ref(),
+            options,
+            offline,
+        )
+    })
+    .await
+    .context("Clone task failed")?
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("Git clone failed: {}", stderr.trim());
-    }
+/// [`clone_git_repo`] with offline mode passed in
+fn checkout_repo(
+    url: &str,
+    git_ref: Option<&str>,
+    commit: Option<&str>,
+    subpath: Option<&str>,
+    cache: Option<&CloneCache>,
+    options: CloneOptions,
+    offline: bool,
+) -> Result<(PathBuf, CloneGuard)> {
+    let (clone_path, guard) = match cache {
+        Some(cache) if offline => {
+            let Some(checkout) = cache.cached(url, git_ref, commit, subpath)? else {
+                bail!(
+                    "{}{} is not in the clone cache and offline mode is on.\n\
+                     Hint: Install it once without --offline to cache it.",
+                    url,
+                    git_ref.map(|r| format!("@{}", r)).unwrap_or_default()
+                );
+            };
+            println!("  Using cached clone (offline)");
+            (
+                checkout.path.clone(),
+                CloneGuard {
+                    _temp_dir: None,
+                    _checkout: Some(checkout),
+                },
+            )
+        }
+        None if offline => {
+            bail!(
+                "Cannot clone {} in offline mode without the clone cache",
+                url
+            );
+        }
+        Some(cache) => {
+            let checkout = cache.checkout(url, git_ref, commit, subpath, |path| {
+                clone_into(url, git_ref, subpath, path, options)
+            })?;
+            if checkout.reused {
+                println!("  Using cached clone");
+            }
+            (
+                checkout.path.clone(),
+                CloneGuard {
+                    _temp_dir: None,
+                    _checkout: Some(checkout),
+                },
+            )
+        }
+        None => {
+            let temp_dir = tempfile::tempdir().context("Failed to create temp directory")?;
+            clone_into(url, git_ref, subpath, temp_dir.path(), options)?;
+            (
+                temp_dir.path().to_path_buf(),
+                CloneGuard {
+                    _temp_dir: Some(temp_dir),
+                    _checkout: None,
+                },
+            )
+        }
+    };
 
     // Determine source path within clone
     let source_path = if let Some(p) = subpath {
         clone_path.join(p)
     } else {
-        clone_path.to_path_buf()
+        clone_path
     };
 
     // Validate skill structure
@@ -600,11 +1524,303 @@ async fn clone_git_repo(
         );
     }
 
-    Ok((source_path, temp_dir))
+    Ok((source_path, guard))
+}
+
+/// Clone `url` into `clone_path`, sparsely when only `subpath` is needed
+///
+/// Submodules need a full checkout, so they turn off sparse cloning.
+fn clone_into(
+    url: &str,
+    git_ref: Option<&str>,
+    subpath: Option<&str>,
+    clone_path: &Path,
+    options: CloneOptions,
+) -> Result<()> {
+    // Only fetch the blobs of the requested subtree when git supports it
+    let sparse_path = subpath
+        .filter(|p| !p.is_empty() && *p != ".")
+        .filter(|_| !options.recurse_submodules)
+        .filter(|_| git::supports_sparse_checkout());
+
+    if let Some(path) = sparse_path {
+        println!("  Cloning repository (sparse: {})...", path);
+        if let Err(e) = sparse_clone(url, git_ref, path, clone_path, options) {
+            // A full clone would fail the same way
+            if e.is::<GitAuthError>() {
+                return Err(e);
+            }
+            println!(
+                "  Sparse checkout failed ({}), falling back to full clone",
+                e
+            );
+            std::fs::remove_dir_all(clone_path).ok();
+            clone_ref(url, git_ref, clone_path, options)?;
+        }
+    } else {
+        println!("  Cloning repository...");
+        clone_ref(url, git_ref, clone_path, options)?;
+    }
+    Ok(())
+}
+
+/// Shallow clone of `git_ref`, falling back to a full clone and checkout
+///
+/// `--branch` only takes branch and tag names the remote advertises, and some
+/// hosts refuse shallow single-branch clones of tags. When that fails, the
+/// whole repository is cloned and `git_ref` checked out, which also covers
+/// commit hashes. Git's output is only reported if both attempts fail.
+fn clone_ref(
+    url: &str,
+    git_ref: Option<&str>,
+    clone_path: &Path,
+    options: CloneOptions,
+) -> Result<()> {
+    let Some(git_ref) = git_ref else {
+        return shallow_clone(url, None, clone_path, options);
+    };
+    let shallow_err = match shallow_clone(url, Some(git_ref), clone_path, options) {
+        Ok(()) => return Ok(()),
+        // A full clone would fail the same way
+        Err(e) if e.is::<GitAuthError>() => return Err(e),
+        Err(e) => e,
+    };
+
+    println!(
+        "  Shallow clone of '{}' failed, retrying with a full clone",
+        git_ref
+    );
+    std::fs::remove_dir_all(clone_path).ok();
+    full_clone(url, git_ref, clone_path, options).map_err(|full_err| {
+        if full_err.is::<GitAuthError>() {
+            return full_err;
+        }
+        anyhow::anyhow!(
+            "Failed to clone {} at '{}'.\n  shallow clone: {:#}\n  full clone: {:#}",
+            url,
+            git_ref,
+            shallow_err,
+            full_err
+        )
+    })
+}
+
+/// Clone the whole repository and check out `git_ref`
+fn full_clone(url: &str, git_ref: &str, clone_path: &Path, options: CloneOptions) -> Result<()> {
+    let output = Command::new("git")
+        .arg("clone")
+        .arg("--quiet")
+        .arg("--no-checkout")
+        .arg(url)
+        .arg(clone_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to execute git clone")?;
+    if !output.status.success() {
+        return Err(clone_failure(url, &String::from_utf8_lossy(&output.stderr)));
+    }
+
+    git::git_cmd(&["checkout", "--quiet", "--detach", git_ref], clone_path)?;
+    if options.recurse_submodules {
+        git::git_cmd(
+            &["submodule", "update", "--quiet", "--init", "--recursive"],
+            clone_path,
+        )?;
+    }
+    Ok(())
+}
+
+/// Enforce size limits on a registry pak of `size` bytes
+///
+/// Fails if the pak is over `max_size_mb`. Returns whether the user should be
+/// asked first because it's over `confirm_over_mb`, which `yes` skips.
+fn check_size(
+    name: &str,
+    size: Option<u64>,
+    confirm_over_mb: Option<u64>,
+    max_size_mb: Option<u64>,
+    yes: bool,
+) -> Result<bool> {
+    let Some(size) = size else {
+        return Ok(false);
+    };
+    if let Some(max) = max_size_mb
+        && size > max.saturating_mul(MB)
+    {
+        bail!(
+            "{} is {}, over the --max-size limit of {} MB",
+            name,
+            format_size(size),
+            max
+        );
+    }
+    Ok(!yes && confirm_over_mb.is_some_and(|limit| size > limit.saturating_mul(MB)))
+}
+
+/// Ask whether to go ahead with a large install; fails without a terminal
+fn confirm_large_install(name: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!(
+            "{} is larger than confirm_install_over_mb.\n\
+             Hint: Pass --yes to install it without asking.",
+            name
+        );
+    }
+    Ok(Confirm::new()
+        .with_prompt(format!("{} is large. Install it anyway?", name))
+        .default(false)
+        .interact()?)
+}
+
+/// Clone failed because the repository needs credentials we don't have
+#[derive(Debug)]
+struct GitAuthError {
+    url: String,
+}
+
+impl std::fmt::Display for GitAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Authentication failed while cloning {}.\n\
+             The repository is likely private.\n",
+            self.url
+        )?;
+        if self.url.starts_with("git@") || self.url.starts_with("ssh://") {
+            write!(
+                f,
+                "Hint: Check that your SSH key has access to the repository and is\n\
+                 \x20     loaded in your SSH agent (ssh-add -l)."
+            )
+        } else {
+            write!(
+                f,
+                "Hint: Clone over SSH instead (use --ssh or prefer_ssh = true for registry\n\
+                 \x20     skills, or a git@ URL), or configure a git credential helper for HTTPS.\n\
+                 \x20     For private registry skills, also make sure you ran 'paks login'."
+            )
+        }
+    }
+}
+
+impl std::error::Error for GitAuthError {}
+
+/// Whether git's stderr indicates missing or rejected credentials
+fn is_auth_failure(stderr: &str) -> bool {
+    const PATTERNS: &[&str] = &[
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "authentication failed",
+        "permission denied (publickey)",
+        "access denied",
+        "repository not found",
+        "the requested url returned error: 401",
+        "the requested url returned error: 403",
+    ];
+    let stderr = stderr.to_ascii_lowercase();
+    PATTERNS.iter().any(|p| stderr.contains(p))
+}
+
+/// Turn a failed clone's stderr into an error, with guidance for auth failures
+fn clone_failure(url: &str, stderr: &str) -> anyhow::Error {
+    if is_auth_failure(stderr) {
+        GitAuthError {
+            url: url.to_string(),
+        }
+        .into()
+    } else {
+        anyhow::anyhow!("Git clone failed: {}", stderr.trim())
+    }
+}
+
+/// Arguments for `git clone` of `git_ref` (or the default branch)
+///
+/// The clone is shallow and single-branch unless `options` ask for the full
+/// history. A `sparse` clone fetches only the blobs it checks out.
+fn clone_args(
+    url: &str,
+    git_ref: Option<&str>,
+    clone_path: &Path,
+    options: CloneOptions,
+    sparse: bool,
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = vec!["clone".into()];
+    if options.depth > 0 {
+        args.push("--depth".into());
+        args.push(options.depth.to_string().into());
+        args.push("--single-branch".into());
+    }
+    if sparse {
+        args.push("--filter=blob:none".into());
+        args.push("--sparse".into());
+    }
+    if options.recurse_submodules {
+        args.push("--recurse-submodules".into());
+    }
+    if let Some(r) = git_ref {
+        args.push("--branch".into());
+        args.push(r.into());
+    }
+    args.push(url.into());
+    args.push(clone_path.into());
+    args
+}
+
+/// Single-branch clone of the whole repository, shallow by default
+fn shallow_clone(
+    url: &str,
+    git_ref: Option<&str>,
+    clone_path: &Path,
+    options: CloneOptions,
+) -> Result<()> {
+    let output = Command::new("git")
+        .args(clone_args(url, git_ref, clone_path, options, false))
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to execute git clone")?;
+
+    if !output.status.success() {
+        return Err(clone_failure(url, &String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(())
+}
+
+/// Shallow clone that only materializes `path`
+///
+/// Uses a blobless partial clone (`--filter=blob:none`) with cone-mode
+/// sparse-checkout, so only the trees of the tip commit plus the blobs under
+/// `path` (and files at the repository root) are downloaded. For a small skill
+/// inside a large monorepo the transfer scales with the size of the skill
+/// rather than the size of the repository. Servers without partial clone
+/// support ignore the filter and send everything, which is no worse than a
+/// regular shallow clone.
+fn sparse_clone(
+    url: &str,
+    git_ref: Option<&str>,
+    path: &str,
+    clone_path: &Path,
+    options: CloneOptions,
+) -> Result<()> {
+    let output = Command::new("git")
+        .args(clone_args(url, git_ref, clone_path, options, true))
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to execute git clone")?;
+
+    if !output.status.success() {
+        return Err(clone_failure(url, &String::from_utf8_lossy(&output.stderr)));
+    }
+
+    git::git_cmd(&["sparse-checkout", "init", "--cone"], clone_path)?;
+    git::git_cmd(&["sparse-checkout", "set", path], clone_path)?;
+
+    Ok(())
 }
 
 /// Copy skill files to target directory
-fn copy_skill_to_target(source_path: &Path, target_dir: &Path) -> Result<()> {
+pub(super) fn copy_skill_to_target(source_path: &Path, target_dir: &Path) -> Result<()> {
     // Create parent directories
     if let Some(parent) = target_dir.parent() {
         std::fs::create_dir_all(parent)
@@ -625,7 +1841,11 @@ fn copy_skill_to_target(source_path: &Path, target_dir: &Path) -> Result<()> {
 }
 
 /// Install a skill from a local path
-async fn install_from_local(source: &Path, install_dir: &Path, force: bool) -> Result<()> {
+async fn install_from_local(
+    source: &Path,
+    install_dir: &Path,
+    force: bool,
+) -> Result<InstalledSkill> {
     let source = if source.is_absolute() {
         source.to_path_buf()
     } else {
@@ -655,10 +1875,19 @@ async fn install_from_local(source: &Path, install_dir: &Path, force: bool) -> R
     // Determine target directory
     let target_dir = install_dir.join(&skill_name);
 
+    let installed = InstalledSkill {
+        name: skill_name.clone(),
+        version: skill.version().to_string(),
+        source: source.display().to_string(),
+        commit: None,
+        checksum: None,
+        dir: target_dir.clone(),
+    };
+
     // Check if source and target are the same
     if source.canonicalize().ok() == target_dir.canonicalize().ok() {
         println!("✓ Skill is already in the target location");
-        return Ok(());
+        return Ok(installed);
     }
 
     // Check if already installed
@@ -689,50 +1918,111 @@ async fn install_from_local(source: &Path, install_dir: &Path, force: bool) -> R
     println!("✓ Installed {} from local path", skill_name);
     println!("  Location: {}", target_dir.display());
 
-    Ok(())
+    Ok(installed)
 }
 
-/// Recursively copy a directory
+/// Files every skill needs; failing to copy one fails the install
+const CRITICAL_FILES: &[&str] = &["SKILL.md"];
+
+/// An entry [`copy_dir_recursive`] couldn't copy
+#[derive(Debug)]
+struct CopyFailure {
+    /// Path relative to the directory being copied
+    path: PathBuf,
+    error: anyhow::Error,
+}
+
+/// Recursively copy a directory, leaving out what its `.paksignore` excludes
+///
+/// Entries that fail to copy are skipped and listed in a warning at the end,
+/// so one odd asset doesn't abort the install. Failing to copy a
+/// [`CRITICAL_FILES`] entry is an error.
 fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+    let rules = IgnoreRules::load(src);
+    let mut failures = Vec::new();
+    copy_tree(src, dst, Path::new(""), &rules, &mut failures)?;
+    check_copy_failures(&failures)
+}
+
+/// Copy `src` to `dst`, collecting per-entry failures under `rel`
+///
+/// Only failing to create `dst` or list `src` is returned as an error.
+fn copy_tree(
+    src: &Path,
+    dst: &Path,
+    rel: &Path,
+    rules: &IgnoreRules,
+    failures: &mut Vec<CopyFailure>,
+) -> Result<()> {
     std::fs::create_dir_all(dst)
         .with_context(|| format!("Failed to create directory {}", dst.display()))?;
 
     for entry in std::fs::read_dir(src)
         .with_context(|| format!("Failed to read directory {}", src.display()))?
     {
-        let entry = entry?;
-        let src_path = entry.path();
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                failures.push(CopyFailure {
+                    path: rel.to_path_buf(),
+                    error: e.into(),
+                });
+                continue;
+            }
+        };
+        let rel_path = rel.join(entry.file_name());
         let dst_path = dst.join(entry.file_name());
+        if let Err(error) = copy_entry(&entry, &dst_path, &rel_path, rules, failures) {
+            failures.push(CopyFailure {
+                path: rel_path,
+                error,
+            });
+        }
+    }
 
-        let file_type = entry.file_type()?;
+    Ok(())
+}
 
-        if file_type.is_dir() {
-            // Skip .git directories
-            if entry.file_name() == ".git" {
-                continue;
-            }
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else if file_type.is_file() {
-            std::fs::copy(&src_path, &dst_path).with_context(|| {
-                format!(
-                    "Failed to copy {} to {}",
-                    src_path.display(),
-                    dst_path.display()
-                )
-            })?;
-        } else if file_type.is_symlink() {
-            // Copy symlink target
-            let target = std::fs::read_link(&src_path)?;
-            #[cfg(unix)]
-            std::os::unix::fs::symlink(&target, &dst_path)
-                .with_context(|| format!("Failed to create symlink at {}", dst_path.display()))?;
-            #[cfg(windows)]
-            {
-                if src_path.is_dir() {
-                    std::os::windows::fs::symlink_dir(&target, &dst_path)?;
-                } else {
-                    std::os::windows::fs::symlink_file(&target, &dst_path)?;
-                }
+/// Copy a single directory entry to `dst_path`
+fn copy_entry(
+    entry: &std::fs::DirEntry,
+    dst_path: &Path,
+    rel_path: &Path,
+    rules: &IgnoreRules,
+    failures: &mut Vec<CopyFailure>,
+) -> Result<()> {
+    let src_path = entry.path();
+    let file_type = entry.file_type()?;
+    if rules.is_ignored(rel_path, file_type.is_dir()) {
+        return Ok(());
+    }
+
+    if file_type.is_dir() {
+        // Skip .git directories
+        if entry.file_name() == ".git" {
+            return Ok(());
+        }
+        copy_tree(&src_path, dst_path, rel_path, rules, failures)?;
+    } else if file_type.is_file() {
+        std::fs::copy(&src_path, dst_path).with_context(|| {
+            format!(
+                "Failed to copy {} to {}",
+                src_path.display(),
+                dst_path.display()
+            )
+        })?;
+    } else if file_type.is_symlink() {
+        // Copy symlink target
+        let target = std::fs::read_link(&src_path)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&target, dst_path)
+            .with_context(|| format!("Failed to create symlink at {}", dst_path.display()))?;
+        #[cfg(windows)]
+        {
+            if src_path.is_dir() {
+                std::os::windows::fs::symlink_dir(&target, dst_path)?;
+            } else {
+                std::os::windows::fs::symlink_file(&target, dst_path)?;
             }
         }
     }
@@ -740,28 +2030,890 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Warn about entries that weren't copied, failing if any was critical
+fn check_copy_failures(failures: &[CopyFailure]) -> Result<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!("⚠ Warning: {} file(s) could not be copied:", failures.len());
+    for failure in failures {
+        eprintln!("    {}: {:#}", failure.path.display(), failure.error);
+    }
+
+    if let Some(critical) = failures
+        .iter()
+        .find(|f| CRITICAL_FILES.iter().any(|c| f.path == Path::new(c)))
+    {
+        bail!(
+            "Failed to copy {}: {:#}",
+            critical.path.display(),
+            critical.error
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn install_pak_info(status: PakStatus, message: Option<&str>) -> InstallPakInfo {
+        InstallPakInfo {
+            id: "00000000-0000-0000-0000-000000000000".parse().unwrap(),
+            owner: "acme".to_string(),
+            name: "old-skill".to_string(),
+            description: None,
+            visibility: Default::default(),
+            status,
+            deprecation_message: message.map(str::to_string),
+        }
+    }
+
+    fn install_response(status: PakStatus, yanked: bool) -> PakInstallResponse {
+        serde_json::from_value(serde_json::json!({
+            "pak": install_pak_info(status, None),
+            "version": {
+                "version": "1.0.0",
+                "tag": "v1.0.0",
+                "commit_hash": "0123456789abcdef",
+                "published_at": "2025-01-01T00:00:00Z",
+                "yanked": yanked,
+            },
+            "repository": {
+                "url": "https://github.com/acme/skills",
+                "clone_url": "https://github.com/acme/skills.git",
+                "ssh_url": "git@github.com:acme/skills.git",
+                "default_branch": "main",
+            },
+            "install": { "path": ".", "files": ["SKILL.md"] },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_install_status_deprecated_warns_but_installs() {
+        let info = install_response(PakStatus::Deprecated, false);
+        let warnings = check_install_status(&info, false, false, false).unwrap();
+        assert_eq!(warnings, ["acme/old-skill is deprecated"]);
+    }
+
+    #[test]
+    fn test_install_status_no_deprecated_refuses() {
+        let info = install_response(PakStatus::Deprecated, false);
+        let err = check_install_status(&info, false, false, true).unwrap_err();
+        assert!(err.to_string().contains("acme/old-skill is deprecated"));
+        assert!(err.to_string().contains("--no-deprecated"));
+
+        let info = install_response(PakStatus::Active, false);
+        assert!(
+            check_install_status(&info, false, false, true)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_install_status_yanked_blocked_without_flag() {
+        let info = install_response(PakStatus::Active, true);
+        let err = check_install_status(&info, true, false, false).unwrap_err();
+        assert!(err.to_string().contains("--allow-yanked"));
+    }
+
+    #[test]
+    fn test_install_status_yanked_allowed_with_flag() {
+        let info = install_response(PakStatus::Active, true);
+        let warnings = check_install_status(&info, true, true, false).unwrap();
+        assert_eq!(warnings, ["acme/old-skill@1.0.0 has been yanked"]);
+    }
+
+    #[test]
+    fn test_install_status_latest_never_yanked() {
+        let info = install_response(PakStatus::Active, true);
+        assert!(check_install_status(&info, false, true, false).is_err());
+    }
+
+    #[test]
+    fn test_existing_install_same_version() {
+        let check = |force| {
+            check_existing_install(
+                Path::new("acme--deploy"),
+                Some("1.2.0"),
+                "1.2.0",
+                force,
+                false,
+            )
+        };
+        assert_eq!(check(false).unwrap(), ExistingInstall::Keep);
+        assert_eq!(check(true).unwrap(), ExistingInstall::Replace);
+    }
+
+    #[test]
+    fn test_existing_install_upgrade_needs_force() {
+        assert!(
+            check_existing_install(
+                Path::new("acme--deploy"),
+                Some("1.2.0"),
+                "1.3.0",
+                false,
+                false
+            )
+            .is_err()
+        );
+        assert_eq!(
+            check_existing_install(
+                Path::new("acme--deploy"),
+                Some("1.2.0"),
+                "1.3.0",
+                true,
+                false
+            )
+            .unwrap(),
+            ExistingInstall::Replace
+        );
+    }
+
+    #[test]
+    fn test_existing_install_downgrade_needs_flag() {
+        let err = check_existing_install(
+            Path::new("acme--deploy"),
+            Some("2.0.0"),
+            "1.9.0",
+            true,
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("--allow-downgrade"));
+        assert!(
+            check_existing_install(
+                Path::new("acme--deploy"),
+                Some("2.0.0"),
+                "1.9.0",
+                false,
+                true
+            )
+            .is_err()
+        );
+        assert_eq!(
+            check_existing_install(
+                Path::new("acme--deploy"),
+                Some("2.0.0"),
+                "1.9.0",
+                true,
+                true
+            )
+            .unwrap(),
+            ExistingInstall::Replace
+        );
+    }
+
+    #[test]
+    fn test_deprecated_pak_warns() {
+        let pak = install_pak_info(PakStatus::Deprecated, Some("use acme/new-skill"));
+        assert_eq!(
+            deprecation_warning(&pak).as_deref(),
+            Some(
+                "acme/old-skill is deprecated: use acme/new-skill\n  \
+                 Hint: Install acme/new-skill instead"
+            )
+        );
+
+        let pak = install_pak_info(PakStatus::Deprecated, None);
+        assert_eq!(
+            deprecation_warning(&pak).as_deref(),
+            Some("acme/old-skill is deprecated")
+        );
+
+        let pak = install_pak_info(PakStatus::Active, None);
+        assert!(deprecation_warning(&pak).is_none());
+    }
+
+    #[test]
+    fn test_deprecation_successor() {
+        let mut pak = install_pak_info(PakStatus::Deprecated, Some("Replaced by `acme/deploy@2`."));
+        assert_eq!(successor(&pak).as_deref(), Some("acme/deploy@2"));
+
+        // The description is a fallback for the message
+        pak.deprecation_message = Some("No longer maintained".to_string());
+        pak.description = Some("Old deploy skill; see acme/deploy-v2 instead".to_string());
+        assert_eq!(successor(&pak).as_deref(), Some("acme/deploy-v2"));
+
+        // Words that aren't pak references, or the pak itself, are ignored
+        pak.description = Some("Use with care, or see acme/old-skill".to_string());
+        assert_eq!(successor(&pak), None);
+
+        // Markers inside other words don't count
+        pak.deprecation_message = Some("Deprecated because acme/legacy-api shut down".to_string());
+        pak.description = Some("Oversee acme/ops rollouts".to_string());
+        assert_eq!(successor(&pak), None);
+    }
+
+    /// Create a local repository with two skills under `skills/`
+    fn monorepo_fixture() -> tempfile::TempDir {
+        let repo = tempfile::tempdir().unwrap();
+        let root = repo.path();
+        for skill in ["alpha", "beta"] {
+            let dir = root.join("skills").join(skill);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: test\n---\n", skill),
+            )
+            .unwrap();
+        }
+        std::fs::write(root.join("README.md"), "# Skills").unwrap();
+
+        for args in [
+            &["init", "-q"][..],
+            &["add", "."],
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "init",
+            ],
+        ] {
+            git::git_cmd(args, root).unwrap();
+        }
+        repo
+    }
+
+    #[test]
+    fn test_cloned_skill_name_cannot_escape_install_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let install_dir = temp.path().join("skills");
+        let source = temp.path().join("source");
+        std::fs::create_dir_all(&install_dir).unwrap();
+        std::fs::create_dir_all(temp.path().join("victim")).unwrap();
+
+        for name in ["..", "../victim"] {
+            std::fs::create_dir_all(&source).unwrap();
+            std::fs::write(
+                source.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: test\n---\n", name),
+            )
+            .unwrap();
+            let err = install_cloned_skill(&source, &install_dir, true).unwrap_err();
+            assert!(err.to_string().contains("Invalid skill name"), "{}", err);
+        }
+        assert!(temp.path().join("victim").exists());
+        assert!(install_dir.exists());
+    }
+
+    #[test]
+    fn test_clone_auth_failure_is_friendly() {
+        let stderr = "Cloning into '/tmp/x'...\n\
+                      fatal: could not read Username for 'https://github.com': \
+                      terminal prompts disabled";
+        let err = clone_failure("https://github.com/acme/private.git", stderr);
+
+        assert!(err.is::<GitAuthError>());
+        let message = err.to_string();
+        assert!(message.contains("https://github.com/acme/private.git"));
+        assert!(message.contains("private"));
+        assert!(message.contains("--ssh"));
+
+        let url = "git@github.com:acme/private.git";
+        let err = clone_failure(url, "git@github.com: Permission denied (publickey).");
+        let message = err.to_string();
+        assert!(message.contains("ssh-add"));
+        assert!(!message.contains("--ssh"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_keeps_going_past_dangling_symlink() {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(src.path().join("assets")).unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        std::fs::write(src.path().join("assets/logo.svg"), "<svg/>").unwrap();
+        std::os::unix::fs::symlink("missing.txt", src.path().join("assets/broken")).unwrap();
+
+        let dst = tempfile::tempdir().unwrap();
+        let target = dst.path().join("demo");
+        copy_dir_recursive(src.path(), &target).unwrap();
+        assert!(target.join("SKILL.md").is_file());
+        assert!(target.join("assets/logo.svg").is_file());
+        assert_eq!(
+            std::fs::read_link(target.join("assets/broken")).unwrap(),
+            Path::new("missing.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_keeps_going_past_unreadable_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let src = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(src.path().join("references")).unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        for name in ["a.md", "locked.md", "z.md"] {
+            std::fs::write(src.path().join("references").join(name), name).unwrap();
+        }
+        let locked = src.path().join("references/locked.md");
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+        if std::fs::read(&locked).is_ok() {
+            // Running as root, which ignores file permissions
+            return;
+        }
+
+        let dst = tempfile::tempdir().unwrap();
+        let target = dst.path().join("demo");
+        let mut failures = Vec::new();
+        copy_tree(
+            src.path(),
+            &target,
+            Path::new(""),
+            &IgnoreRules::load(src.path()),
+            &mut failures,
+        )
+        .unwrap();
+        let failed: Vec<_> = failures.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(failed, [Path::new("references/locked.md")]);
+        assert!(target.join("SKILL.md").is_file());
+        assert!(target.join("references/a.md").is_file());
+        assert!(target.join("references/z.md").is_file());
+
+        // It isn't a critical file, so the copy as a whole still succeeds
+        copy_dir_recursive(src.path(), &dst.path().join("again")).unwrap();
+        assert!(dst.path().join("again/references/z.md").is_file());
+    }
+
+    #[test]
+    fn test_copy_dir_honors_paksignore() {
+        let src = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(src.path().join("scripts/node_modules/dep")).unwrap();
+        std::fs::write(src.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        std::fs::write(src.path().join(".paksignore"), "node_modules/\n*.tmp\n").unwrap();
+        std::fs::write(src.path().join("scripts/run.sh"), "").unwrap();
+        std::fs::write(src.path().join("scripts/scratch.tmp"), "").unwrap();
+        std::fs::write(src.path().join("scripts/node_modules/dep/index.js"), "").unwrap();
+
+        let dst = tempfile::tempdir().unwrap();
+        let target = dst.path().join("demo");
+        copy_dir_recursive(src.path(), &target).unwrap();
+        assert!(target.join("scripts/run.sh").is_file());
+        assert!(target.join(".paksignore").is_file());
+        assert!(!target.join("scripts/scratch.tmp").exists());
+        assert!(!target.join("scripts/node_modules").exists());
+    }
+
+    #[test]
+    fn test_check_copy_failures() {
+        let failure = |path: &str| CopyFailure {
+            path: PathBuf::from(path),
+            error: anyhow::anyhow!("permission denied"),
+        };
+        check_copy_failures(&[]).unwrap();
+        check_copy_failures(&[failure("assets/big.bin")]).unwrap();
+
+        let err =
+            check_copy_failures(&[failure("assets/big.bin"), failure("SKILL.md")]).unwrap_err();
+        assert!(err.to_string().contains("Failed to copy SKILL.md"));
+    }
+
+    #[test]
+    fn test_check_size_limits() {
+        let size = Some(20 * MB);
+        // Unknown sizes and sizes under the limits pass without asking
+        assert!(!check_size("acme/big", None, Some(1), Some(1), false).unwrap());
+        assert!(!check_size("acme/big", size, Some(50), Some(50), false).unwrap());
+
+        assert!(check_size("acme/big", size, Some(10), None, false).unwrap());
+        assert!(!check_size("acme/big", size, Some(10), None, true).unwrap());
+
+        // --max-size applies even with --yes
+        let err = check_size("acme/big", size, None, Some(10), true).unwrap_err();
+        assert!(err.to_string().contains("20.0 MB"));
+        assert!(err.to_string().contains("--max-size limit of 10 MB"));
+    }
+
+    #[test]
+    fn test_clone_other_failure_keeps_git_error() {
+        let err = clone_failure(
+            "https://github.com/acme/skills.git",
+            "fatal: Remote branch v9.9.9 not found in upstream origin",
+        );
+        assert!(!err.is::<GitAuthError>());
+        assert!(err.to_string().contains("Remote branch v9.9.9 not found"));
+    }
+
+    #[tokio::test]
+    async fn test_clone_subpath_is_sparse() {
+        if !git::supports_sparse_checkout() {
+            return;
+        }
+        let repo = monorepo_fixture();
+        let url = format!("file://{}", repo.path().display());
+
+        let (source, _clone) = clone_git_repo(
+            &url,
+            None,
+            None,
+            Some("skills/alpha"),
+            None,
+            CloneOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(source.join("SKILL.md").is_file());
+        assert!(!source.join("../beta").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clone_ref_not_at_branch_tip() {
+        let repo = monorepo_fixture();
+        let root = repo.path();
+        let tagged = git::git_cmd(&["rev-parse", "HEAD"], root).unwrap();
+        git::git_cmd(&["tag", "v1.0.0"], root).unwrap();
+        std::fs::remove_dir_all(root.join("skills/beta")).unwrap();
+        for args in [
+            &["add", "-A"][..],
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "drop beta",
+            ],
+        ] {
+            git::git_cmd(args, root).unwrap();
+        }
+
+        let bare = tempfile::tempdir().unwrap();
+        git::git_cmd(
+            &[
+                "clone",
+                "-q",
+                "--bare",
+                &root.to_string_lossy(),
+                &bare.path().to_string_lossy(),
+            ],
+            root,
+        )
+        .unwrap();
+        let url = format!("file://{}", bare.path().display());
+
+        // A tag behind the branch tip, and a commit hash `--branch` can't take
+        for git_ref in ["v1.0.0", tagged.as_str()] {
+            let (source, _clone) = clone_git_repo(
+                &url,
+                Some(git_ref),
+                None,
+                Some("skills/beta"),
+                None,
+                CloneOptions::default(),
+            )
+            .await
+            .unwrap();
+            assert!(source.join("SKILL.md").is_file());
+        }
+
+        let err = clone_git_repo(
+            &url,
+            Some("v9.9.9"),
+            None,
+            None,
+            None,
+            CloneOptions::default(),
+        )
+        .await
+        .err()
+        .unwrap();
+        let message = format!("{:#}", err);
+        assert!(message.contains("shallow clone"));
+        assert!(message.contains("full clone"));
+    }
+
+    #[tokio::test]
+    async fn test_cached_clone_is_reused() {
+        let repo = monorepo_fixture();
+        let url = format!("file://{}", repo.path().display());
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = CloneCache::new(cache_dir.path().to_path_buf());
+
+        // Each guard is dropped at once; holding it would keep the entry locked
+        let (first, _) = clone_git_repo(
+            &url,
+            None,
+            None,
+            Some("skills/alpha"),
+            Some(&cache),
+            CloneOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert!(first.starts_with(cache_dir.path()));
+
+        let (second, _) = clone_git_repo(
+            &url,
+            None,
+            None,
+            Some("skills/alpha"),
+            Some(&cache),
+            CloneOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(first, second);
+        assert!(second.join("SKILL.md").is_file());
+    }
+
+    #[tokio::test]
+    async fn test_offline_checkout_uses_cache_only() {
+        let repo = monorepo_fixture();
+        let url = format!("file://{}", repo.path().display());
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = CloneCache::new(cache_dir.path().to_path_buf());
+
+        // Nothing cached yet, and offline mode never clones
+        let err = checkout_repo(
+            &url,
+            None,
+            None,
+            Some("skills/alpha"),
+            Some(&cache),
+            CloneOptions::default(),
+            true,
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("not in the clone cache"));
+        assert!(
+            checkout_repo(
+                &url,
+                None,
+                None,
+                Some("skills/alpha"),
+                None,
+                CloneOptions::default(),
+                true
+            )
+            .is_err()
+        );
+
+        clone_git_repo(
+            &url,
+            None,
+            None,
+            Some("skills/alpha"),
+            Some(&cache),
+            CloneOptions::default(),
+        )
+        .await
+        .unwrap();
+        // Unreachable once cached: offline mode must not ask the remote
+        drop(repo);
+        let (source, _clone) = checkout_repo(
+            &url,
+            None,
+            None,
+            Some("skills/alpha"),
+            Some(&cache),
+            CloneOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert!(source.starts_with(cache_dir.path()));
+        assert!(source.join("SKILL.md").is_file());
+    }
+
     #[test]
-    fn test_skill_ref_parse() {
-        let ref1 = SkillRef::parse("stakpak/kubernetes-deploy").unwrap();
-        assert_eq!(ref1.account, "stakpak");
-        assert_eq!(ref1.name, "kubernetes-deploy");
-        assert!(ref1.version.is_none());
+    fn test_offline_install_info_from_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MetadataCache::with_clock(
+            dir.path().to_path_buf(),
+            "https://registry.test",
+            std::time::Duration::ZERO,
+            crate::commands::core::cache::SystemClock,
+        );
+        let args = local_install_args(vec![], dir.path(), false);
+
+        let err = offline_install_info(Some(&cache), "acme/k8s", &args).unwrap_err();
+        assert!(err.to_string().contains("not cached"));
+
+        let info = install_response(PakStatus::Active, false);
+        cache
+            .put(INSTALL_CACHE_NAMESPACE, "acme/k8s", &info)
+            .unwrap();
+        // Served however old the entry is
+        let cached = offline_install_info(Some(&cache), "acme/k8s", &args).unwrap();
+        assert_eq!(cached.version.commit_hash, info.version.commit_hash);
+
+        let args = InstallArgs {
+            from_archive: true,
+            ..args
+        };
+        assert!(offline_install_info(Some(&cache), "acme/k8s", &args).is_err());
+    }
 
-        let ref2 = SkillRef::parse("stakpak/kubernetes-deploy@1.2.3").unwrap();
-        assert_eq!(ref2.account, "stakpak");
-        assert_eq!(ref2.name, "kubernetes-deploy");
-        assert_eq!(ref2.version, Some("1.2.3".to_string()));
+    fn local_install_args(sources: Vec<String>, dir: &Path, fail_fast: bool) -> InstallArgs {
+        InstallArgs {
+            sources,
+            dir: Some(dir.to_string_lossy().into_owned()),
+            fail_fast,
+            git_depth: 1,
+            ..Default::default()
+        }
     }
 
     #[test]
-    fn test_skill_ref_parse_invalid() {
-        assert!(SkillRef::parse("invalid").is_err());
-        assert!(SkillRef::parse("too/many/slashes").is_err());
-        assert!(SkillRef::parse("UPPERCASE/skill").is_err());
+    fn test_clone_args_for_options() {
+        let args = |git_ref, options, sparse| -> Vec<String> {
+            clone_args(
+                "https://x/r.git",
+                git_ref,
+                Path::new("/tmp/c"),
+                options,
+                sparse,
+            )
+            .into_iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+        };
+        let full = CloneOptions {
+            depth: 0,
+            recurse_submodules: false,
+        };
+        let deep = CloneOptions {
+            depth: 20,
+            recurse_submodules: false,
+        };
+        let submodules = CloneOptions {
+            depth: 1,
+            recurse_submodules: true,
+        };
+        let full_submodules = CloneOptions {
+            depth: 0,
+            recurse_submodules: true,
+        };
+
+        assert_eq!(
+            args(None, CloneOptions::default(), false),
+            [
+                "clone",
+                "--depth",
+                "1",
+                "--single-branch",
+                "https://x/r.git",
+                "/tmp/c"
+            ]
+        );
+        assert_eq!(
+            args(Some("v1"), deep, false),
+            [
+                "clone",
+                "--depth",
+                "20",
+                "--single-branch",
+                "--branch",
+                "v1",
+                "https://x/r.git",
+                "/tmp/c"
+            ]
+        );
+        assert_eq!(
+            args(Some("v1"), full, false),
+            ["clone", "--branch", "v1", "https://x/r.git", "/tmp/c"]
+        );
+        assert_eq!(
+            args(None, submodules, false),
+            [
+                "clone",
+                "--depth",
+                "1",
+                "--single-branch",
+                "--recurse-submodules",
+                "https://x/r.git",
+                "/tmp/c"
+            ]
+        );
+        assert_eq!(
+            args(None, full_submodules, false),
+            ["clone", "--recurse-submodules", "https://x/r.git", "/tmp/c"]
+        );
+        assert_eq!(
+            args(None, full, true),
+            [
+                "clone",
+                "--filter=blob:none",
+                "--sparse",
+                "https://x/r.git",
+                "/tmp/c"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clone_options_bypass_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut args = local_install_args(Vec::new(), dir.path(), false);
+        assert_eq!(args.clone_options(), CloneOptions::default());
+        assert!(clone_cache(&args).unwrap().is_some());
+
+        args.git_depth = 0;
+        assert!(clone_cache(&args).unwrap().is_none());
+
+        args.git_depth = 1;
+        args.recurse_submodules = true;
+        assert!(clone_cache(&args).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_install_batch_continues_past_failures() {
+        let repo = monorepo_fixture();
+        let alpha = repo
+            .path()
+            .join("skills/alpha")
+            .to_string_lossy()
+            .into_owned();
+        let beta = repo
+            .path()
+            .join("skills/beta")
+            .to_string_lossy()
+            .into_owned();
+        let missing = repo
+            .path()
+            .join("skills/missing")
+            .to_string_lossy()
+            .into_owned();
+
+        let target = tempfile::tempdir().unwrap();
+        let args = local_install_args(vec![alpha, missing.clone(), beta], target.path(), false);
+        let err = run(args).await.unwrap_err();
+        assert_eq!(err.to_string(), format!("Failed to install: {}", missing));
+        assert!(target.path().join("alpha/SKILL.md").is_file());
+        assert!(target.path().join("beta/SKILL.md").is_file());
+
+        // Both installs are recorded in the skills directory's lockfile
+        let lock = manifest::load_lock(target.path()).unwrap();
+        let names: Vec<_> = lock.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta"]);
+        assert_eq!(
+            lock[0].checksum,
+            Some(checksum::compute(&target.path().join("alpha")).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_install_batch_fail_fast() {
+        let repo = monorepo_fixture();
+        let alpha = repo
+            .path()
+            .join("skills/alpha")
+            .to_string_lossy()
+            .into_owned();
+        let missing = repo
+            .path()
+            .join("skills/missing")
+            .to_string_lossy()
+            .into_owned();
+
+        let target = tempfile::tempdir().unwrap();
+        let args = local_install_args(vec![missing, alpha], target.path(), true);
+        assert!(run(args).await.is_err());
+        assert!(!target.path().join("alpha").exists());
+    }
+
+    fn locked_deploy() -> manifest::LockedSkill {
+        manifest::LockedSkill {
+            name: "acme/deploy".to_string(),
+            version: "1.2.0".to_string(),
+            source: "registry".to_string(),
+            commit: Some("abc123".to_string()),
+            checksum: Some("sha256:aaaa".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_check_frozen_matches() {
+        let locked = locked_deploy();
+        assert!(check_frozen(&locked, "1.2.0", "abc123", Some("sha256:aaaa")).is_ok());
+        // However the registry spells the same digest
+        assert!(check_frozen(&locked, "1.2.0", "abc123", Some("AAAA")).is_ok());
+        assert!(check_frozen(&locked, "1.2.0", "abc123", Some("sha256:AAAA")).is_ok());
+        // A registry without checksums can't contradict the lockfile
+        assert!(check_frozen(&locked, "1.2.0", "abc123", None).is_ok());
+    }
+
+    #[test]
+    fn test_check_frozen_mismatch() {
+        let locked = locked_deploy();
+
+        let err = check_frozen(&locked, "1.2.0", "fff999", Some("sha256:aaaa")).unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("resolves acme/deploy differently"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("commit fff999 (locked abc123)"),
+            "{}",
+            message
+        );
+        assert!(!message.contains("checksum"), "{}", message);
+
+        let err = check_frozen(&locked, "1.2.0", "abc123", Some("sha256:bbbb")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("checksum sha256:bbbb (locked sha256:aaaa)")
+        );
+        assert!(err.to_string().contains("paks lock"));
+    }
+
+    #[test]
+    fn test_frozen_entry() {
+        let skills = tempfile::tempdir().unwrap();
+        manifest::write_lock(skills.path(), vec![locked_deploy()]).unwrap();
+
+        let entry = frozen_entry(&SkillRef::parse("acme/deploy").unwrap(), skills.path()).unwrap();
+        assert_eq!(entry, locked_deploy());
+        assert!(
+            frozen_entry(
+                &SkillRef::parse("acme/deploy@1.2.0").unwrap(),
+                skills.path()
+            )
+            .is_ok()
+        );
+
+        let err = frozen_entry(
+            &SkillRef::parse("acme/deploy@2.0.0").unwrap(),
+            skills.path(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("pins 1.2.0"), "{}", err);
+        let err = frozen_entry(&SkillRef::parse("acme/lint").unwrap(), skills.path()).unwrap_err();
+        assert!(err.to_string().contains("acme/lint is not in"), "{}", err);
+    }
+
+    #[test]
+    fn test_frozen_sources() {
+        let err = frozen_sources(&[], &[]).unwrap_err();
+        assert!(err.to_string().contains("paks lock"), "{}", err);
+
+        let local = manifest::LockedSkill {
+            name: "notes".to_string(),
+            version: "0.1.0".to_string(),
+            source: "local".to_string(),
+            commit: None,
+            checksum: None,
+        };
+        let lock = [locked_deploy(), local];
+        assert_eq!(frozen_sources(&[], &lock).unwrap(), ["acme/deploy"]);
+        assert_eq!(
+            frozen_sources(&["acme/deploy@1.2.0".to_string()], &lock).unwrap(),
+            ["acme/deploy@1.2.0"]
+        );
     }
 
     #[test]
@@ -792,6 +2944,53 @@ mod tests {
         matches!(detect_source_type("/absolute/path"), SourceType::Local(_));
     }
 
+    #[test]
+    fn test_parse_gist_url() {
+        assert_eq!(
+            parse_gist_url("https://gist.github.com/alice/0123abcd"),
+            Some(("0123abcd".to_string(), None))
+        );
+        assert_eq!(
+            parse_gist_url("https://gist.github.com/0123abcd"),
+            Some(("0123abcd".to_string(), None))
+        );
+        assert_eq!(
+            parse_gist_url("https://gist.github.com/0123abcd.git"),
+            Some(("0123abcd".to_string(), None))
+        );
+        assert_eq!(
+            parse_gist_url("https://gist.github.com/alice/0123abcd/beef42/"),
+            Some(("0123abcd".to_string(), Some("beef42".to_string())))
+        );
+        assert_eq!(
+            parse_gist_url("https://gist.github.com/alice/0123abcd#file-skill-md"),
+            Some(("0123abcd".to_string(), None))
+        );
+
+        // Not gists
+        assert_eq!(parse_gist_url("https://gist.github.com/alice"), None);
+        assert_eq!(parse_gist_url("https://github.com/alice/0123abcd"), None);
+        assert_eq!(
+            parse_gist_url("https://gist.github.com/alice/0123abcd/raw/SKILL.md"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_detect_gist_source() {
+        match detect_source_type("https://gist.github.com/alice/0123abcd") {
+            SourceType::Gist { id, revision } => {
+                assert_eq!(id, "0123abcd");
+                assert_eq!(revision, None);
+            }
+            other => panic!("expected gist source, got {:?}", other),
+        }
+        assert_eq!(
+            gist_clone_url("0123abcd"),
+            "https://gist.github.com/0123abcd.git"
+        );
+    }
+
     #[test]
     fn test_parse_git_url() {
         // Fragment syntax with ref and path
@@ -1,25 +1,55 @@
-//! List command - list installed skills
+//! List command - list installed skills, or an owner's paks in the registry
 
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{Context, Result, bail};
+use paks_api::pagination::MAX_PAGINATED_RESULTS;
+use paks_api::{ApiError, Pak, PaksClient, SearchPaksQuery};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
+use super::core::client::registry_client;
 use super::core::config::Config;
+use super::core::offline;
+use super::core::output::OutputFormat;
 use super::core::skill::Skill;
+use super::core::token;
 
-#[derive(Clone, Copy)]
-pub enum OutputFormat {
-    Table,
-    Json,
-    Yaml,
-}
+/// Page size when fetching an owner's paks from the registry
+const REMOTE_PAGE_SIZE: u32 = 100;
 
 pub struct ListArgs {
     pub agent: Option<String>,
     pub all: bool,
     pub format: OutputFormat,
+    /// List paks in the registry instead: `Some(None)` for the logged-in
+    /// user's, `Some(Some(owner))` for another owner's
+    pub remote: Option<Option<String>>,
+}
+
+/// A published pak as listed by `--remote`
+#[derive(Debug, Serialize)]
+struct RemotePak {
+    uri: String,
+    description: Option<String>,
+    visibility: String,
+    /// Downloads in the registry's current time window
+    downloads: i64,
+    total_downloads: i64,
+}
+
+impl From<Pak> for RemotePak {
+    fn from(pak: Pak) -> Self {
+        Self {
+            uri: pak.uri,
+            description: pak.description,
+            visibility: pak.visibility.to_string(),
+            downloads: pak.download_count,
+            total_downloads: pak.total_downloads,
+        }
+    }
 }
 
 /// Skill info for listing
+#[derive(Debug, PartialEq)]
 struct SkillInfo {
     name: String,
     version: String,
@@ -29,6 +59,10 @@ struct SkillInfo {
 pub async fn run(args: ListArgs) -> Result<()> {
     let config = Config::load()?;
 
+    if let Some(owner) = args.remote {
+        return run_remote(&config, owner, args.format).await;
+    }
+
     if args.all {
         println!("Installed skills:\n");
         for (id, agent_config) in &config.agents {
@@ -89,33 +123,164 @@ pub async fn run(args: ListArgs) -> Result<()> {
     Ok(())
 }
 
-/// List all skills in a directory
-fn list_skills_in_dir(dir: &Path) -> Vec<SkillInfo> {
-    let mut skills = Vec::new();
+/// List the paks `owner` (or the logged-in user) has published
+async fn run_remote(config: &Config, owner: Option<String>, format: OutputFormat) -> Result<()> {
+    offline::ensure_online("Listing remote paks")?;
+    let (owner, paks) = match owner {
+        Some(owner) => {
+            let client = registry_client(config)?;
+            let paks = owner_paks(&client, &owner)
+                .await
+                .with_context(|| format!("Failed to list paks published by {}", owner))?;
+            (owner, paks)
+        }
+        None => {
+            // Your own paks, the same listing as `paks mine`
+            let client = token::authenticated_client(config).await?;
+            let user = match client.get_current_user().await {
+                Ok(user) => user,
+                Err(ApiError::AuthRequired | ApiError::InvalidToken) => {
+                    bail!("Not authenticated. Run 'paks login' first.")
+                }
+                Err(e) => return Err(e).context("Failed to fetch the current user"),
+            };
+            let mut paks: Vec<RemotePak> = client
+                .list_my_paks()
+                .await
+                .context("Failed to list your paks")?
+                .into_iter()
+                .map(|p| RemotePak::from(p.pak))
+                .collect();
+            paks.sort_by(|a, b| a.uri.cmp(&b.uri));
+            (user.username, paks)
+        }
+    };
+
+    print_remote_paks(&owner, &paks, format)
+}
 
-    if !dir.exists() {
-        return skills;
+/// Every pak published by `owner`, sorted by URI
+///
+/// Stops after [`MAX_PAGINATED_RESULTS`], in case the registry keeps
+/// returning full pages.
+async fn owner_paks(client: &PaksClient, owner: &str) -> Result<Vec<RemotePak>, ApiError> {
+    let mut paks: Vec<Pak> = Vec::new();
+    while paks.len() < MAX_PAGINATED_RESULTS {
+        let page = client
+            .search_paks(SearchPaksQuery {
+                owner: Some(owner.to_string()),
+                limit: Some(REMOTE_PAGE_SIZE),
+                offset: Some(paks.len() as u32),
+                ..Default::default()
+            })
+            .await?;
+        let last = page.len() < REMOTE_PAGE_SIZE as usize;
+        paks.extend(page);
+        if last {
+            break;
+        }
     }
+    paks.truncate(MAX_PAGINATED_RESULTS);
+    paks.retain(|p| p.owner_name.eq_ignore_ascii_case(owner));
+    paks.sort_by(|a, b| a.uri.cmp(&b.uri));
+    Ok(paks.into_iter().map(RemotePak::from).collect())
+}
 
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir()
-                && let Ok(skill) = Skill::load(&path)
-            {
-                skills.push(SkillInfo {
-                    name: skill.name().to_string(),
-                    version: skill.version().to_string(),
-                    description: skill.frontmatter.description.clone(),
-                });
+fn print_remote_paks(owner: &str, paks: &[RemotePak], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            if paks.is_empty() {
+                println!("{} has not published any paks.", owner);
+                return Ok(());
+            }
+            println!("Paks published by {}:\n", owner);
+
+            let uri_width = paks.iter().map(|p| p.uri.len()).max().unwrap_or(3).max(3);
+            println!("  {:<uri_width$}  {:>9}  DESCRIPTION", "PAK", "DOWNLOADS");
+            println!(
+                "  {:<uri_width$}  {:>9}  {}",
+                "─".repeat(uri_width),
+                "─".repeat(9),
+                "─".repeat(40)
+            );
+            for pak in paks {
+                let desc = pak.description.as_deref().unwrap_or("");
+                let desc = if desc.chars().count() > 50 {
+                    format!("{}...", desc.chars().take(47).collect::<String>())
+                } else {
+                    desc.to_string()
+                };
+                println!(
+                    "  {:<uri_width$}  {:>9}  {}",
+                    pak.uri, pak.total_downloads, desc
+                );
             }
         }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(paks)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml_ng::to_string(paks)?),
     }
+    Ok(())
+}
+
+/// Most threads used to load skills from one directory
+const MAX_LOAD_THREADS: usize = 8;
+
+/// List all skills in a directory, sorted by name
+///
+/// Each SKILL.md is read and parsed on a small pool of threads, since
+/// directories with many skills are otherwise slow to list.
+fn list_skills_in_dir(dir: &Path) -> Vec<SkillInfo> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_LOAD_THREADS);
+    let chunk_size = dirs.len().div_ceil(threads).max(1);
+    let mut skills: Vec<SkillInfo> = std::thread::scope(|scope| {
+        let handles: Vec<_> = dirs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(|| {
+                    chunk
+                        .iter()
+                        .filter_map(|d| load_skill_info(d))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            // A panic while loading is a bug; surface it rather than
+            // silently listing fewer skills
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect()
+    });
 
     skills.sort_by(|a, b| a.name.cmp(&b.name));
     skills
 }
 
+/// Listing details of the skill in `dir`, if it holds a valid one
+fn load_skill_info(dir: &Path) -> Option<SkillInfo> {
+    let skill = Skill::load(dir).ok()?;
+    Some(SkillInfo {
+        name: skill.name().to_string(),
+        version: skill.version().to_string(),
+        description: skill.frontmatter.description.clone(),
+    })
+}
+
 /// Print skills in the specified format
 fn print_skills(skills: &[SkillInfo], format: OutputFormat) {
     match format {
@@ -191,3 +356,62 @@ fn print_skills(skills: &[SkillInfo], format: OutputFormat) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paks_api::test_support::pak_json;
+
+    #[test]
+    fn test_list_skills_matches_sequential_load() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..100 {
+            let skill_dir = dir.path().join(format!("dir-{}", i));
+            std::fs::create_dir_all(&skill_dir).unwrap();
+            std::fs::write(
+                skill_dir.join("SKILL.md"),
+                format!(
+                    "---\nname: skill-{:03}\ndescription: Skill number {}\n---\n",
+                    99 - i,
+                    i
+                ),
+            )
+            .unwrap();
+        }
+        // Directories without a valid skill are left out
+        std::fs::create_dir_all(dir.path().join("empty")).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let mut sequential: Vec<SkillInfo> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .flatten()
+            .filter_map(|entry| load_skill_info(&entry.path()))
+            .collect();
+        sequential.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let skills = list_skills_in_dir(dir.path());
+        assert_eq!(skills.len(), 100);
+        assert_eq!(skills[0].name, "skill-000");
+        assert_eq!(skills, sequential);
+        assert!(list_skills_in_dir(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_remote_pak_fields() {
+        let mut pak = pak_json("alice", "my-skill");
+        pak["description"] = "Does things".into();
+        pak["download_count"] = 12.into();
+        pak["total_downloads"] = 340.into();
+        let pak: Pak = serde_json::from_value(pak).unwrap();
+        assert_eq!(
+            serde_json::to_value(RemotePak::from(pak)).unwrap(),
+            serde_json::json!({
+                "uri": "alice/my-skill",
+                "description": "Does things",
+                "visibility": "PUBLIC",
+                "downloads": 12,
+                "total_downloads": 340,
+            })
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Lock command - regenerate a skills directory's paks.lock from what's installed
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::core::checksum;
+use super::core::client::registry_client;
+use super::core::config::Config;
+use super::core::manifest::{self, LockedSkill};
+use super::core::offline;
+use super::core::skill::Skill;
+use super::core::update;
+use super::install::install_target;
+
+pub struct LockArgs {
+    pub agent: Option<String>,
+    pub dir: Option<String>,
+}
+
+pub async fn run(args: LockArgs) -> Result<()> {
+    let (skills_dir, _) = install_target(args.dir.as_deref(), args.agent.as_deref())?;
+    let previous = manifest::load_lock(&skills_dir)?;
+    let mut skills = lock_entries(&skills_dir, &previous)?;
+
+    // Pin registry skills the previous lockfile had no commit for
+    if !offline::is_offline() && skills.iter().any(needs_commit) {
+        let config = Config::load()?;
+        let client = registry_client(&config)?;
+        for locked in skills.iter_mut().filter(|s| needs_commit(s)) {
+            let uri = format!("{}@{}", locked.name, locked.version);
+            match client.get_pak_install(&uri).await {
+                Ok(info) => {
+                    locked.commit = Some(info.version.commit_hash);
+                    locked.checksum = info.version.checksum;
+                }
+                Err(e) => eprintln!("⚠ Warning: Couldn't resolve the commit of {}: {}", uri, e),
+            }
+        }
+    }
+
+    let count = skills.len();
+    manifest::write_lock(&skills_dir, skills)?;
+    println!(
+        "✓ Wrote {} ({} skill{})",
+        skills_dir.join(manifest::LOCK_FILE).display(),
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+fn needs_commit(locked: &LockedSkill) -> bool {
+    locked.source == "registry" && locked.commit.is_none()
+}
+
+/// Lockfile entries for the skills installed in `skills_dir`
+///
+/// Source, commit and checksum carry over from the `previous` entry of the
+/// same version; otherwise registry skills are recognised by their
+/// `owner--name` directory and their commit and checksum are left to be
+/// resolved. Registry skills are only ever locked to the registry's
+/// checksum, since the installed files may have changed since (e.g. by a
+/// postinstall hook); other skills get the checksum of their files.
+fn lock_entries(skills_dir: &Path, previous: &[LockedSkill]) -> Result<Vec<LockedSkill>> {
+    if !skills_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut skills = Vec::new();
+    for entry in std::fs::read_dir(skills_dir)
+        .with_context(|| format!("Failed to read {}", skills_dir.display()))?
+        .flatten()
+    {
+        let dir = entry.path();
+        let dir_name = entry.file_name().to_string_lossy().into_owned();
+        if dir_name.starts_with('.') || !dir.join("SKILL.md").is_file() {
+            continue;
+        }
+        let Ok(skill) = Skill::load(&dir) else {
+            continue;
+        };
+
+        let registry_name = update::registry_name(&dir_name);
+        let name = registry_name
+            .clone()
+            .unwrap_or_else(|| skill.name().to_string());
+        let version = skill.version().to_string();
+        let earlier = previous.iter().find(|s| s.name == name);
+        let same_version = earlier.filter(|earlier| earlier.version == version);
+        let (source, commit) = match (same_version, earlier) {
+            (Some(earlier), _) => (earlier.source.clone(), earlier.commit.clone()),
+            _ if registry_name.is_some() => ("registry".to_string(), None),
+            (None, Some(earlier)) => (earlier.source.clone(), None),
+            (None, None) => ("local".to_string(), None),
+        };
+        let checksum = if source == "registry" {
+            same_version.and_then(|earlier| earlier.checksum.clone())
+        } else {
+            checksum::compute(&dir).ok()
+        };
+
+        skills.push(LockedSkill {
+            name,
+            version,
+            source,
+            commit,
+            checksum,
+        });
+    }
+    Ok(skills)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::install_fake_skill;
+    use tempfile::tempdir;
+
+    fn locked(name: &str, version: &str, source: &str, commit: Option<&str>) -> LockedSkill {
+        LockedSkill {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: source.to_string(),
+            commit: commit.map(str::to_string),
+            checksum: None,
+        }
+    }
+
+    #[test]
+    fn test_lock_entries_from_installed_skills() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "acme--deploy", "1.2.0");
+        install_fake_skill(skills.path(), "acme--lint", "2.0.0");
+        install_fake_skill(skills.path(), "notes", "0.1.0");
+        install_fake_skill(skills.path(), ".hidden", "0.1.0");
+        std::fs::create_dir(skills.path().join("empty")).unwrap();
+
+        let previous = [
+            LockedSkill {
+                checksum: Some("sha256:abcd".to_string()),
+                ..locked("acme/deploy", "1.2.0", "registry", Some("abc123"))
+            },
+            // Upgraded since it was locked, so its commit is stale
+            locked("acme/lint", "1.0.0", "registry", Some("def456")),
+            locked("notes", "0.0.1", "https://github.com/acme/notes.git", None),
+            locked("acme/removed", "1.0.0", "registry", Some("0123abcd")),
+        ];
+        let mut entries = lock_entries(skills.path(), &previous).unwrap();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.version.as_str(),
+                    s.source.as_str(),
+                    s.commit.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("acme/deploy", "1.2.0", "registry", Some("abc123")),
+                ("acme/lint", "2.0.0", "registry", None),
+                ("notes", "0.1.0", "https://github.com/acme/notes.git", None),
+            ]
+        );
+        // Registry skills keep the registry's checksum, never the files'
+        assert_eq!(entries[0].checksum.as_deref(), Some("sha256:abcd"));
+        assert_eq!(entries[1].checksum, None);
+        assert_eq!(
+            entries[2].checksum,
+            Some(checksum::compute(&skills.path().join("notes")).unwrap())
+        );
+        assert!(needs_commit(&entries[1]));
+        assert!(!needs_commit(&entries[2]));
+    }
+}
@@ -2,20 +2,49 @@
 
 use anyhow::{Result, bail};
 use dialoguer::{Confirm, Input};
-use paks_api::PaksClient;
+use paks_api::UserInfo;
+use std::io::Read;
 
+use super::core::client;
 use super::core::config::Config;
+use super::core::keychain::{OsKeychain, SecretStore};
+use super::core::token::TokenVerifier;
 
 pub struct LoginArgs {
     pub token: Option<String>,
+    pub token_stdin: bool,
+    pub no_verify: bool,
+    /// Registry name or URL to log in to; a new URL is added to the config
+    pub registry: Option<String>,
 }
 
 pub async fn run_login(args: LoginArgs) -> Result<()> {
-    // Check if already logged in
+    // Check if already logged in (skipped for --token-stdin, which must stay
+    // non-interactive)
     let mut config = Config::load()?;
-    if let Some(existing_token) = config.get_auth_token() {
+    let migrated = config.migrate_tokens_to_keychain(&OsKeychain);
+    if migrated > 0 {
+        config.save()?;
+        println!(
+            "Moved {} token(s) from config.toml to the OS keychain",
+            migrated
+        );
+    }
+
+    let client = match &args.registry {
+        Some(registry) => {
+            let (name, url) = config.select_registry(registry)?;
+            println!("Using registry '{}' ({})", name, url);
+            client::builder(&url).build()?
+        }
+        None => client::builder(config.resolve_registry_url(None)?).build()?,
+    };
+
+    if !args.token_stdin
+        && let Ok(Some(existing_token)) = config.get_stored_auth_token(&OsKeychain)
+    {
         // Verify existing token
-        let mut client = PaksClient::new()?;
+        let mut client = client.clone();
         client.set_token(existing_token);
 
         if let Ok(user) = client.get_current_user().await {
@@ -32,44 +61,78 @@ pub async fn run_login(args: LoginArgs) -> Result<()> {
         }
     }
 
-    // Get token - either from args or prompt
+    // Get token - from args, stdin, or prompt
     let token = if let Some(t) = args.token {
         t
+    } else if args.token_stdin {
+        read_token(std::io::stdin().lock())?
     } else {
         println!("Get your API token from: https://stakpak.dev/settings/tokens");
         println!();
         Input::new().with_prompt("API Token").interact_text()?
     };
 
-    if token.trim().is_empty() {
-        bail!("Token cannot be empty");
+    let user = store_token(&mut config, &OsKeychain, token, !args.no_verify, &client).await?;
+    config.save()?;
+
+    println!();
+    match user {
+        Some(user) => println!("✓ Logged in as: {}", user.username),
+        None => {
+            println!("✓ Token saved");
+            eprintln!("⚠ Warning: token was not verified (--no-verify)");
+        }
     }
 
-    // Validate token
-    print!("Validating token... ");
-    let mut client = PaksClient::new()?;
-    client.set_token(&token);
+    Ok(())
+}
 
-    let user = client.get_current_user().await.map_err(|e| {
-        println!("✗");
-        anyhow::anyhow!("Invalid token: {}", e)
-    })?;
-    println!("✓");
+/// Validate `token` and set it on `config`, keeping the secret in `store`
+///
+/// With `verify` the token is checked against the registry first, its expiry
+/// is recorded and the owning user is returned; otherwise it is stored as-is.
+async fn store_token(
+    config: &mut Config,
+    store: &impl SecretStore,
+    token: String,
+    verify: bool,
+    verifier: &impl TokenVerifier,
+) -> Result<Option<UserInfo>> {
+    if token.trim().is_empty() {
+        bail!("Token cannot be empty");
+    }
 
-    // Save token to config
-    config.set_auth_token(token);
-    config.save()?;
+    let verified = if verify {
+        print!("Validating token... ");
+        let verified = verifier.verify(&token).await.map_err(|e| {
+            println!("✗");
+            anyhow::anyhow!("Invalid token: {}", e)
+        })?;
+        println!("✓");
+        Some(verified)
+    } else {
+        None
+    };
 
-    println!();
-    println!("✓ Logged in as: {}", user.username);
+    let expires_at = verified.as_ref().and_then(|v| v.expires_at);
+    config.set_auth_token(store, token, expires_at)?;
+    Ok(verified.map(|v| v.user))
+}
 
-    Ok(())
+/// Read a token from `reader`, dropping the trailing newline
+fn read_token(mut reader: impl Read) -> Result<String> {
+    let mut token = String::new();
+    reader.read_to_string(&mut token)?;
+    Ok(token.trim_end_matches(['\n', '\r']).to_string())
 }
 
 pub async fn run_logout() -> Result<()> {
     let mut config = Config::load()?;
 
-    if config.get_auth_token().is_none() {
+    if config
+        .get_stored_auth_token(&OsKeychain)
+        .is_ok_and(|token| token.is_none())
+    {
         println!("Not logged in.");
         return Ok(());
     }
@@ -86,10 +149,129 @@ pub async fn run_logout() -> Result<()> {
     }
 
     // Clear token
-    config.clear_auth_token();
+    config.clear_auth_token(&OsKeychain);
     config.save()?;
 
     println!("✓ Logged out successfully.");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::core::keychain::MemoryStore;
+    use paks_api::{ApiError, VerifyTokenResponse};
+    use std::cell::Cell;
+    use std::io::Cursor;
+
+    /// Verifier that counts calls and accepts every token
+    #[derive(Default)]
+    struct CountingVerifier {
+        calls: Cell<usize>,
+    }
+
+    impl TokenVerifier for CountingVerifier {
+        async fn verify(&self, _token: &str) -> Result<VerifyTokenResponse, ApiError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(VerifyTokenResponse {
+                valid: true,
+                user: UserInfo {
+                    id: "1".to_string(),
+                    username: "alice".to_string(),
+                    first_name: None,
+                    last_name: None,
+                    email: "alice@example.com".to_string(),
+                    profile_img_url: None,
+                    job_role: None,
+                    company: None,
+                },
+                expires_at: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_verify_stores_token_without_network() {
+        let mut config = Config::default();
+        let store = MemoryStore::default();
+        let verifier = CountingVerifier::default();
+
+        let user = store_token(
+            &mut config,
+            &store,
+            "pak_offline".to_string(),
+            false,
+            &verifier,
+        )
+        .await
+        .unwrap();
+
+        assert!(user.is_none());
+        assert_eq!(verifier.calls.get(), 0);
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_offline")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_checks_token() {
+        let mut config = Config::default();
+        let store = MemoryStore::default();
+        let verifier = CountingVerifier::default();
+
+        let user = store_token(
+            &mut config,
+            &store,
+            "pak_online".to_string(),
+            true,
+            &verifier,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(user.map(|u| u.username).as_deref(), Some("alice"));
+        assert_eq!(verifier.calls.get(), 1);
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_online")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_to_new_registry_url() {
+        let mut config = Config::default();
+        let store = MemoryStore::default();
+        let verifier = CountingVerifier::default();
+
+        let (name, _) = config.select_registry("https://paks.acme.dev").unwrap();
+        store_token(&mut config, &store, "pak_acme".to_string(), true, &verifier)
+            .await
+            .unwrap();
+
+        assert_eq!(verifier.calls.get(), 1);
+        assert!(config.registries[&name].token_in_keychain);
+        assert_eq!(store.secrets.borrow()[&name], "pak_acme");
+        assert_eq!(
+            config.get_auth_token(&store).unwrap().as_deref(),
+            Some("pak_acme")
+        );
+        assert!(!config.registries.contains_key("stakpak"));
+    }
+
+    #[test]
+    fn test_read_token_from_stdin() {
+        let token = read_token(Cursor::new("pak_secret123\n")).unwrap();
+        assert_eq!(token, "pak_secret123");
+
+        let token = read_token(Cursor::new("pak_secret123\r\n")).unwrap();
+        assert_eq!(token, "pak_secret123");
+    }
+
+    #[test]
+    fn test_read_token_empty_stdin() {
+        let token = read_token(Cursor::new("\n")).unwrap();
+        assert!(token.trim().is_empty());
+    }
+}
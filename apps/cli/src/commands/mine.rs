@@ -0,0 +1,126 @@
+//! Mine command - list paks published by the logged-in user
+
+use anyhow::{Context, Result};
+use paks_api::{ApiError, PakWithLatestVersion};
+
+use super::core::config::Config;
+use super::core::output::OutputFormat;
+use super::core::token;
+
+pub struct MineArgs {
+    pub format: OutputFormat,
+}
+
+pub async fn run(args: MineArgs) -> Result<()> {
+    let config = Config::load()?;
+    let client = token::authenticated_client(&config).await?;
+
+    let mut paks = match client.list_my_paks().await {
+        Ok(paks) => paks,
+        Err(ApiError::AuthRequired | ApiError::InvalidToken) => {
+            anyhow::bail!("Not authenticated. Run 'paks login' first.")
+        }
+        Err(e) => return Err(e).context("Failed to list your paks"),
+    };
+    paks.sort_by(|a, b| a.pak.uri.cmp(&b.pak.uri));
+
+    print_paks(&paks, args.format)
+}
+
+/// Latest version and its review status, or placeholders when unpublished
+fn latest_columns(pak: &PakWithLatestVersion) -> (String, String) {
+    match &pak.latest_version {
+        Some(version) => (version.version.clone(), version.status.to_string()),
+        None => ("-".to_string(), "-".to_string()),
+    }
+}
+
+fn print_paks(paks: &[PakWithLatestVersion], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            if paks.is_empty() {
+                println!("You have not published any paks yet.");
+                return Ok(());
+            }
+
+            let uri_width = paks
+                .iter()
+                .map(|p| p.pak.uri.len())
+                .max()
+                .unwrap_or(3)
+                .max(3);
+
+            println!(
+                "  {:<uri_width$}  {:<10}  {:<10}  STATUS",
+                "PAK", "VISIBILITY", "LATEST"
+            );
+            println!(
+                "  {:<uri_width$}  {:<10}  {:<10}  {}",
+                "─".repeat(uri_width),
+                "─".repeat(10),
+                "─".repeat(10),
+                "─".repeat(10)
+            );
+
+            for pak in paks {
+                let (version, status) = latest_columns(pak);
+                println!(
+                    "  {:<uri_width$}  {:<10}  {:<10}  {}",
+                    pak.pak.uri,
+                    pak.pak.visibility.to_string(),
+                    version,
+                    status
+                );
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(paks)?);
+        }
+        OutputFormat::Yaml => {
+            print!("{}", serde_yaml_ng::to_string(paks)?);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paks_api::test_support::pak_json;
+
+    fn pak(latest_version: Option<serde_json::Value>) -> PakWithLatestVersion {
+        let mut pak = pak_json("alice", "my-skill");
+        pak["visibility"] = "PRIVATE".into();
+        pak["latest_version"] = latest_version.into();
+        serde_json::from_value(pak).unwrap()
+    }
+
+    #[test]
+    fn test_latest_columns() {
+        let published = pak(Some(serde_json::json!({
+            "id": "00000000-0000-0000-0000-000000000002",
+            "version": "1.2.0",
+            "git_tag": "v1.2.0",
+            "checksum": "abc",
+            "size_bytes": null,
+            "manifest": "",
+            "status": "SUBMITTED",
+            "downloads": 0,
+            "usages": 0,
+            "published_at": "2025-01-01T00:00:00Z",
+            "created_at": "2025-01-01T00:00:00Z",
+            "updated_at": "2025-01-01T00:00:00Z",
+        })));
+        assert_eq!(
+            latest_columns(&published),
+            ("1.2.0".to_string(), "SUBMITTED".to_string())
+        );
+
+        let unpublished = pak(None);
+        assert_eq!(
+            latest_columns(&unpublished),
+            ("-".to_string(), "-".to_string())
+        );
+    }
+}
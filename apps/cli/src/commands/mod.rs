@@ -1,13 +1,32 @@
 //! Command implementations for paks CLI
 
 pub mod agent;
+pub mod cache;
+pub mod completions;
 pub mod core;
 pub mod create;
+pub mod deprecate;
+pub mod diff;
+pub mod doctor;
+pub mod external;
 pub mod info;
+pub mod init;
 pub mod install;
 pub mod list;
+pub mod lock;
 pub mod login;
+pub mod mine;
+pub mod outdated;
+pub mod ping;
 pub mod publish;
 pub mod remove;
+pub mod rename;
 pub mod search;
+#[cfg(test)]
+pub mod test_support;
+pub mod tree;
+pub mod update;
 pub mod validate;
+pub mod version;
+pub mod whoami;
+pub mod yank;
@@ -0,0 +1,220 @@
+//! Outdated command - report available upgrades without installing anything
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::core::client::registry_client;
+use super::core::config::Config;
+use super::core::fanout::FanOut;
+use super::core::manifest::{self, DependencySpec};
+use super::core::output::OutputFormat;
+use super::core::skill::Skill;
+use super::core::update::{self, UpdateDecision, UpdatePlan};
+use super::install::install_target;
+use super::update::{installed_registry_skills, latest_versions};
+
+pub struct OutdatedArgs {
+    pub agent: Option<String>,
+    pub format: OutputFormat,
+    /// Registry requests in flight at once
+    pub jobs: Option<usize>,
+}
+
+/// One installed skill and how it compares to the registry
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct OutdatedEntry {
+    name: String,
+    current: String,
+    /// Latest registry version; `None` for skills the registry doesn't serve
+    latest: Option<String>,
+    /// `registry`, `git` or `local`
+    source: &'static str,
+    outdated: bool,
+}
+
+pub async fn run(args: OutdatedArgs) -> Result<()> {
+    let config = Config::load()?;
+    let (skills_dir, _) = install_target(None, args.agent.as_deref())?;
+
+    let dependencies = match manifest::find_project_root(&std::env::current_dir()?) {
+        Some(root) => manifest::load_dependencies(&root)?,
+        None => BTreeMap::new(),
+    };
+
+    let installed = installed_registry_skills(&skills_dir, &dependencies);
+    let plan = if installed.is_empty() {
+        Vec::new()
+    } else {
+        let client = registry_client(&config)?;
+        let fanout = FanOut::from_config(&config, args.jobs);
+        let registry = latest_versions(&client, &installed, &fanout).await?;
+        update::plan_updates(&installed, &registry)
+    };
+
+    let mut entries: Vec<OutdatedEntry> = plan.iter().map(registry_entry).collect();
+    entries.extend(non_registry_skills(&skills_dir, &dependencies));
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if entries.is_empty() && matches!(args.format, OutputFormat::Table) {
+        println!("No skills installed in {}", skills_dir.display());
+        return Ok(());
+    }
+    print_entries(&entries, args.format)
+}
+
+fn registry_entry(plan: &UpdatePlan) -> OutdatedEntry {
+    let (latest, outdated) = match &plan.decision {
+        UpdateDecision::Upgrade { available } | UpdateDecision::Pinned { available } => {
+            (Some(available.clone()), true)
+        }
+        UpdateDecision::Unchanged => (Some(plan.installed.clone()), false),
+        UpdateDecision::NotInRegistry => (None, false),
+    };
+    OutdatedEntry {
+        name: plan.name.clone(),
+        current: plan.installed.clone(),
+        latest,
+        source: "registry",
+        outdated,
+    }
+}
+
+/// Installed skills in `skills_dir` that didn't come from the registry
+///
+/// A skill whose `paks.toml` entry points at a git URL is reported as `git`;
+/// everything else is `local`.
+fn non_registry_skills(
+    skills_dir: &Path,
+    dependencies: &BTreeMap<String, DependencySpec>,
+) -> Vec<OutdatedEntry> {
+    let Ok(entries) = std::fs::read_dir(skills_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| update::registry_name(&entry.file_name().to_string_lossy()).is_none())
+        .filter_map(|entry| {
+            let skill = Skill::load(&entry.path()).ok()?;
+            let source = match dependencies.get(skill.name()) {
+                Some(spec) if is_git_source(&spec.source) => "git",
+                _ => "local",
+            };
+            Some(OutdatedEntry {
+                name: skill.name().to_string(),
+                current: skill.version().to_string(),
+                latest: None,
+                source,
+                outdated: false,
+            })
+        })
+        .collect()
+}
+
+fn is_git_source(source: &str) -> bool {
+    source.contains("://") || source.starts_with("git@") || source.ends_with(".git")
+}
+
+fn print_entries(entries: &[OutdatedEntry], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            let name_width = entries
+                .iter()
+                .map(|e| e.name.len())
+                .max()
+                .unwrap_or(4)
+                .max(4);
+            let current_width = entries
+                .iter()
+                .map(|e| e.current.len())
+                .max()
+                .unwrap_or(7)
+                .max(7);
+            let latest_width = entries
+                .iter()
+                .map(|e| e.latest.as_deref().map_or(1, str::len))
+                .max()
+                .unwrap_or(6)
+                .max(6);
+
+            println!(
+                "    {:<name_width$}  {:<current_width$}  {:<latest_width$}  SOURCE",
+                "NAME", "CURRENT", "LATEST"
+            );
+            for entry in entries {
+                let marker = if entry.outdated { "↑" } else { " " };
+                println!(
+                    "  {} {:<name_width$}  {:<current_width$}  {:<latest_width$}  {}",
+                    marker,
+                    entry.name,
+                    entry.current,
+                    entry.latest.as_deref().unwrap_or("-"),
+                    entry.source
+                );
+            }
+
+            let outdated = entries.iter().filter(|e| e.outdated).count();
+            println!();
+            if outdated == 0 {
+                println!("✓ All registry skills are up to date");
+            } else {
+                println!("{} skill(s) can be updated with `paks update`", outdated);
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(entries)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml_ng::to_string(entries)?),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::install_fake_skill;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_registry_entry_marks_outdated() {
+        let plan = UpdatePlan {
+            name: "acme/deploy".to_string(),
+            installed: "1.0.0".to_string(),
+            decision: UpdateDecision::Upgrade {
+                available: "1.2.0".to_string(),
+            },
+        };
+        let entry = registry_entry(&plan);
+        assert_eq!(entry.latest.as_deref(), Some("1.2.0"));
+        assert!(entry.outdated);
+
+        let plan = UpdatePlan {
+            decision: UpdateDecision::Unchanged,
+            ..plan
+        };
+        assert!(!registry_entry(&plan).outdated);
+    }
+
+    #[test]
+    fn test_non_registry_skills_report_source() {
+        let skills = tempdir().unwrap();
+        for name in ["from-git", "from-disk", "acme--deploy"] {
+            install_fake_skill(skills.path(), name, "0.1.0");
+        }
+        let dependencies = BTreeMap::from([(
+            "from-git".to_string(),
+            DependencySpec {
+                version: "^0.1.0".to_string(),
+                source: "https://github.com/acme/skills.git".to_string(),
+            },
+        )]);
+
+        let mut entries = non_registry_skills(skills.path(), &dependencies);
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        let sources: Vec<_> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.source))
+            .collect();
+        assert_eq!(sources, [("from-disk", "local"), ("from-git", "git")]);
+    }
+}
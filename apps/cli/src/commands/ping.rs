@@ -0,0 +1,40 @@
+//! Ping command - check that the registry is reachable
+
+use anyhow::{Result, bail};
+use paks_api::ApiError;
+use std::time::Instant;
+
+use super::core::client;
+use super::core::config::Config;
+
+pub struct PingArgs {
+    /// Registry name or URL to ping (default registry if unset)
+    pub registry: Option<String>,
+}
+
+pub async fn run(args: PingArgs) -> Result<()> {
+    let config = Config::load()?;
+    let url = config.resolve_registry_url(args.registry.as_deref())?;
+    let client = client::builder(&url).build()?;
+
+    println!("Registry: {}", client.base_url());
+    let started = Instant::now();
+    let result = client.health().await;
+    let elapsed = started.elapsed().as_millis();
+
+    match result {
+        Ok(true) => {
+            println!("✓ Reachable ({} ms)", elapsed);
+            Ok(())
+        }
+        Ok(false) => bail!(
+            "Registry responded but reports it is unhealthy ({} ms)",
+            elapsed
+        ),
+        Err(ApiError::Unreachable(_)) => bail!(
+            "Registry is unreachable.\n\
+             Hint: Check your network connection, proxy settings, or the registry URL."
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -1,14 +1,19 @@
 //! Publish command - publish a skill to the registry
 
-use anyhow::{Result, bail};
+use anyhow::{Result, anyhow, bail};
 use dialoguer::{Confirm, Input, Select};
-use paks_api::{PaksClient, PublishPakRequest};
-use std::io::{self, Write};
+use paks_api::{ApiError, PublishPakRequest};
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
 use std::path::Path;
 
 use super::core::config::Config;
-use super::core::git;
-use super::core::skill::Skill;
+use super::core::git::{self, ChangeEntry};
+use super::core::offline;
+use super::core::output::OutputFormat;
+use super::core::paksignore::IgnoreRules;
+use super::core::skill::{BumpLevel, Skill, parse_version};
+use super::core::token;
 
 pub struct PublishArgs {
     pub path: String,
@@ -16,10 +21,171 @@ pub struct PublishArgs {
     pub dry_run: bool,
     pub yes: bool,
     pub tag: Option<String>,
+    /// Publish even if the skill directory has uncommitted changes
+    pub allow_dirty: bool,
+    /// Branch to register instead of the checked-out one
+    pub branch: Option<String>,
+    /// Path of the skill within the repository, instead of detecting it
+    pub repo_path: Option<String>,
+    /// Output format for the `--dry-run` plan
+    pub format: OutputFormat,
+}
+
+/// What publishing will do with the tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PublishAction {
+    /// Create and push the tag, then register it
+    CreateTag,
+    /// Register a tag that already exists
+    UseExisting,
+}
+
+/// Everything a publish would do, as shown by `--dry-run`
+#[derive(Debug, Serialize)]
+struct PublishPlan {
+    name: String,
+    version: String,
+    repository: String,
+    branch: String,
+    path: String,
+    tag: String,
+    action: PublishAction,
+}
+
+impl PublishPlan {
+    fn render(&self) -> String {
+        let mut out = String::from("[Dry run] Would execute:\n");
+        out.push_str(&format!("  Repository: {}\n", self.repository));
+        out.push_str(&format!("  Branch: {}\n", self.branch));
+        out.push_str(&format!("  Path: {}\n", self.path));
+        out.push_str(&format!("  Tag: {}\n", self.tag));
+        out.push_str(match self.action {
+            PublishAction::CreateTag => {
+                "  Action: Create and push new tag, then register with registry\n"
+            }
+            PublishAction::UseExisting => "  Action: Register existing tag with registry\n",
+        });
+        out
+    }
+}
+
+/// The branch to publish from: `--branch`, or the checked-out branch
+fn resolve_branch(skill_path: &Path, branch: Option<&str>) -> Result<String> {
+    match branch {
+        Some(branch) => Ok(branch.to_string()),
+        None => git::get_current_branch(skill_path).map_err(|e| {
+            anyhow::anyhow!(
+                "{}\nHint: Pass --branch to publish from a detached HEAD.",
+                e
+            )
+        }),
+    }
+}
+
+/// Normalize a `--repo-path` to the form the registry expects (`.` for the
+/// repository root, no leading `./` or trailing `/`)
+fn normalize_repo_path(path: &str) -> Result<String> {
+    let trimmed = path.trim_start_matches("./").trim_end_matches('/');
+    if trimmed.starts_with('/') || trimmed.split('/').any(|part| part == "..") {
+        bail!(
+            "Invalid --repo-path '{}': must be relative to the repository root",
+            path
+        );
+    }
+    Ok(if trimmed.is_empty() {
+        ".".to_string()
+    } else {
+        trimmed.to_string()
+    })
+}
+
+/// Make sure `rev` (the tag, or `HEAD` for a tag still to be created) is on
+/// an explicitly given branch
+fn check_tag_on_branch(
+    skill_path: &Path,
+    rev: &str,
+    tag: &str,
+    branch: &str,
+    remote: &str,
+) -> Result<()> {
+    if !git::is_on_branch(skill_path, rev, branch, remote) {
+        bail!(
+            "Tag {} is not on branch {}.\n\
+             Hint: Check --branch, or fetch the branch so it can be checked.",
+            tag,
+            branch
+        );
+    }
+    Ok(())
+}
+
+/// What to do about the state of the working tree before publishing
+#[derive(Debug, PartialEq, Eq)]
+enum DirtyTree {
+    Clean,
+    /// Dirty, but `--allow-dirty` was given
+    Allowed,
+    /// Dirty; ask the user whether to continue
+    Prompt,
+}
+
+/// Decide how to handle uncommitted changes
+///
+/// Without `--allow-dirty`, a dirty tree is an error unless there is a user
+/// to ask, so CI never publishes uncommitted work by accident.
+fn check_dirty_tree(
+    changes: &[ChangeEntry],
+    allow_dirty: bool,
+    interactive: bool,
+) -> Result<DirtyTree> {
+    if changes.is_empty() {
+        Ok(DirtyTree::Clean)
+    } else if allow_dirty {
+        Ok(DirtyTree::Allowed)
+    } else if interactive {
+        Ok(DirtyTree::Prompt)
+    } else {
+        bail!(
+            "{} uncommitted changes in the skill directory.\n\
+             Hint: Commit them first, or pass --allow-dirty to publish anyway.",
+            changes.len()
+        );
+    }
+}
+
+/// Drop changes to files the skill's `.paksignore` excludes
+///
+/// `changes` are relative to the repository root and `pak_path` is where the
+/// skill sits in it. Untracked directories show up with a trailing `/`.
+fn without_ignored(
+    changes: Vec<ChangeEntry>,
+    rules: &IgnoreRules,
+    pak_path: &str,
+) -> Vec<ChangeEntry> {
+    changes
+        .into_iter()
+        .filter(|change| {
+            let rel = match pak_path {
+                "." => Some(change.path.as_str()),
+                _ => change
+                    .path
+                    .strip_prefix(pak_path)
+                    .and_then(|p| p.strip_prefix('/')),
+            };
+            match rel {
+                Some(rel) => {
+                    let dir = rel.strip_suffix('/');
+                    !rules.is_ignored(Path::new(dir.unwrap_or(rel)), dir.is_some())
+                }
+                None => true,
+            }
+        })
+        .collect()
 }
 
 /// Prompt for confirmation to continue with uncommitted changes
-fn prompt_continue_with_changes(changes: &[String]) -> Result<bool> {
+fn prompt_continue_with_changes(changes: &[ChangeEntry]) -> Result<bool> {
     println!("  ⚠ Uncommitted changes detected:");
     for change in changes.iter().take(10) {
         println!("    {}", change);
@@ -56,6 +222,42 @@ enum TagSelection {
     New(String),
 }
 
+/// Turn a failed publish into an actionable error, using the registry's
+/// error code when it sent a known one
+fn publish_error(error: ApiError, tag: &str, pak_path: &str) -> anyhow::Error {
+    let hint = match error.code() {
+        Some("TAG_NOT_FOUND") => format!(
+            "The registry can't find tag {} in the repository.\n\
+             Hint: Push it with 'git push origin {}' and publish again",
+            tag, tag
+        ),
+        Some("BRANCH_NOT_FOUND") => "The registry can't find the branch in the repository.\n\
+             Hint: Push the branch, or pass the right one with --branch"
+            .to_string(),
+        Some("VERSION_MISMATCH") => format!(
+            "The version in SKILL.md doesn't match the tag {}.\n\
+             Hint: Set metadata.version to {} in SKILL.md, commit, and tag again",
+            tag,
+            tag.trim_start_matches('v')
+        ),
+        Some("VERSION_EXISTS") => format!(
+            "Version {} has already been published.\n\
+             Hint: Run 'paks publish' again and pick a newer version",
+            tag.trim_start_matches('v')
+        ),
+        Some("SKILL_MD_NOT_FOUND") => format!(
+            "The registry found no SKILL.md at '{}' in tag {}.\n\
+             Hint: Check that SKILL.md is committed at that path",
+            pak_path, tag
+        ),
+        Some("INVALID_SKILL_MD") => "The registry rejected SKILL.md.\n\
+             Hint: Run 'paks validate' to see what's wrong"
+            .to_string(),
+        _ => return error.into(),
+    };
+    anyhow!("{}\n  ({})", hint, error)
+}
+
 /// Filter tags to only include valid semver tags (v*.*.*)
 fn filter_semver_tags(tags: &[String]) -> Vec<String> {
     tags.iter()
@@ -65,27 +267,34 @@ fn filter_semver_tags(tags: &[String]) -> Vec<String> {
 }
 
 /// Prompt user to select version bump type, existing tag, or enter custom version
-fn prompt_tag_selection(existing_tags: &[String], current_version: &str) -> Result<TagSelection> {
+///
+/// Bump options are only offered when `current_version` is valid semver.
+fn prompt_tag_selection(
+    existing_tags: &[String],
+    current_version: Option<&semver::Version>,
+) -> Result<TagSelection> {
     // Filter to only semver tags
     let semver_tags = filter_semver_tags(existing_tags);
 
     // Check if we have a valid current version to bump from
-    let has_valid_version = parse_version(current_version).is_ok();
+    let has_valid_version = current_version.is_some();
 
     let mut options: Vec<String> = Vec::new();
     let mut option_count = 0;
 
     // Add bump options only if we have a valid base version
-    if has_valid_version {
-        let (major, minor, patch) = parse_version(current_version)?;
-        let patch_version = format!("v{}.{}.{}", major, minor, patch + 1);
-        let minor_version = format!("v{}.{}.{}", major, minor + 1, 0);
-        let major_version = format!("v{}.{}.{}", major + 1, 0, 0);
-
-        options.push(format!("📦 Patch  → {}", patch_version));
-        options.push(format!("🔧 Minor  → {}", minor_version));
-        options.push(format!("🚀 Major  → {}", major_version));
-        option_count = 3;
+    let mut bumped = Vec::new();
+    if let Some(current_version) = current_version {
+        for (level, label) in [
+            (BumpLevel::Patch, "📦 Patch "),
+            (BumpLevel::Minor, "🔧 Minor "),
+            (BumpLevel::Major, "🚀 Major "),
+        ] {
+            let tag = format!("v{}", level.apply(current_version)?);
+            options.push(format!("{} → {}", label, tag));
+            bumped.push(tag);
+        }
+        option_count = bumped.len();
     }
 
     // Add existing semver tags
@@ -105,20 +314,9 @@ fn prompt_tag_selection(existing_tags: &[String], current_version: &str) -> Resu
         .interact()?;
 
     if has_valid_version {
-        let (major, minor, patch) = parse_version(current_version)?;
         if selection < option_count {
             // Bump options
-            match selection {
-                0 => Ok(TagSelection::New(format!(
-                    "v{}.{}.{}",
-                    major,
-                    minor,
-                    patch + 1
-                ))),
-                1 => Ok(TagSelection::New(format!("v{}.{}.{}", major, minor + 1, 0))),
-                2 => Ok(TagSelection::New(format!("v{}.{}.{}", major + 1, 0, 0))),
-                _ => unreachable!(),
-            }
+            Ok(TagSelection::New(bumped[selection].clone()))
         } else if selection == custom_idx {
             prompt_custom_version()
         } else {
@@ -137,7 +335,7 @@ fn prompt_tag_selection(existing_tags: &[String], current_version: &str) -> Resu
 /// Prompt user to enter a custom version
 fn prompt_custom_version() -> Result<TagSelection> {
     let version: String = Input::new()
-        .with_prompt("Enter version (e.g., 1.0.0 or v1.0.0)")
+        .with_prompt("Enter version (e.g., 1.0.0, v1.0.0 or 1.0.0-rc.1)")
         .interact_text()?;
 
     // Normalize to v-prefixed format
@@ -153,50 +351,37 @@ fn prompt_custom_version() -> Result<TagSelection> {
     Ok(TagSelection::New(tag))
 }
 
-/// Parse version string into (major, minor, patch)
-fn parse_version(version: &str) -> Result<(u32, u32, u32)> {
-    let v = version.strip_prefix('v').unwrap_or(version);
-    let parts: Vec<&str> = v.split('.').collect();
-
-    if parts.len() != 3 {
-        bail!(
-            "Invalid version format: {}. Expected MAJOR.MINOR.PATCH",
-            version
-        );
+pub async fn run(args: PublishArgs) -> Result<()> {
+    let structured = args.format.is_structured();
+    if structured && !args.dry_run {
+        bail!("--format is only supported with --dry-run");
     }
+    // Keep stdout for the plan when it's structured
+    let progress = |line: String| {
+        if structured {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+    };
 
-    let major: u32 = parts[0]
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid major version"))?;
-    let minor: u32 = parts[1]
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid minor version"))?;
-    let patch: u32 = parts[2]
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid patch version"))?;
-
-    Ok((major, minor, patch))
-}
-
-pub async fn run(args: PublishArgs) -> Result<()> {
     let skill_path = Path::new(&args.path).canonicalize()?;
 
     // Step 1: Load and validate the skill
-    let skill = Skill::load(&skill_path)?;
-    println!("Publishing skill: {}", skill.name());
+    let mut skill = Skill::load(&skill_path)?;
+    progress(format!("Publishing skill: {}", skill.name()));
 
     // Validate unless skipped
     if !args.skip_validation {
-        print!("  Validating SKILL.md... ");
         let warnings = skill.frontmatter.validate()?;
-        println!("✓");
+        progress("  Validating SKILL.md... ✓".to_string());
         for warning in &warnings {
-            println!("  ⚠ {}", warning);
+            progress(format!("  ⚠ {}", warning));
         }
     }
 
     // Get current version from SKILL.md
-    let current_version = skill.version();
+    let current_version = skill.semver().ok();
 
     // Step 2: Git checks
     if !git::is_git_repo(&skill_path) {
@@ -205,24 +390,36 @@ pub async fn run(args: PublishArgs) -> Result<()> {
 
     let remote = "origin";
     let repo_url = git::get_remote_url(&skill_path, remote)?;
-    let branch = git::get_current_branch(&skill_path)?;
+    let branch = resolve_branch(&skill_path, args.branch.as_deref())?;
 
     // Get pak path relative to repo root (this is what we send to the API)
-    let pak_path_in_repo = git::get_pak_path_in_repo(&skill_path)?;
+    let local_pak_path = git::get_pak_path_in_repo(&skill_path)?;
+    let pak_path_in_repo = match &args.repo_path {
+        Some(path) => normalize_repo_path(path)?,
+        None => local_pak_path.clone(),
+    };
 
-    // Step 3: Check for uncommitted changes in the skill directory
-    let uncommitted_changes = git::get_uncommitted_changes(&skill_path)?;
-    if !uncommitted_changes.is_empty() && !args.yes {
-        println!();
-        if !prompt_continue_with_changes(&uncommitted_changes)? {
-            println!("Aborted.");
-            return Ok(());
-        }
-    } else if !uncommitted_changes.is_empty() && args.yes {
-        println!(
-            "  ⚠ {} uncommitted changes detected, continuing with --yes",
+    // Step 3: Check for uncommitted changes in the skill directory, other
+    // than to files that aren't published
+    let uncommitted_changes = without_ignored(
+        git::get_uncommitted_changes(&skill_path)?,
+        &IgnoreRules::load(&skill_path),
+        &local_pak_path,
+    );
+    let interactive = !args.yes && io::stdin().is_terminal();
+    match check_dirty_tree(&uncommitted_changes, args.allow_dirty, interactive)? {
+        DirtyTree::Clean => {}
+        DirtyTree::Allowed => progress(format!(
+            "  ⚠ {} uncommitted changes detected, continuing with --allow-dirty",
             uncommitted_changes.len()
-        );
+        )),
+        DirtyTree::Prompt => {
+            println!();
+            if !prompt_continue_with_changes(&uncommitted_changes)? {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
     }
 
     // Step 4: Determine which tag to use
@@ -243,8 +440,7 @@ pub async fn run(args: PublishArgs) -> Result<()> {
         (tag_to_check, false)
     } else if args.yes {
         // Non-interactive mode: create patch bump
-        let (major, minor, patch) = parse_version(current_version)?;
-        let new_tag = format!("v{}.{}.{}", major, minor, patch + 1);
+        let new_tag = format!("v{}", skill.bump_version(BumpLevel::Patch)?);
         if git::tag_exists(&skill_path, &new_tag) {
             bail!("Tag {} already exists.", new_tag);
         }
@@ -252,7 +448,7 @@ pub async fn run(args: PublishArgs) -> Result<()> {
     } else {
         // Interactive mode: let user choose bump type or existing tag
         println!();
-        match prompt_tag_selection(&existing_tags, current_version)? {
+        match prompt_tag_selection(&existing_tags, current_version.as_ref())? {
             TagSelection::New(tag) => {
                 if git::tag_exists(&skill_path, &tag) {
                     bail!("Tag {} already exists.", tag);
@@ -263,24 +459,42 @@ pub async fn run(args: PublishArgs) -> Result<()> {
         }
     };
 
+    if args.branch.is_some() {
+        let rev = if needs_create { "HEAD" } else { tag.as_str() };
+        check_tag_on_branch(&skill_path, rev, &tag, &branch, remote)?;
+    }
+
     // Dry run output
     if args.dry_run {
-        println!();
-        println!("[Dry run] Would execute:");
-        println!("  Repository: {}", repo_url);
-        println!("  Branch: {}", branch);
-        println!("  Path: {}", pak_path_in_repo);
-        println!("  Tag: {}", tag);
-        if needs_create {
-            println!("  Action: Create and push new tag, then register with registry");
-        } else {
-            println!("  Action: Register existing tag with registry");
+        let plan = PublishPlan {
+            name: skill.name().to_string(),
+            version: parse_version(&tag)?.to_string(),
+            repository: repo_url,
+            branch,
+            path: pak_path_in_repo,
+            tag,
+            action: if needs_create {
+                PublishAction::CreateTag
+            } else {
+                PublishAction::UseExisting
+            },
+        };
+        match args.format {
+            OutputFormat::Table => {
+                println!();
+                print!("{}", plan.render());
+                println!();
+                println!("✓ Dry run complete.");
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+            OutputFormat::Yaml => print!("{}", serde_yaml_ng::to_string(&plan)?),
         }
-        println!();
-        println!("✓ Dry run complete.");
         return Ok(());
     }
 
+    // Pushing the tag and registering it both need the network
+    offline::ensure_online("Publishing")?;
+
     // Step 5: Confirm before publishing (unless --yes)
     if !args.yes {
         println!();
@@ -309,15 +523,11 @@ pub async fn run(args: PublishArgs) -> Result<()> {
     }
 
     // Step 7: Register with registry
-    print!("  Registering with registry... ");
-
     let config = Config::load()?;
-    let token = config
-        .get_auth_token()
-        .ok_or_else(|| anyhow::anyhow!("Not authenticated. Run 'paks login' first."))?;
+    let client = token::authenticated_client(&config).await?;
+    token::warn_if_expiring(&client).await;
 
-    let mut client = PaksClient::new()?;
-    client.set_token(token);
+    print!("  Registering with registry... ");
 
     let request = PublishPakRequest {
         repository: repo_url,
@@ -330,7 +540,10 @@ pub async fn run(args: PublishArgs) -> Result<()> {
         tag: tag.clone(),
     };
 
-    client.publish_pak(request).await?;
+    if let Err(e) = client.publish_pak(request).await {
+        println!("✗");
+        return Err(publish_error(e, &tag, &pak_path_in_repo));
+    }
     println!("✓");
 
     println!();
@@ -343,3 +556,168 @@ pub async fn run(args: PublishArgs) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::core::git::ChangeKind;
+
+    fn dirty() -> Vec<ChangeEntry> {
+        vec![ChangeEntry {
+            status: ChangeKind::Modified,
+            path: "SKILL.md".to_string(),
+        }]
+    }
+
+    #[test]
+    fn test_dirty_tree_non_interactive_is_error() {
+        let err = check_dirty_tree(&dirty(), false, false).unwrap_err();
+        assert!(err.to_string().contains("--allow-dirty"));
+    }
+
+    #[test]
+    fn test_dirty_tree_allowed_proceeds() {
+        assert_eq!(
+            check_dirty_tree(&dirty(), true, false).unwrap(),
+            DirtyTree::Allowed
+        );
+        assert_eq!(
+            check_dirty_tree(&dirty(), false, true).unwrap(),
+            DirtyTree::Prompt
+        );
+        assert_eq!(
+            check_dirty_tree(&[], false, false).unwrap(),
+            DirtyTree::Clean
+        );
+    }
+
+    #[test]
+    fn test_ignored_changes_dont_count() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".paksignore"), "build/\n").unwrap();
+        let rules = IgnoreRules::load(dir.path());
+        let change = |path: &str| ChangeEntry {
+            status: ChangeKind::Untracked,
+            path: path.to_string(),
+        };
+
+        let changes = vec![
+            change("skills/demo/build/"),
+            change("skills/demo/assets/build/out.txt"),
+            change("skills/demo/SKILL.md"),
+            change("skills/demo-two/build/"),
+        ];
+        let kept = without_ignored(changes, &rules, "skills/demo");
+        let paths: Vec<&str> = kept.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["skills/demo/SKILL.md", "skills/demo-two/build/"]);
+
+        let kept = without_ignored(vec![change("build/"), change("SKILL.md")], &rules, ".");
+        assert_eq!(kept.len(), 1);
+    }
+
+    fn git(args: &[&str], dir: &Path) {
+        let mut full = vec!["-c", "user.name=test", "-c", "user.email=test@example.com"];
+        full.extend_from_slice(args);
+        git::git_cmd(&full, dir).unwrap();
+    }
+
+    /// A repo on `main` with `v0.1.0` tagged, plus `v0.2.0` only on `feature`,
+    /// checked out at a detached HEAD
+    fn detached_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(&["init", "-q", "-b", "main"], root);
+        std::fs::write(root.join("SKILL.md"), "one").unwrap();
+        git(&["add", "-A"], root);
+        git(&["commit", "-q", "-m", "one"], root);
+        git(&["tag", "v0.1.0"], root);
+        git(&["checkout", "-q", "-b", "feature"], root);
+        std::fs::write(root.join("SKILL.md"), "two").unwrap();
+        git(&["commit", "-q", "-am", "two"], root);
+        git(&["tag", "v0.2.0"], root);
+        git(&["checkout", "-q", "--detach", "v0.1.0"], root);
+        dir
+    }
+
+    #[test]
+    fn test_branch_override_on_detached_head() {
+        let repo = detached_repo();
+        let root = repo.path();
+
+        let err = resolve_branch(root, None).unwrap_err();
+        assert!(err.to_string().contains("--branch"));
+        assert_eq!(resolve_branch(root, Some("main")).unwrap(), "main");
+
+        check_tag_on_branch(root, "v0.1.0", "v0.1.0", "main", "origin").unwrap();
+        check_tag_on_branch(root, "HEAD", "v0.1.1", "main", "origin").unwrap();
+        let err = check_tag_on_branch(root, "v0.2.0", "v0.2.0", "main", "origin").unwrap_err();
+        assert!(err.to_string().contains("not on branch main"));
+        check_tag_on_branch(root, "v0.2.0", "v0.2.0", "feature", "origin").unwrap();
+        assert!(check_tag_on_branch(root, "v0.1.0", "v0.1.0", "missing", "origin").is_err());
+    }
+
+    #[test]
+    fn test_publish_error_explains_known_codes() {
+        let api_error = |code: Option<&str>| ApiError::Api {
+            status: 422,
+            code: code.map(str::to_string),
+            message: "rejected".to_string(),
+        };
+
+        let err = publish_error(api_error(Some("VERSION_MISMATCH")), "v1.2.0", ".").to_string();
+        assert!(err.starts_with("The version in SKILL.md doesn't match the tag v1.2.0."));
+        assert!(err.contains("Set metadata.version to 1.2.0"));
+        assert!(err.ends_with("(API error (422, VERSION_MISMATCH): rejected)"));
+
+        let err = publish_error(api_error(Some("TAG_NOT_FOUND")), "v1.2.0", ".").to_string();
+        assert!(err.contains("git push origin v1.2.0"));
+
+        // Unknown codes and errors without one are passed through
+        let err = publish_error(api_error(Some("SOMETHING_NEW")), "v1.2.0", ".").to_string();
+        assert_eq!(err, "API error (422, SOMETHING_NEW): rejected");
+        let err = publish_error(api_error(None), "v1.2.0", ".").to_string();
+        assert_eq!(err, "API error (422): rejected");
+    }
+
+    #[test]
+    fn test_normalize_repo_path() {
+        assert_eq!(normalize_repo_path("skills/demo").unwrap(), "skills/demo");
+        assert_eq!(
+            normalize_repo_path("./skills/demo/").unwrap(),
+            "skills/demo"
+        );
+        assert_eq!(normalize_repo_path(".").unwrap(), ".");
+        assert_eq!(normalize_repo_path("./").unwrap(), ".");
+        assert!(normalize_repo_path("/abs/path").is_err());
+        assert!(normalize_repo_path("../elsewhere").is_err());
+    }
+
+    #[test]
+    fn test_dry_run_plan_output() {
+        let plan = PublishPlan {
+            name: "demo".to_string(),
+            version: "1.2.0".to_string(),
+            repository: "https://github.com/acme/skills".to_string(),
+            branch: "main".to_string(),
+            path: "skills/demo".to_string(),
+            tag: "v1.2.0".to_string(),
+            action: PublishAction::CreateTag,
+        };
+        assert_eq!(
+            serde_json::to_value(&plan).unwrap(),
+            serde_json::json!({
+                "name": "demo",
+                "version": "1.2.0",
+                "repository": "https://github.com/acme/skills",
+                "branch": "main",
+                "path": "skills/demo",
+                "tag": "v1.2.0",
+                "action": "create_tag",
+            })
+        );
+        assert!(
+            plan.render()
+                .ends_with("  Action: Create and push new tag, then register with registry\n")
+        );
+    }
+}
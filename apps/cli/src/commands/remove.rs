@@ -1,62 +1,78 @@
 //! Remove command - remove an installed skill
 
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::core::config::Config;
+use super::core::hooks::{self, Hook};
+use super::core::output::OutputFormat;
 use super::core::skill::Skill;
+use super::install::install_target;
 
 pub struct RemoveArgs {
     pub name: String,
     pub agent: Option<String>,
     pub all: bool,
     pub yes: bool,
+    /// Run the skill's preremove hook without asking
+    pub run_hooks: bool,
+    pub format: OutputFormat,
+}
+
+/// Result of removing a skill from one or more agents
+///
+/// Agents are given by id, `None` being the shared default skills directory.
+#[derive(Debug, Default)]
+pub struct RemoveOutcome {
+    /// Agents the skill was removed from, with the removed directory
+    pub removed: Vec<(Option<String>, PathBuf)>,
+    /// Agents where the skill was not installed
+    pub not_found: Vec<Option<String>>,
+    /// Agents where removal was declined at the confirmation prompt
+    pub skipped: Vec<Option<String>>,
 }
 
 pub async fn run(args: RemoveArgs) -> Result<()> {
-    let config = Config::load()?;
-
-    let mut removed_count = 0;
-    let mut not_found_count = 0;
-
-    if args.all {
-        // Remove from all agent directories
-        for (id, agent_config) in &config.agents {
-            let skill_path = agent_config.skills_dir.join(&args.name);
-            if skill_path.exists() {
-                if confirm_removal(&args.name, &agent_config.name, args.yes)? {
-                    remove_skill_dir(&skill_path)?;
-                    println!("✓ Removed '{}' from {}", args.name, id);
-                    removed_count += 1;
-                }
-            } else {
-                not_found_count += 1;
+    validate_skill_name(&args.name)?;
+
+    let mut config = Config::load()?;
+
+    let outcome = if args.all {
+        // Remove from all agent directories, following each agent's aliases
+        let targets = config.agents.iter().map(|(id, agent)| {
+            let name = config.resolve_skill_alias(id, &agent.skills_dir, &args.name);
+            (Some(id.as_str()), agent.skills_dir.as_path(), name)
+        });
+
+        let outcome = remove_from_agents(targets, args.run_hooks, |id, name| {
+            confirm_removal(name, &agent_label(&config, id), args.yes)
+        })?;
+
+        let mut forgotten = false;
+        for (agent, path) in &outcome.removed {
+            if let Some(agent) = agent
+                && config.aliases.contains_key(agent)
+                && let Some(name) = path.file_name().and_then(|n| n.to_str())
+            {
+                config.forget_aliases(agent, name);
+                forgotten = true;
             }
         }
-
-        if removed_count == 0 && not_found_count > 0 {
-            println!("Skill '{}' not found in any agent directory", args.name);
+        if forgotten {
+            config.save()?;
         }
+        outcome
     } else {
-        // Get target directory
-        let (agent_name, skills_dir) = if let Some(agent_name) = &args.agent {
-            if let Some(agent_config) = config.get_agent(agent_name) {
-                (agent_config.name.clone(), agent_config.skills_dir.clone())
-            } else {
-                bail!("Agent '{}' not found", agent_name);
-            }
-        } else {
-            // Use default agent or ~/.paks/skills
-            if let Some(agent) = config.get_default_agent() {
-                let name = config.default_agent.as_deref().unwrap_or("default");
-                (name.to_string(), agent.skills_dir.clone())
-            } else {
-                ("paks".to_string(), Config::default_skills_dir())
-            }
-        };
+        let (skills_dir, agent) = install_target(None, args.agent.as_deref())?;
+        let agent_name = agent_label(&config, agent.as_deref());
 
-        let skill_path = skills_dir.join(&args.name);
+        // Follow an alias left by `paks rename`
+        let scope = config.skills_scope(args.agent.as_deref()).to_string();
+        let name = config
+            .resolve_skill_alias(&scope, &skills_dir, &args.name)
+            .to_string();
+        let skill_path = skills_dir.join(&name);
 
         if !skill_path.exists() {
             bail!(
@@ -69,20 +85,160 @@ pub async fn run(args: RemoveArgs) -> Result<()> {
 
         // Verify it's a valid skill
         if let Err(e) = Skill::load(&skill_path) {
-            println!("⚠ Warning: {} (removing anyway)", e);
+            eprintln!("⚠ Warning: {} (removing anyway)", e);
         }
 
-        if confirm_removal(&args.name, &agent_name, args.yes)? {
+        let outcome = remove_from_agents(
+            [(agent.as_deref(), skills_dir.as_path(), name.as_str())],
+            args.run_hooks,
+            |_, name| confirm_removal(name, &agent_name, args.yes),
+        )?;
+        if !outcome.removed.is_empty() && config.aliases.contains_key(&scope) {
+            config.forget_aliases(&scope, &name);
+            config.save()?;
+        }
+        outcome
+    };
+
+    print_outcome(&config, &args.name, &outcome, args.all, args.format);
+
+    Ok(())
+}
+
+/// Display name of the agent with id `agent`; `paks` for the shared default
+/// skills directory
+pub(super) fn agent_label(config: &Config, agent: Option<&str>) -> String {
+    match agent {
+        Some(id) => config
+            .get_agent(id)
+            .map_or(id, |a| a.name.as_str())
+            .to_string(),
+        None => "paks".to_string(),
+    }
+}
+
+/// Remove a skill from each of the given `(agent id, skills dir, skill
+/// directory name)` targets
+///
+/// `confirm` is called with the agent id and directory name before each
+/// removal and can decline it. The skill's preremove hook then runs, with
+/// `run_hooks` as consent; if it fails the skill is left in place.
+pub fn remove_from_agents<'a>(
+    targets: impl IntoIterator<Item = (Option<&'a str>, &'a Path, &'a str)>,
+    run_hooks: bool,
+    mut confirm: impl FnMut(Option<&str>, &str) -> Result<bool>,
+) -> Result<RemoveOutcome> {
+    let mut outcome = RemoveOutcome::default();
+
+    for (agent, skills_dir, name) in targets {
+        validate_skill_name(name)?;
+        let skill_path = skills_dir.join(name);
+        if !skill_path.exists() {
+            outcome.not_found.push(agent.map(str::to_string));
+            continue;
+        }
+
+        ensure_within_skills_dir(skills_dir, &skill_path)?;
+
+        if confirm(agent, name)? {
+            hooks::run(&skill_path, Hook::PreRemove, run_hooks).with_context(|| {
+                format!(
+                    "Not removing {}: its preremove hook did not complete",
+                    skill_path.display()
+                )
+            })?;
             remove_skill_dir(&skill_path)?;
-            println!("✓ Removed '{}' from {}", args.name, agent_name);
+            outcome
+                .removed
+                .push((agent.map(str::to_string), skill_path));
         } else {
-            println!("Cancelled");
+            outcome.skipped.push(agent.map(str::to_string));
         }
     }
 
+    Ok(outcome)
+}
+
+/// Reject names that could resolve outside of a skills directory
+pub(super) fn validate_skill_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." {
+        bail!("Invalid skill name '{}'", name);
+    }
+    if name.contains('/') || name.contains('\\') || Path::new(name).is_absolute() {
+        bail!(
+            "Invalid skill name '{}': expected a directory name, not a path",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Make sure a removal target lives directly inside the skills directory
+pub(super) fn ensure_within_skills_dir(skills_dir: &Path, target: &Path) -> Result<()> {
+    let root = skills_dir.canonicalize()?;
+
+    // Resolve the parent rather than the target itself so a symlinked skill is
+    // judged by where the link lives, not where it points
+    let parent = target.parent().map(Path::canonicalize).transpose()?;
+    if parent.as_deref() != Some(root.as_path()) {
+        bail!(
+            "Refusing to remove {}: it is outside of {}",
+            target.display(),
+            skills_dir.display()
+        );
+    }
     Ok(())
 }
 
+/// Print the removal outcome in the requested format
+///
+/// Tables name agents by display name; JSON and YAML keep their ids.
+fn print_outcome(
+    config: &Config,
+    name: &str,
+    outcome: &RemoveOutcome,
+    all: bool,
+    format: OutputFormat,
+) {
+    match format {
+        OutputFormat::Table => {
+            for (agent, _) in &outcome.removed {
+                println!(
+                    "✓ Removed '{}' from {}",
+                    name,
+                    agent_label(config, agent.as_deref())
+                );
+            }
+            if !outcome.skipped.is_empty() {
+                println!("Cancelled");
+            } else if all && outcome.removed.is_empty() && !outcome.not_found.is_empty() {
+                println!("Skill '{}' not found in any agent directory", name);
+            }
+        }
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let value = serde_json::json!({
+                "name": name,
+                "removed": outcome
+                    .removed
+                    .iter()
+                    .map(|(agent, path)| serde_json::json!({
+                        "agent": agent,
+                        "path": path.display().to_string(),
+                    }))
+                    .collect::<Vec<_>>(),
+                "not_found": outcome.not_found,
+                "skipped": outcome.skipped,
+            });
+            let rendered = if matches!(format, OutputFormat::Json) {
+                serde_json::to_string_pretty(&value).unwrap_or_default()
+            } else {
+                serde_yaml_ng::to_string(&value).unwrap_or_default()
+            };
+            println!("{}", rendered.trim_end());
+        }
+    }
+}
+
 /// Confirm removal with user (unless --yes)
 fn confirm_removal(skill_name: &str, agent_name: &str, skip_confirm: bool) -> Result<bool> {
     if skip_confirm {
@@ -99,7 +255,141 @@ fn confirm_removal(skill_name: &str, agent_name: &str, skip_confirm: bool) -> Re
 }
 
 /// Remove a skill directory
-fn remove_skill_dir(path: &PathBuf) -> Result<()> {
+fn remove_skill_dir(path: &Path) -> Result<()> {
     std::fs::remove_dir_all(path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::install_fake_skill;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_remove_from_all_agents_aggregates() {
+        let claude = tempdir().unwrap();
+        let cursor = tempdir().unwrap();
+        let goose = tempdir().unwrap();
+        install_fake_skill(claude.path(), "my-skill", "0.1.0");
+        install_fake_skill(goose.path(), "my-skill", "0.1.0");
+
+        let targets = [
+            (Some("claude-code"), claude.path(), "my-skill"),
+            (Some("cursor"), cursor.path(), "my-skill"),
+            (Some("goose"), goose.path(), "my-skill"),
+        ];
+        let outcome = remove_from_agents(targets, false, |_, _| Ok(true)).unwrap();
+
+        let removed: Vec<_> = outcome.removed.iter().map(|(a, _)| a.as_deref()).collect();
+        assert_eq!(removed, [Some("claude-code"), Some("goose")]);
+        assert_eq!(outcome.not_found, [Some("cursor".to_string())]);
+        assert!(outcome.skipped.is_empty());
+        assert_eq!(outcome.removed[0].1, claude.path().join("my-skill"));
+        assert!(!claude.path().join("my-skill").exists());
+        assert!(!goose.path().join("my-skill").exists());
+    }
+
+    #[test]
+    fn test_remove_declined_is_skipped() {
+        let claude = tempdir().unwrap();
+        let goose = tempdir().unwrap();
+        install_fake_skill(claude.path(), "my-skill", "0.1.0");
+        install_fake_skill(goose.path(), "my-skill", "0.1.0");
+
+        let targets = [
+            (Some("claude-code"), claude.path(), "my-skill"),
+            (Some("goose"), goose.path(), "my-skill"),
+        ];
+        let outcome =
+            remove_from_agents(targets, false, |agent, _| Ok(agent == Some("goose"))).unwrap();
+
+        assert_eq!(outcome.removed.len(), 1);
+        assert_eq!(outcome.skipped, [Some("claude-code".to_string())]);
+        assert!(claude.path().join("my-skill").exists());
+        assert!(!goose.path().join("my-skill").exists());
+    }
+
+    #[test]
+    fn test_remove_from_default_dir_has_no_agent() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "my-skill", "0.1.0");
+
+        let outcome = remove_from_agents([(None, skills.path(), "my-skill")], false, |agent, _| {
+            Ok(agent.is_none())
+        })
+        .unwrap();
+        assert_eq!(outcome.removed, [(None, skills.path().join("my-skill"))]);
+    }
+
+    #[test]
+    fn test_remove_skill_with_invalid_preremove_hook() {
+        let skills = tempdir().unwrap();
+        let dir = skills.path().join("my-skill");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            "---\nname: my-skill\ndescription: test\nmetadata:\n  preremove: ../escape.sh\n---\n",
+        )
+        .unwrap();
+
+        // The hook is skipped with a warning and the skill still goes
+        let outcome = remove_from_agents(
+            [(Some("claude-code"), skills.path(), "my-skill")],
+            true,
+            |_, _| Ok(true),
+        )
+        .unwrap();
+        assert_eq!(outcome.removed.len(), 1);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_remove_rejects_parent_traversal() {
+        let root = tempdir().unwrap();
+        let skills = root.path().join("skills");
+        std::fs::create_dir_all(&skills).unwrap();
+        install_fake_skill(root.path(), "escape", "0.1.0");
+
+        let err = remove_from_agents(
+            [(Some("claude-code"), skills.as_path(), "../escape")],
+            false,
+            |_, _| Ok(true),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("not a path"));
+        assert!(root.path().join("escape").exists());
+    }
+
+    #[test]
+    fn test_remove_rejects_absolute_name() {
+        let root = tempdir().unwrap();
+        let skills = root.path().join("skills");
+        std::fs::create_dir_all(&skills).unwrap();
+        install_fake_skill(root.path(), "victim", "0.1.0");
+        let victim = root.path().join("victim");
+
+        let err = remove_from_agents(
+            [(
+                Some("claude-code"),
+                skills.as_path(),
+                victim.to_str().unwrap(),
+            )],
+            false,
+            |_, _| Ok(true),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("Invalid skill name"));
+        assert!(victim.exists());
+    }
+
+    #[test]
+    fn test_remove_rejects_dot_names() {
+        assert!(validate_skill_name("..").is_err());
+        assert!(validate_skill_name(".").is_err());
+        assert!(validate_skill_name("").is_err());
+        assert!(validate_skill_name("owner--skill").is_ok());
+    }
+}
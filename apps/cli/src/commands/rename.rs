@@ -0,0 +1,130 @@
+//! Rename command - move an installed skill to a different directory name
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+use super::core::config::Config;
+use super::install::install_target;
+use super::remove::{agent_label, ensure_within_skills_dir, validate_skill_name};
+
+pub struct RenameArgs {
+    pub old: String,
+    pub new: String,
+    pub agent: Option<String>,
+}
+
+pub async fn run(args: RenameArgs) -> Result<()> {
+    let mut config = Config::load()?;
+
+    let (skills_dir, agent) = install_target(None, args.agent.as_deref())?;
+    let agent_name = agent_label(&config, agent.as_deref());
+
+    // The old name may itself be an alias from an earlier rename
+    let scope = config.skills_scope(args.agent.as_deref()).to_string();
+    let old = config
+        .resolve_skill_alias(&scope, &skills_dir, &args.old)
+        .to_string();
+    let target = rename_skill(&skills_dir, &old, &args.new)?;
+
+    // Keep the old name working for remove and info
+    config.record_rename(&scope, &old, &args.new);
+    config.save()?;
+
+    println!("✓ Renamed '{}' to '{}' in {}", old, args.new, agent_name);
+    println!("  Location: {}", target.display());
+    println!("  '{}' still refers to it as an alias", old);
+
+    Ok(())
+}
+
+/// Move the skill directory `old` to `new` inside `skills_dir`
+///
+/// Only the directory is renamed; the skill's frontmatter is left untouched.
+/// Both names must be plain directory names, so a skill can't be moved to
+/// another agent this way.
+pub fn rename_skill(skills_dir: &Path, old: &str, new: &str) -> Result<PathBuf> {
+    for name in [old, new] {
+        if validate_skill_name(name).is_err() {
+            bail!(
+                "Invalid skill name '{}': rename only works within one skills directory.\n\
+                 Hint: To move a skill to another agent, remove it and install it there.",
+                name
+            );
+        }
+    }
+    if old == new {
+        bail!("'{}' and '{}' are the same name", old, new);
+    }
+
+    let source = skills_dir.join(old);
+    let target = skills_dir.join(new);
+
+    if !source.exists() {
+        bail!("Skill '{}' not found in {}", old, skills_dir.display());
+    }
+    ensure_within_skills_dir(skills_dir, &source)?;
+
+    if target.exists() {
+        bail!(
+            "'{}' already exists in {}.\n\
+             Hint: Remove it first with 'paks remove {}' or pick another name.",
+            new,
+            skills_dir.display(),
+            new
+        );
+    }
+
+    std::fs::rename(&source, &target).with_context(|| {
+        format!(
+            "Failed to rename {} to {}",
+            source.display(),
+            target.display()
+        )
+    })?;
+
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::install_fake_skill;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rename_within_skills_dir() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "my-skill", "0.1.0");
+
+        let target = rename_skill(skills.path(), "my-skill", "my-skill-fork").unwrap();
+
+        assert_eq!(target, skills.path().join("my-skill-fork"));
+        assert!(!skills.path().join("my-skill").exists());
+        // Frontmatter keeps the original name
+        let skill_md = std::fs::read_to_string(target.join("SKILL.md")).unwrap();
+        assert!(skill_md.contains("name: my-skill\n"));
+    }
+
+    #[test]
+    fn test_rename_refuses_collision() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "a", "0.1.0");
+        install_fake_skill(skills.path(), "b", "0.1.0");
+
+        let err = rename_skill(skills.path(), "a", "b").unwrap_err();
+
+        assert!(err.to_string().contains("already exists"));
+        assert!(skills.path().join("a").exists());
+    }
+
+    #[test]
+    fn test_rename_refuses_paths() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "a", "0.1.0");
+
+        let err = rename_skill(skills.path(), "a", "../other-agent/a").unwrap_err();
+
+        assert!(err.to_string().contains("remove it and install"));
+        assert!(skills.path().join("a").exists());
+    }
+}
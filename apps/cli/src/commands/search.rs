@@ -1,49 +1,328 @@
 //! Search command - search for skills in the registry
 
-use anyhow::{Context, Result};
-use paks_api::{PaksClient, SearchPaksQuery};
+use anyhow::{Context, Result, bail};
+use paks_api::{Pak, PakContent, PakSortBy, PaksClient, SearchPaksQuery, SearchPaksResponse};
+
+use super::core::cache::MetadataCache;
+use super::core::client;
+use super::core::config::Config;
+use super::core::content::ContentSource;
+use super::core::fanout::FanOut;
+use super::core::offline;
+use super::core::output::OutputFormat;
+use super::core::style::Style;
+use super::core::suggest;
+use super::core::token::authenticated_client;
+
+/// Matching lines shown per result in content search
+const MAX_MATCH_LINES: usize = 2;
+
+/// Fields of `Pak` that `--fields` can select
+const PAK_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "owner_name",
+    "uri",
+    "full_uri",
+    "path",
+    "repository_url",
+    "description",
+    "tags",
+    "license",
+    "visibility",
+    "status",
+    "download_count",
+    "usage_count",
+    "total_downloads",
+    "total_usages",
+    "created_at",
+    "updated_at",
+];
 
 pub struct SearchArgs {
-    pub query: String,
+    /// Keyword query; may be omitted when `owner` is set
+    pub query: Option<String>,
+    /// Only show paks published by this owner
+    pub owner: Option<String>,
+    /// Server-side sort order (by relevance if unset)
+    pub sort: Option<PakSortBy>,
     pub limit: usize,
+    pub offset: usize,
+    /// 1-based page of `limit` results; overrides `offset` when set
+    pub page: Option<usize>,
+    /// Only show results whose SKILL.md mentions this term
+    pub in_content: Option<String>,
+    /// Maximum number of SKILL.md bodies to fetch for content search
+    pub content_limit: usize,
+    /// Skip the metadata cache
+    pub no_cache: bool,
+    /// Registry requests in flight at once for content search
+    pub jobs: Option<usize>,
+    pub format: OutputFormat,
+    /// Only emit these `Pak` fields per result (json/yaml only)
+    pub fields: Vec<String>,
+    /// Registry name or URL to search (default registry if unset)
+    pub registry: Option<String>,
+    /// Search as the logged-in user, so their private and unlisted paks show up
+    pub private: bool,
+}
+
+/// Client for the registry `name_or_url`, or the default registry if `None`
+fn registry_client(config: &Config, name_or_url: Option<&str>) -> Result<PaksClient> {
+    client::builder(config.resolve_registry_url(name_or_url)?)
+        .build()
+        .context("Failed to create API client")
+}
+
+/// A search result with the SKILL.md lines that matched a content search
+struct SearchHit {
+    pak: Pak,
+    matches: Vec<String>,
 }
 
 pub async fn run(args: SearchArgs) -> Result<()> {
+    let structured = args.format.is_structured();
+    if !args.fields.is_empty() {
+        if !structured {
+            bail!("--fields requires --format json or yaml");
+        }
+        validate_fields(&args.fields)?;
+    }
+
+    offline::ensure_online("Searching the registry")?;
+
     // Create API client
-    let client = PaksClient::builder()
-        .base_url("https://apiv2.stakpak.dev")
-        .build()
-        .context("Failed to create API client")?;
+    let config = Config::load()?;
+    let client = if args.private {
+        authenticated_client(&config).await?
+    } else {
+        registry_client(&config, args.registry.as_deref())?
+    };
+    let registry_url = client.base_url().to_string();
 
-    // Build search query
-    let query = SearchPaksQuery {
-        query: Some(args.query.clone()),
-        limit: Some(args.limit as u32),
-        ..Default::default()
+    // Results that depend on who is asking aren't cached
+    let cache = if args.no_cache || args.private {
+        None
+    } else {
+        MetadataCache::new(&registry_url, config.cache_ttl()).ok()
     };
 
-    // Execute search
-    let mut results = client
-        .search_paks(query)
-        .await
-        .context("Failed to search registry")?;
+    let query = build_query(&args)?;
+
+    // Execute search, reusing a recent response for the same query
+    let cache_key = serde_json::to_string(&query)?;
+    let cached = cache
+        .as_ref()
+        .and_then(|c| c.get::<SearchPaksResponse>("search", &cache_key));
+    let response = match cached {
+        Some(response) => response,
+        None => {
+            let response = client
+                .search_paks_page(query)
+                .await
+                .context("Failed to search registry")?;
+            if let Some(cache) = &cache {
+                // Caching is best-effort
+                cache.put("search", &cache_key, &response).ok();
+            }
+            response
+        }
+    };
+    let total = response.total_count;
+    let mut results = response.results;
 
+    if results.is_empty() && structured {
+        return print_structured(&[], &args.fields, args.format);
+    }
     if results.is_empty() {
-        println!("\n  No skills found matching '{}'\n", args.query);
+        println!("\n  {}", no_results_message(&args));
+        if let Some(text) = &args.query {
+            let suggestions = suggest::did_you_mean(&client, text).await;
+            if let Some(hint) = suggest::format_hint(&suggestions) {
+                println!("  {}", hint);
+            }
+        }
+        println!();
         return Ok(());
     }
 
-    // Sort by downloads (descending)
-    results.sort_by(|a, b| b.total_downloads.cmp(&a.total_downloads));
+    // Keep the registry's order when a sort was asked for, otherwise
+    // show the most downloaded first
+    if args.sort.is_none() {
+        results.sort_by_key(|pak| std::cmp::Reverse(pak.total_downloads));
+    }
+
+    let results_len = results.len();
 
-    println!();
-    for pak in results {
+    let hits = if let Some(term) = &args.in_content {
+        let searched = results.len().min(args.content_limit);
+        let fanout = FanOut::from_config(&config, args.jobs);
+        let hits = filter_by_content(&client, results, term, args.content_limit, &fanout).await;
+        if hits.is_empty() && !structured {
+            println!(
+                "\n  No skills among the top {} mention '{}'\n",
+                searched, term
+            );
+            return Ok(());
+        }
+        hits
+    } else {
+        results
+            .into_iter()
+            .map(|pak| SearchHit {
+                pak,
+                matches: Vec::new(),
+            })
+            .collect()
+    };
+
+    if structured {
+        let paks: Vec<Pak> = hits.into_iter().map(|hit| hit.pak).collect();
+        return print_structured(&paks, &args.fields, args.format);
+    }
+
+    let style = Style::current();
+    let footer = if let Some(term) = &args.in_content {
+        format!(
+            "{} of {} searched skills mention '{}'",
+            hits.len(),
+            results_len.min(args.content_limit),
+            term
+        )
+    } else {
+        summary_line(
+            results_len,
+            start_offset(&args)?,
+            args.limit,
+            args.page,
+            total,
+        )
+    };
+
+    print!(
+        "{}",
+        render_hits(&hits, args.in_content.as_deref(), &footer, &style)
+    );
+
+    Ok(())
+}
+
+/// Registry query for `args`
+///
+/// With only `--owner` and no query text, this lists the owner's paks.
+fn build_query(args: &SearchArgs) -> Result<SearchPaksQuery> {
+    let text = args
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+    let owner = args
+        .owner
+        .as_deref()
+        .map(str::trim)
+        .filter(|o| !o.is_empty());
+    if text.is_none() && owner.is_none() {
+        bail!("Give a search query, --owner, or both");
+    }
+    Ok(SearchPaksQuery {
+        query: text.map(str::to_string),
+        owner: owner.map(str::to_string),
+        sort_by: args.sort,
+        limit: Some(
+            u32::try_from(args.limit)
+                .with_context(|| format!("--limit {} is too large", args.limit))?,
+        ),
+        offset: match start_offset(args)? {
+            0 => None,
+            offset => Some(
+                u32::try_from(offset).with_context(|| format!("Offset {} is too large", offset))?,
+            ),
+        },
+        ..Default::default()
+    })
+}
+
+/// Number of results to skip, from `--page` if given, otherwise `--offset`
+fn start_offset(args: &SearchArgs) -> Result<usize> {
+    match args.page {
+        Some(page) => page
+            .saturating_sub(1)
+            .checked_mul(args.limit)
+            .with_context(|| format!("--page {} is too large for --limit {}", page, args.limit)),
+        None => Ok(args.offset),
+    }
+}
+
+/// What to say when a search comes back empty
+fn no_results_message(args: &SearchArgs) -> String {
+    match (&args.query, &args.owner) {
+        (Some(query), Some(owner)) => {
+            format!("No skills by '{}' found matching '{}'", owner, query)
+        }
+        (None, Some(owner)) => format!("No skills found for owner '{}'", owner),
+        (Some(query), None) => format!("No skills found matching '{}'", query),
+        (None, None) => "No skills found".to_string(),
+    }
+}
+
+/// Reject `--fields` names that aren't fields of `Pak`
+fn validate_fields(fields: &[String]) -> Result<()> {
+    let unknown: Vec<&str> = fields
+        .iter()
+        .map(String::as_str)
+        .filter(|f| !PAK_FIELDS.contains(f))
+        .collect();
+    if !unknown.is_empty() {
+        bail!(
+            "Unknown field(s): {}\nValid fields: {}",
+            unknown.join(", "),
+            PAK_FIELDS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Results as JSON values, keeping only `fields` when any are given
+fn project(paks: &[Pak], fields: &[String]) -> Result<Vec<serde_json::Value>> {
+    paks.iter()
+        .map(|pak| {
+            let value = serde_json::to_value(pak)?;
+            if fields.is_empty() {
+                return Ok(value);
+            }
+            let projected: serde_json::Map<_, _> = fields
+                .iter()
+                .map(|f| (f.clone(), value.get(f).cloned().unwrap_or_default()))
+                .collect();
+            Ok(serde_json::Value::Object(projected))
+        })
+        .collect()
+}
+
+fn print_structured(paks: &[Pak], fields: &[String], format: OutputFormat) -> Result<()> {
+    let values = project(paks, fields)?;
+    match format {
+        OutputFormat::Yaml => print!("{}", serde_yaml_ng::to_string(&values)?),
+        _ => println!("{}", serde_json::to_string_pretty(&values)?),
+    }
+    Ok(())
+}
+
+/// Render search hits followed by the `footer` summary and an install hint
+fn render_hits(hits: &[SearchHit], term: Option<&str>, footer: &str, style: &Style) -> String {
+    let mut out = String::from("\n");
+    for SearchHit { pak, matches } in hits {
         // First line: owner/name + stats
         let downloads = format_count(pak.total_downloads);
-        print!(
-            "  \x1b[1;36m{}\x1b[0m/\x1b[1m{}\x1b[0m \x1b[2m↓{}\x1b[0m",
-            pak.owner_name, pak.name, downloads
-        );
+        out.push_str(&format!(
+            "  {}/{} {}",
+            style.bold_cyan(&pak.owner_name),
+            style.bold(&pak.name),
+            style.dim(format!("↓{}", downloads))
+        ));
+        if let Some(license) = &pak.license {
+            out.push_str(&format!(" {}", style.dim(license)));
+        }
 
         // Tags inline (up to 3)
         if let Some(ref tags) = pak.tags
@@ -52,28 +331,148 @@ pub async fn run(args: SearchArgs) -> Result<()> {
             let tags_str: String = tags
                 .iter()
                 .take(3)
-                .map(|t| format!("\x1b[33m#{}\x1b[0m", t))
+                .map(|t| style.yellow(format!("#{}", t)))
                 .collect::<Vec<_>>()
                 .join(" ");
-            print!("  {}", tags_str);
+            out.push_str(&format!("  {}", tags_str));
         }
-        println!();
+        out.push('\n');
 
         // Description on second line
         if let Some(desc) = &pak.description {
             let truncated: String = desc.chars().take(72).collect();
             let suffix = if desc.len() > 72 { "…" } else { "" };
-            println!("    \x1b[2m{}{}\x1b[0m", truncated, suffix);
+            out.push_str(&format!(
+                "    {}\n",
+                style.dim(format!("{}{}", truncated, suffix))
+            ));
+        }
+
+        if let Some(term) = term {
+            for line in matches {
+                out.push_str(&format!(
+                    "    {} {}\n",
+                    style.dim("│"),
+                    highlight(line, term, style)
+                ));
+            }
         }
     }
 
-    println!("\n  \x1b[2mInstall: paks install <owner>/<skill>\x1b[0m\n");
+    out.push_str(&format!("\n  {}\n", style.dim(footer)));
+    out.push_str(&format!(
+        "  {}\n\n",
+        style.dim("Install: paks install <owner>/<skill>")
+    ));
+    out
+}
 
-    Ok(())
+/// Fetch SKILL.md for up to `limit` results and keep the ones mentioning `term`
+///
+/// Bodies are fetched concurrently within the limits of `fanout`; results
+/// whose SKILL.md can't be fetched are dropped. Result order is preserved.
+async fn filter_by_content(
+    source: &impl ContentSource,
+    paks: Vec<Pak>,
+    term: &str,
+    limit: usize,
+    fanout: &FanOut,
+) -> Vec<SearchHit> {
+    let paks: Vec<Pak> = paks.into_iter().take(limit).collect();
+    let bodies = fanout
+        .run(paks.clone(), |pak| async move {
+            let uri = format!("{}/{}/SKILL.md", pak.owner_name, pak.name);
+            source.fetch(&uri).await.map(|r| r.content)
+        })
+        .await;
+
+    paks.into_iter()
+        .zip(bodies)
+        .filter_map(|(pak, body)| match body {
+            Ok(PakContent::File { content }) => {
+                let matches = matching_lines(&content, term);
+                (!matches.is_empty()).then_some(SearchHit { pak, matches })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Lines of `body` containing `term` (case-insensitive), trimmed and capped
+fn matching_lines(body: &str, term: &str) -> Vec<String> {
+    let needle = term.to_ascii_lowercase();
+    body.lines()
+        .map(str::trim)
+        .filter(|line| line.to_ascii_lowercase().contains(&needle))
+        .take(MAX_MATCH_LINES)
+        .map(|line| line.chars().take(100).collect())
+        .collect()
+}
+
+/// Highlight case-insensitive occurrences of `term` in `line`
+fn highlight(line: &str, term: &str, style: &Style) -> String {
+    if term.is_empty() {
+        return line.to_string();
+    }
+    // ASCII lowercasing keeps byte offsets aligned with the original
+    let haystack = line.to_ascii_lowercase();
+    let needle = term.to_ascii_lowercase();
+
+    let mut out = String::with_capacity(line.len());
+    let mut last = 0;
+    for (start, _) in haystack.match_indices(&needle) {
+        let end = start + needle.len();
+        out.push_str(&line[last..start]);
+        out.push_str(&style.bold_yellow(&line[start..end]));
+        last = end;
+    }
+    out.push_str(&line[last..]);
+    out
+}
+
+/// One-line summary of how many results are shown and whether more exist
+///
+/// Past the first page the shown results are given as a range, e.g.
+/// "Showing 11–20 of 42 results". The hint for seeing more follows the flag
+/// the user paged with: the next `--page` if one was given, else `--offset`.
+fn summary_line(
+    shown: usize,
+    offset: usize,
+    limit: usize,
+    page: Option<usize>,
+    total: Option<u64>,
+) -> String {
+    let noun = |n: u64| if n == 1 { "result" } else { "results" };
+    let range = if offset > 0 {
+        format!("{}–{}", offset + 1, offset + shown)
+    } else {
+        shown.to_string()
+    };
+    let next = match page {
+        Some(page) => format!("--page {}", page + 1),
+        None => format!("--offset {}", offset + shown),
+    };
+    match total {
+        Some(total) if ((offset + shown) as u64) < total => format!(
+            "Showing {} of {} {} (use --limit or {} to see more)",
+            range,
+            total,
+            noun(total),
+            next
+        ),
+        Some(total) => format!("Showing {} of {} {}", range, total, noun(total)),
+        None if shown >= limit => format!(
+            "Showing {} {} (more may be available, use {})",
+            range,
+            noun(shown as u64),
+            next
+        ),
+        None => format!("Showing {} {}", range, noun(shown as u64)),
+    }
 }
 
 /// Format large numbers with K/M suffixes
-fn format_count(n: i64) -> String {
+pub(super) fn format_count(n: i64) -> String {
     if n >= 1_000_000 {
         format!("{:.1}M", n as f64 / 1_000_000.0)
     } else if n >= 1_000 {
@@ -82,3 +481,312 @@ fn format_count(n: i64) -> String {
         n.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use paks_api::test_support::pak_json;
+    use paks_api::{ApiError, PakContentResponse};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    /// Content source serving SKILL.md bodies keyed by URI
+    struct MockContent(HashMap<String, String>);
+
+    impl ContentSource for MockContent {
+        async fn fetch(&self, uri: &str) -> Result<PakContentResponse, ApiError> {
+            self.0
+                .get(uri)
+                .map(|body| PakContentResponse {
+                    uri: uri.to_string(),
+                    content: PakContent::File {
+                        content: body.clone(),
+                    },
+                })
+                .ok_or_else(|| ApiError::NotFound(uri.to_string()))
+        }
+    }
+
+    fn pak(owner: &str, name: &str) -> Pak {
+        serde_json::from_value(pak_json(owner, name)).unwrap()
+    }
+
+    fn mock_content() -> MockContent {
+        MockContent(HashMap::from([
+            (
+                "acme/deploy/SKILL.md".to_string(),
+                "# Deploy\nRun `kubectl apply -f k8s/`\n".to_string(),
+            ),
+            (
+                "acme/lint/SKILL.md".to_string(),
+                "# Lint\nRun the linter.\n".to_string(),
+            ),
+            (
+                "acme/rollout/SKILL.md".to_string(),
+                "Use KUBECTL rollout status\n".to_string(),
+            ),
+        ]))
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_content_keeps_matching_bodies() {
+        let paks = vec![
+            pak("acme", "deploy"),
+            pak("acme", "lint"),
+            pak("acme", "missing"),
+            pak("acme", "rollout"),
+        ];
+        let hits = filter_by_content(
+            &mock_content(),
+            paks,
+            "kubectl",
+            10,
+            &FanOut::new(4, Duration::ZERO),
+        )
+        .await;
+
+        let names: Vec<_> = hits.iter().map(|h| h.pak.name.as_str()).collect();
+        assert_eq!(names, ["deploy", "rollout"]);
+        assert_eq!(hits[0].matches, ["Run `kubectl apply -f k8s/`"]);
+    }
+
+    #[tokio::test]
+    async fn test_filter_by_content_respects_limit() {
+        let paks = vec![
+            pak("acme", "lint"),
+            pak("acme", "deploy"),
+            pak("acme", "rollout"),
+        ];
+        let hits = filter_by_content(
+            &mock_content(),
+            paks,
+            "kubectl",
+            2,
+            &FanOut::new(4, Duration::ZERO),
+        )
+        .await;
+
+        let names: Vec<_> = hits.iter().map(|h| h.pak.name.as_str()).collect();
+        assert_eq!(names, ["deploy"]);
+    }
+
+    #[test]
+    fn test_highlight_is_case_insensitive() {
+        assert_eq!(
+            highlight("Use KUBECTL or kubectl", "kubectl", &Style::new(true)),
+            "Use \x1b[1;33mKUBECTL\x1b[0m or \x1b[1;33mkubectl\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_render_hits_without_color_has_no_escapes() {
+        let mut tagged = pak("acme", "deploy");
+        tagged.description = Some("Deploy with kubectl".to_string());
+        tagged.tags = Some(vec!["k8s".to_string(), "ops".to_string()]);
+        tagged.license = Some("MIT".to_string());
+        let hits = [SearchHit {
+            pak: tagged,
+            matches: vec!["Run `kubectl apply`".to_string()],
+        }];
+
+        let style = Style::resolve(false, Some("1".into()), true);
+        let out = render_hits(&hits, Some("kubectl"), "Showing 1 result", &style);
+        assert!(!out.contains('\x1b'), "unexpected escape in {:?}", out);
+        assert!(out.contains("acme/deploy ↓0 MIT  #k8s #ops"));
+        assert!(out.contains("│ Run `kubectl apply`"));
+
+        let colored = render_hits(
+            &hits,
+            Some("kubectl"),
+            "Showing 1 result",
+            &Style::new(true),
+        );
+        assert!(colored.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_project_keeps_only_requested_fields() {
+        let fields = ["owner_name".to_string(), "total_downloads".to_string()];
+        validate_fields(&fields).unwrap();
+
+        let values = project(&[pak("acme", "deploy")], &fields).unwrap();
+
+        let keys: Vec<&String> = values[0].as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["owner_name", "total_downloads"]);
+        assert_eq!(values[0]["owner_name"], "acme");
+    }
+
+    #[test]
+    fn test_json_output_has_listing_fields() {
+        let mut tagged = pak("acme", "deploy");
+        tagged.description = Some("Deploy with kubectl".to_string());
+        tagged.tags = Some(vec!["k8s".to_string()]);
+        tagged.total_downloads = 42;
+
+        let values = project(&[tagged], &[]).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string_pretty(&values).unwrap()).unwrap();
+
+        let result = &json[0];
+        assert_eq!(result["owner_name"], "acme");
+        assert_eq!(result["name"], "deploy");
+        assert_eq!(result["description"], "Deploy with kubectl");
+        assert_eq!(result["tags"], serde_json::json!(["k8s"]));
+        assert_eq!(result["total_downloads"], 42);
+        assert!(!json.to_string().contains('\x1b'));
+    }
+
+    #[test]
+    fn test_validate_fields_rejects_unknown() {
+        let err = validate_fields(&["name".to_string(), "bogus".to_string()]).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Unknown field(s): bogus"));
+        assert!(message.contains("total_downloads"));
+    }
+
+    #[test]
+    fn test_pak_fields_match_schema() {
+        let value = serde_json::to_value(pak("acme", "deploy")).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut fields = PAK_FIELDS.to_vec();
+        keys.sort();
+        fields.sort();
+        assert_eq!(keys, fields);
+    }
+
+    #[test]
+    fn test_summary_line_with_total() {
+        assert_eq!(
+            summary_line(10, 0, 10, None, Some(42)),
+            "Showing 10 of 42 results (use --limit or --offset 10 to see more)"
+        );
+        assert_eq!(
+            summary_line(2, 40, 10, None, Some(42)),
+            "Showing 41–42 of 42 results"
+        );
+        assert_eq!(
+            summary_line(10, 10, 10, None, Some(42)),
+            "Showing 11–20 of 42 results (use --limit or --offset 20 to see more)"
+        );
+        assert_eq!(
+            summary_line(1, 0, 10, None, Some(1)),
+            "Showing 1 of 1 result"
+        );
+    }
+
+    #[test]
+    fn test_summary_line_without_total() {
+        assert_eq!(summary_line(3, 0, 10, None, None), "Showing 3 results");
+        assert_eq!(
+            summary_line(10, 10, 10, None, None),
+            "Showing 11–20 results (more may be available, use --offset 20)"
+        );
+    }
+
+    #[test]
+    fn test_summary_line_after_page() {
+        assert_eq!(
+            summary_line(10, 10, 10, Some(2), Some(42)),
+            "Showing 11–20 of 42 results (use --limit or --page 3 to see more)"
+        );
+        assert_eq!(
+            summary_line(10, 10, 10, Some(2), None),
+            "Showing 11–20 results (more may be available, use --page 3)"
+        );
+    }
+
+    fn search_args(query: Option<&str>, owner: Option<&str>) -> SearchArgs {
+        SearchArgs {
+            query: query.map(str::to_string),
+            owner: owner.map(str::to_string),
+            sort: None,
+            limit: 10,
+            offset: 0,
+            page: None,
+            in_content: None,
+            content_limit: 10,
+            no_cache: false,
+            jobs: None,
+            format: OutputFormat::Table,
+            fields: Vec::new(),
+            registry: None,
+            private: false,
+        }
+    }
+
+    #[test]
+    fn test_build_query_with_owner_and_sort() {
+        let mut args = search_args(Some("deploy"), Some("acme"));
+        args.sort = Some(PakSortBy::MostPopular);
+        args.offset = 20;
+
+        let query = build_query(&args).unwrap();
+        assert_eq!(query.query.as_deref(), Some("deploy"));
+        assert_eq!(query.owner.as_deref(), Some("acme"));
+        assert_eq!(query.sort_by, Some(PakSortBy::MostPopular));
+        assert_eq!(query.limit, Some(10));
+        assert_eq!(query.offset, Some(20));
+
+        let json = serde_json::to_value(&query).unwrap();
+        assert_eq!(json["sort_by"], "MOST_POPULAR");
+    }
+
+    #[test]
+    fn test_build_query_owner_only_lists_owner() {
+        let query = build_query(&search_args(None, Some("acme"))).unwrap();
+        assert_eq!(query.query, None);
+        assert_eq!(query.owner.as_deref(), Some("acme"));
+        assert_eq!(query.sort_by, None);
+        assert_eq!(query.offset, None);
+
+        let json = serde_json::to_value(&query).unwrap();
+        assert!(json.get("query").is_none());
+        assert!(json.get("sort_by").is_none());
+    }
+
+    #[test]
+    fn test_build_query_threads_offset_and_page() {
+        let mut args = search_args(Some("deploy"), None);
+        args.offset = 15;
+        assert_eq!(build_query(&args).unwrap().offset, Some(15));
+
+        // --page wins over --offset and counts in pages of --limit
+        args.page = Some(3);
+        assert_eq!(build_query(&args).unwrap().offset, Some(20));
+
+        args.page = Some(1);
+        assert_eq!(build_query(&args).unwrap().offset, None);
+
+        // Offsets that overflow are errors rather than wrapping
+        args.page = Some(usize::MAX);
+        let err = build_query(&args).unwrap_err().to_string();
+        assert!(err.contains("too large"), "{}", err);
+        args.page = None;
+        args.offset = u32::MAX as usize + 1;
+        assert!(build_query(&args).is_err());
+    }
+
+    #[test]
+    fn test_build_query_needs_query_or_owner() {
+        assert!(build_query(&search_args(None, None)).is_err());
+        assert!(build_query(&search_args(Some("  "), None)).is_err());
+    }
+
+    #[test]
+    fn test_registry_client_uses_override() {
+        let mut config = Config::default();
+        config.select_registry("https://paks.acme.dev").unwrap();
+
+        let client = registry_client(&config, None).unwrap();
+        assert_eq!(client.base_url(), "https://paks.acme.dev/");
+
+        let client = registry_client(&config, Some("http://localhost:4000")).unwrap();
+        assert_eq!(client.base_url(), "http://localhost:4000/");
+    }
+}
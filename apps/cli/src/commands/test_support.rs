@@ -0,0 +1,21 @@
+//! Fixtures shared by command tests
+
+use std::path::Path;
+
+/// Create a minimal skill at `skills_dir/dir_name` as an install would
+///
+/// The frontmatter name is the part after `owner--`, so `acme--deploy`
+/// holds a skill called `deploy`.
+pub fn install_fake_skill(skills_dir: &Path, dir_name: &str, version: &str) {
+    let dir = skills_dir.join(dir_name);
+    std::fs::create_dir_all(&dir).unwrap();
+    let name = dir_name.rsplit("--").next().unwrap_or(dir_name);
+    std::fs::write(
+        dir.join("SKILL.md"),
+        format!(
+            "---\nname: {}\ndescription: test\nmetadata:\n  version: \"{}\"\n---\n",
+            name, version
+        ),
+    )
+    .unwrap();
+}
@@ -0,0 +1,608 @@
+//! Tree command - print the directory tree of a skill, or with `--deps` its
+//! dependency tree
+
+use anyhow::{Context, Result, bail};
+use paks_api::{ApiError, ContentItemType, PakContent, SkillRef};
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use super::core::client::registry_client;
+use super::core::config::Config;
+use super::core::content::ContentSource;
+use super::core::paksignore;
+use super::core::size::format_size;
+use super::core::skill::{Skill, SkillDependency, parse_version};
+use super::install::{CloneGuard, CloneOptions, clone_git_repo};
+
+pub struct TreeArgs {
+    /// Skill path or registry reference (owner/name[@version])
+    pub skill: String,
+    /// Maximum depth to descend (unlimited if not set)
+    pub depth: Option<usize>,
+    /// Show the dependency tree instead of the files
+    pub deps: bool,
+}
+
+/// A file or directory in the rendered tree
+#[derive(Debug, Default)]
+struct TreeNode {
+    name: String,
+    is_dir: bool,
+    size: Option<u64>,
+    children: Vec<TreeNode>,
+}
+
+/// Flat entry collected from a local walk or registry listing
+pub(super) struct TreeEntry {
+    pub(super) components: Vec<String>,
+    pub(super) is_dir: bool,
+    pub(super) size: Option<u64>,
+}
+
+pub async fn run(args: TreeArgs) -> Result<()> {
+    if args.deps {
+        return run_deps(args).await;
+    }
+    let path = Path::new(&args.skill);
+
+    let (label, entries) = if path.exists() {
+        let label = path
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| args.skill.clone());
+        (label, local_entries(path, args.depth)?)
+    } else {
+        let skill_ref = SkillRef::parse(&args.skill).with_context(|| {
+            format!(
+                "'{}' is neither a local path nor a registry reference",
+                args.skill
+            )
+        })?;
+        let config = Config::load()?;
+        let client = registry_client(&config)?;
+        let entries = registry_entries(&client, &skill_ref.to_uri(), args.depth).await?;
+        (skill_ref.to_uri(), entries)
+    };
+
+    print!("{}", render(&label, &build_tree(entries)));
+    Ok(())
+}
+
+/// Collect entries below a local skill directory, honoring `.paksignore`
+pub(super) fn local_entries(root: &Path, depth: Option<usize>) -> Result<Vec<TreeEntry>> {
+    let mut entries = Vec::new();
+
+    for entry in paksignore::walk_builder(root).max_depth(depth).build() {
+        let entry = entry?;
+        if entry.depth() == 0 {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root)?;
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        let size = if is_dir {
+            None
+        } else {
+            entry.metadata().ok().map(|m| m.len())
+        };
+        entries.push(TreeEntry {
+            components: relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect(),
+            is_dir,
+            size,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Collect entries of a registry pak by walking its content listings
+pub(super) async fn registry_entries(
+    source: &impl ContentSource,
+    uri: &str,
+    depth: Option<usize>,
+) -> Result<Vec<TreeEntry>> {
+    let root = match source.fetch(uri).await {
+        Ok(response) => response.content,
+        Err(ApiError::NotFound(_)) => bail!("Skill '{}' not found in registry", uri),
+        Err(e) => bail!("Failed to fetch {}: {}", uri, e),
+    };
+    let PakContent::Directory { items } = root else {
+        bail!("Expected a directory listing for {}", uri);
+    };
+
+    let mut entries = Vec::new();
+    // Iterative walk to avoid recursive async calls
+    let mut pending = vec![(items, Vec::<String>::new())];
+
+    while let Some((items, prefix)) = pending.pop() {
+        for item in items {
+            let mut components = prefix.clone();
+            components.push(item.name.clone());
+            let is_dir = item.item_type == ContentItemType::Dir;
+
+            if is_dir && depth.is_none_or(|max| components.len() < max) {
+                let listing = source
+                    .fetch(&item.uri)
+                    .await
+                    .with_context(|| format!("Failed to fetch {}", item.uri))?;
+                if let PakContent::Directory { items } = listing.content {
+                    pending.push((items, components.clone()));
+                }
+            }
+
+            entries.push(TreeEntry {
+                components,
+                is_dir,
+                size: item.size.and_then(|s| u64::try_from(s).ok()),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Assemble flat entries into a tree sorted by name
+fn build_tree(entries: Vec<TreeEntry>) -> TreeNode {
+    let mut root = TreeNode {
+        is_dir: true,
+        ..Default::default()
+    };
+
+    for entry in entries {
+        let Some((last, parents)) = entry.components.split_last() else {
+            continue;
+        };
+        let mut node = &mut root;
+        for part in parents {
+            let index = match node.children.iter().position(|c| &c.name == part) {
+                Some(index) => index,
+                None => {
+                    node.children.push(TreeNode {
+                        name: part.clone(),
+                        is_dir: true,
+                        ..Default::default()
+                    });
+                    node.children.len() - 1
+                }
+            };
+            node = &mut node.children[index];
+        }
+        match node.children.iter_mut().find(|c| &c.name == last) {
+            Some(existing) => {
+                existing.is_dir = entry.is_dir;
+                existing.size = entry.size;
+            }
+            None => node.children.push(TreeNode {
+                name: last.clone(),
+                is_dir: entry.is_dir,
+                size: entry.size,
+                children: Vec::new(),
+            }),
+        }
+    }
+
+    sort_tree(&mut root);
+    root
+}
+
+fn sort_tree(node: &mut TreeNode) {
+    node.children.sort_by(|a, b| a.name.cmp(&b.name));
+    for child in &mut node.children {
+        sort_tree(child);
+    }
+}
+
+/// Render a tree with box-drawing characters
+fn render(label: &str, root: &TreeNode) -> String {
+    let mut out = format!("{}/\n", label.trim_end_matches('/'));
+    render_children(root, "", &mut out);
+    out
+}
+
+fn render_children(node: &TreeNode, prefix: &str, out: &mut String) {
+    let count = node.children.len();
+    for (i, child) in node.children.iter().enumerate() {
+        let last = i + 1 == count;
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        out.push_str(prefix);
+        out.push_str(branch);
+        out.push_str(&child.name);
+        if child.is_dir {
+            out.push('/');
+        } else if let Some(size) = child.size {
+            out.push_str(&format!(" ({})", format_size(size)));
+        }
+        out.push('\n');
+
+        if child.is_dir {
+            render_children(child, &format!("{}{}", prefix, indent), out);
+        }
+    }
+}
+
+/// Where a skill in the dependency tree comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DepSource {
+    Registry,
+    Git,
+    Local,
+}
+
+impl fmt::Display for DepSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DepSource::Registry => "registry",
+            DepSource::Git => "git",
+            DepSource::Local => "local",
+        })
+    }
+}
+
+/// A skill in the dependency tree
+#[derive(Debug, PartialEq, Eq)]
+struct DepNode {
+    name: String,
+    /// Resolved version, or the requirement if the dependency couldn't be resolved
+    version: Option<String>,
+    source: DepSource,
+    state: DepState,
+    children: Vec<DepNode>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum DepState {
+    Resolved,
+    /// Already on the path from the root; not expanded again
+    Cycle,
+    Unresolved(String),
+}
+
+/// A skill whose SKILL.md has been loaded, with what's needed to resolve its
+/// own dependencies
+struct ResolvedSkill {
+    skill: Skill,
+    source: DepSource,
+    /// Identifies the skill for cycle detection
+    key: String,
+    /// Directory local path dependencies are relative to
+    dir: Option<PathBuf>,
+    /// Keeps a git dependency's clone around while its dependencies resolve
+    _clone: Option<CloneGuard>,
+}
+
+async fn run_deps(args: TreeArgs) -> Result<()> {
+    let config = Config::load()?;
+    let client = registry_client(&config)?;
+
+    let path = Path::new(&args.skill);
+    let root = if path.exists() {
+        resolve_local(path)?
+    } else {
+        let skill_ref = SkillRef::parse(&args.skill).with_context(|| {
+            format!(
+                "'{}' is neither a local path nor a registry reference",
+                args.skill
+            )
+        })?;
+        resolve_registry(&client, &skill_ref).await?
+    };
+
+    let tree = DepNode {
+        name: root.skill.name().to_string(),
+        version: Some(root.skill.version().to_string()),
+        source: root.source,
+        state: DepState::Resolved,
+        children: expand(&client, &root, &mut vec![root.key.clone()], args.depth).await,
+    };
+    print!("{}", render_deps(&tree));
+    Ok(())
+}
+
+/// Dependency nodes of `parent`, `remaining` levels deep (unlimited if `None`)
+///
+/// `ancestors` holds the keys of the skills on the path from the root, so a
+/// dependency that refers back to one of them is marked as a cycle.
+fn expand<'a>(
+    source: &'a impl ContentSource,
+    parent: &'a ResolvedSkill,
+    ancestors: &'a mut Vec<String>,
+    remaining: Option<usize>,
+) -> Pin<Box<dyn Future<Output = Vec<DepNode>> + 'a>> {
+    Box::pin(async move {
+        if remaining == Some(0) {
+            return Vec::new();
+        }
+
+        let mut nodes = Vec::new();
+        for dep in &parent.skill.frontmatter.dependencies {
+            let node = match resolve_dependency(source, parent, dep).await {
+                Ok(resolved) => {
+                    let (state, children) = if ancestors.contains(&resolved.key) {
+                        (DepState::Cycle, Vec::new())
+                    } else {
+                        ancestors.push(resolved.key.clone());
+                        let children =
+                            expand(source, &resolved, ancestors, remaining.map(|r| r - 1)).await;
+                        ancestors.pop();
+                        (DepState::Resolved, children)
+                    };
+                    DepNode {
+                        name: dep.name.clone(),
+                        version: Some(resolved.skill.version().to_string()),
+                        source: resolved.source,
+                        state,
+                        children,
+                    }
+                }
+                Err(e) => DepNode {
+                    name: dep.name.clone(),
+                    version: dep.version.clone(),
+                    source: dependency_source(dep),
+                    state: DepState::Unresolved(format!("{:#}", e)),
+                    children: Vec::new(),
+                },
+            };
+            nodes.push(node);
+        }
+        nodes
+    })
+}
+
+fn dependency_source(dep: &SkillDependency) -> DepSource {
+    if dep.path.is_some() {
+        DepSource::Local
+    } else if dep.git.is_some() {
+        DepSource::Git
+    } else {
+        DepSource::Registry
+    }
+}
+
+/// Load the SKILL.md of `dep`, a dependency declared by `parent`
+async fn resolve_dependency(
+    source: &impl ContentSource,
+    parent: &ResolvedSkill,
+    dep: &SkillDependency,
+) -> Result<ResolvedSkill> {
+    match dependency_source(dep) {
+        DepSource::Local => {
+            let Some(dir) = &parent.dir else {
+                bail!("local path dependencies are only supported for local or git skills");
+            };
+            resolve_local(&dir.join(dep.path.as_deref().unwrap_or_default()))
+        }
+        DepSource::Git => {
+            let url = dep.git.as_deref().unwrap_or_default();
+            let (path, clone) = clone_git_repo(
+                url,
+                dep.git_ref.as_deref(),
+                None,
+                None,
+                None,
+                CloneOptions::default(),
+            )
+            .await?;
+            Ok(ResolvedSkill {
+                skill: Skill::load(&path)?,
+                source: DepSource::Git,
+                key: format!("{}#{}", url, dep.git_ref.as_deref().unwrap_or("HEAD")),
+                dir: Some(path),
+                _clone: Some(clone),
+            })
+        }
+        DepSource::Registry => {
+            // An exact version pins the lookup; a range resolves to the latest
+            let reference = match dep.version.as_deref().filter(|v| parse_version(v).is_ok()) {
+                Some(version) => format!("{}@{}", dep.name, version),
+                None => dep.name.clone(),
+            };
+            resolve_registry(source, &SkillRef::parse(&reference)?).await
+        }
+    }
+}
+
+fn resolve_local(path: &Path) -> Result<ResolvedSkill> {
+    let dir = path
+        .canonicalize()
+        .with_context(|| format!("{} does not exist", path.display()))?;
+    Ok(ResolvedSkill {
+        skill: Skill::load(&dir)?,
+        source: DepSource::Local,
+        key: dir.display().to_string(),
+        dir: Some(dir),
+        _clone: None,
+    })
+}
+
+async fn resolve_registry(
+    source: &impl ContentSource,
+    skill_ref: &SkillRef,
+) -> Result<ResolvedSkill> {
+    let uri = skill_ref.to_uri();
+    let skill_md = match source.fetch(&format!("{}/SKILL.md", uri)).await {
+        Ok(response) => response.content,
+        Err(ApiError::NotFound(_)) => bail!("Skill '{}' not found in registry", uri),
+        Err(e) => bail!("Failed to fetch {}: {}", uri, e),
+    };
+    let PakContent::File { content } = skill_md else {
+        bail!("SKILL.md in {} is not a file", uri);
+    };
+    Ok(ResolvedSkill {
+        skill: Skill::from_skill_md(PathBuf::from(&uri), &content)
+            .with_context(|| format!("Invalid SKILL.md in {}", uri))?,
+        source: DepSource::Registry,
+        key: format!("{}/{}", skill_ref.account(), skill_ref.name()),
+        dir: None,
+        _clone: None,
+    })
+}
+
+/// Render a dependency tree, one `name@version (source)` per line
+fn render_deps(root: &DepNode) -> String {
+    let mut out = format!("{}\n", dep_label(root));
+    render_dep_children(root, "", &mut out);
+    out
+}
+
+fn render_dep_children(node: &DepNode, prefix: &str, out: &mut String) {
+    let count = node.children.len();
+    for (i, child) in node.children.iter().enumerate() {
+        let (branch, indent) = if i + 1 == count {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        out.push_str(&format!("{}{}{}\n", prefix, branch, dep_label(child)));
+        render_dep_children(child, &format!("{}{}", prefix, indent), out);
+    }
+}
+
+fn dep_label(node: &DepNode) -> String {
+    let mut label = match &node.version {
+        Some(version) => format!("{}@{} ({})", node.name, version, node.source),
+        None => format!("{} ({})", node.name, node.source),
+    };
+    match &node.state {
+        DepState::Resolved => {}
+        DepState::Cycle => label.push_str(" [cycle]"),
+        DepState::Unresolved(reason) => label.push_str(&format!(" [unresolved: {}]", reason)),
+    }
+    label
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("SKILL.md"), "x".repeat(2048)).unwrap();
+        std::fs::write(root.join(".paksignore"), "*.log\n").unwrap();
+        std::fs::write(root.join("debug.log"), "noise").unwrap();
+        std::fs::create_dir_all(root.join("scripts")).unwrap();
+        std::fs::write(root.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        std::fs::create_dir_all(root.join("references/guides")).unwrap();
+        std::fs::write(root.join("references/api.md"), "# API").unwrap();
+        std::fs::write(root.join("references/guides/intro.md"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_render_local_tree() {
+        let dir = fixture();
+        let tree = build_tree(local_entries(dir.path(), None).unwrap());
+
+        assert_eq!(
+            render("my-skill", &tree),
+            "my-skill/\n\
+             ├── .paksignore (6 B)\n\
+             ├── SKILL.md (2.0 KB)\n\
+             ├── references/\n\
+             │   ├── api.md (5 B)\n\
+             │   └── guides/\n\
+             │       └── intro.md (0 B)\n\
+             └── scripts/\n\
+             \x20   └── run.sh (10 B)\n"
+        );
+    }
+
+    #[test]
+    fn test_render_local_tree_with_depth() {
+        let dir = fixture();
+        let tree = build_tree(local_entries(dir.path(), Some(1)).unwrap());
+
+        assert_eq!(
+            render("my-skill", &tree),
+            "my-skill/\n\
+             ├── .paksignore (6 B)\n\
+             ├── SKILL.md (2.0 KB)\n\
+             ├── references/\n\
+             └── scripts/\n"
+        );
+    }
+
+    /// Content source serving SKILL.md bodies keyed by URI
+    struct MockContent(std::collections::HashMap<String, String>);
+
+    impl ContentSource for MockContent {
+        async fn fetch(&self, uri: &str) -> Result<paks_api::PakContentResponse, ApiError> {
+            self.0
+                .get(uri)
+                .map(|content| paks_api::PakContentResponse {
+                    uri: uri.to_string(),
+                    content: PakContent::File {
+                        content: content.clone(),
+                    },
+                })
+                .ok_or_else(|| ApiError::NotFound(uri.to_string()))
+        }
+    }
+
+    fn write_skill(dir: &Path, name: &str, dependencies: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!(
+                "---\nname: {}\ndescription: test\nmetadata:\n  version: 1.0.0\ndependencies:\n{}---\n",
+                name, dependencies
+            ),
+        )
+        .unwrap();
+    }
+
+    async fn dep_tree(source: &MockContent, path: &Path, depth: Option<usize>) -> String {
+        let root = resolve_local(path).unwrap();
+        let children = expand(source, &root, &mut vec![root.key.clone()], depth).await;
+        render_deps(&DepNode {
+            name: root.skill.name().to_string(),
+            version: Some(root.skill.version().to_string()),
+            source: root.source,
+            state: DepState::Resolved,
+            children,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_dependency_tree_marks_cycles() {
+        let dir = tempdir().unwrap();
+        write_skill(
+            &dir.path().join("app"),
+            "app",
+            "  - name: lib\n    path: ../lib\n  - name: acme/deploy\n    version: 2.0.0\n  - name: acme/missing\n",
+        );
+        write_skill(
+            &dir.path().join("lib"),
+            "lib",
+            "  - name: app\n    path: ../app\n",
+        );
+        let source = MockContent(std::collections::HashMap::from([(
+            "acme/deploy@2.0.0/SKILL.md".to_string(),
+            "---\nname: deploy\ndescription: test\nmetadata:\n  version: 2.0.0\n---\n".to_string(),
+        )]));
+
+        assert_eq!(
+            dep_tree(&source, &dir.path().join("app"), None).await,
+            "app@1.0.0 (local)\n\
+             ├── lib@1.0.0 (local)\n\
+             │   └── app@1.0.0 (local) [cycle]\n\
+             ├── acme/deploy@2.0.0 (registry)\n\
+             └── acme/missing (registry) [unresolved: Skill 'acme/missing' not found in registry]\n"
+        );
+
+        let shallow = dep_tree(&source, &dir.path().join("app"), Some(1)).await;
+        assert!(shallow.contains("├── lib@1.0.0 (local)\n├── acme/deploy"));
+        assert!(!shallow.contains("[cycle]"));
+    }
+}
@@ -0,0 +1,190 @@
+//! Update command - upgrade installed registry skills to their latest versions
+
+use anyhow::{Context, Result, bail};
+use paks_api::{ApiError, PaksClient};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::core::client::registry_client;
+use super::core::config::Config;
+use super::core::fanout::FanOut;
+use super::core::manifest::{self, DependencySpec};
+use super::core::skill::Skill;
+use super::core::update::{self, InstalledVersion, UpdateDecision, UpdatePlan};
+use super::install::{self, InstallArgs, install_target};
+
+pub struct UpdateArgs {
+    /// Only update these skills (`owner/name`); all registry skills if empty
+    pub skills: Vec<String>,
+    pub agent: Option<String>,
+    /// Print the plan without installing anything
+    pub dry_run: bool,
+    /// Registry requests in flight at once
+    pub jobs: Option<usize>,
+    /// Don't ask before installing paks over `confirm_install_over_mb`
+    pub yes: bool,
+}
+
+pub async fn run(args: UpdateArgs) -> Result<()> {
+    let config = Config::load()?;
+    let (skills_dir, _) = install_target(None, args.agent.as_deref())?;
+
+    // Exact constraints in the project's paks.toml hold skills back
+    let dependencies = match manifest::find_project_root(&std::env::current_dir()?) {
+        Some(root) => manifest::load_dependencies(&root)?,
+        None => BTreeMap::new(),
+    };
+
+    let mut installed = installed_registry_skills(&skills_dir, &dependencies);
+    if !args.skills.is_empty() {
+        for name in &args.skills {
+            if !installed.iter().any(|s| &s.name == name) {
+                bail!("'{}' is not installed from the registry", name);
+            }
+        }
+        installed.retain(|s| args.skills.contains(&s.name));
+    }
+    if installed.is_empty() {
+        println!("No registry skills installed in {}", skills_dir.display());
+        return Ok(());
+    }
+
+    let client = registry_client(&config)?;
+    let fanout = FanOut::from_config(&config, args.jobs);
+    let registry = latest_versions(&client, &installed, &fanout).await?;
+    let plan = update::plan_updates(&installed, &registry);
+
+    print_plan(&plan);
+
+    let upgrades: Vec<&UpdatePlan> = plan
+        .iter()
+        .filter(|p| matches!(p.decision, UpdateDecision::Upgrade { .. }))
+        .collect();
+    if args.dry_run || upgrades.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    for entry in upgrades {
+        install::run(InstallArgs {
+            sources: vec![entry.name.clone()],
+            dir: Some(skills_dir.to_string_lossy().into_owned()),
+            force: true,
+            yes: args.yes,
+            git_depth: 1,
+            ..Default::default()
+        })
+        .await
+        .with_context(|| format!("Failed to update {}", entry.name))?;
+    }
+
+    Ok(())
+}
+
+/// Installed skills in `skills_dir` that came from the registry
+///
+/// Registry installs live in `owner--name` directories; anything else was
+/// installed from git or a local path and is skipped.
+pub fn installed_registry_skills(
+    skills_dir: &Path,
+    dependencies: &BTreeMap<String, DependencySpec>,
+) -> Vec<InstalledVersion> {
+    let Ok(entries) = std::fs::read_dir(skills_dir) else {
+        return Vec::new();
+    };
+
+    let mut skills: Vec<InstalledVersion> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = update::registry_name(&entry.file_name().to_string_lossy())?;
+            let skill = Skill::load(&entry.path()).ok()?;
+            let pinned = dependencies
+                .get(&name)
+                .is_some_and(DependencySpec::is_pinned);
+            Some(InstalledVersion {
+                name,
+                version: skill.version().to_string(),
+                pinned,
+            })
+        })
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
+}
+
+/// Latest registry version of each installed skill; unknown skills are omitted
+pub(super) async fn latest_versions(
+    client: &PaksClient,
+    installed: &[InstalledVersion],
+    fanout: &FanOut,
+) -> Result<HashMap<String, String>> {
+    let names: Vec<String> = installed.iter().map(|s| s.name.clone()).collect();
+    let results = fanout
+        .run(names.clone(), |name| async move {
+            client.get_pak_install(&name).await
+        })
+        .await;
+
+    let mut versions = HashMap::new();
+    for (name, result) in names.into_iter().zip(results) {
+        match result {
+            Ok(info) => {
+                versions.insert(name, info.version.version);
+            }
+            Err(ApiError::NotFound(_)) => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to look up {}", name)),
+        }
+    }
+    Ok(versions)
+}
+
+fn print_plan(plan: &[UpdatePlan]) {
+    for entry in plan {
+        let marker = match entry.decision {
+            UpdateDecision::Upgrade { .. } => "↑",
+            UpdateDecision::Unchanged => "✓",
+            UpdateDecision::Pinned { .. } | UpdateDecision::NotInRegistry => "-",
+        };
+        println!("  {} {}", marker, entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::install_fake_skill;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_installed_registry_skills_skips_non_registry() {
+        let skills = tempdir().unwrap();
+        install_fake_skill(skills.path(), "acme--deploy", "1.0.0");
+        install_fake_skill(skills.path(), "acme--lint", "2.0.0");
+        install_fake_skill(skills.path(), "from-git", "0.1.0");
+
+        let dependencies = BTreeMap::from([(
+            "acme/lint".to_string(),
+            DependencySpec {
+                version: "=2.0.0".to_string(),
+                source: "registry".to_string(),
+            },
+        )]);
+        let installed = installed_registry_skills(skills.path(), &dependencies);
+
+        assert_eq!(
+            installed,
+            [
+                InstalledVersion {
+                    name: "acme/deploy".to_string(),
+                    version: "1.0.0".to_string(),
+                    pinned: false,
+                },
+                InstalledVersion {
+                    name: "acme/lint".to_string(),
+                    version: "2.0.0".to_string(),
+                    pinned: true,
+                },
+            ]
+        );
+    }
+}
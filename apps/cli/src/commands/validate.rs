@@ -1,49 +1,202 @@
 //! Validate command - validate a skill's structure and SKILL.md
+//!
+//! Validation only reads the skill directory: it never touches the network
+//! or runs git, so it works in sandboxes. Checks that need reference data
+//! (like the SPDX license list) use copies bundled into the binary.
 
 use anyhow::{Result, bail};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
 
-use super::core::skill::Skill;
+use super::core::offline;
+use super::core::output::OutputFormat;
+use super::core::skill::{DEFAULT_VERSION, Diagnostic, Skill, SkillFrontmatter};
+use super::core::spdx;
+use super::core::spec::{self, SpecRules};
 
 pub struct ValidateArgs {
     pub path: String,
     pub strict: bool,
+    /// Agent Skills spec version to validate against (latest if unset)
+    pub spec_version: Option<String>,
+    pub format: OutputFormat,
+    /// Correct mechanical frontmatter problems in place before validating
+    pub fix: bool,
 }
 
-pub async fn run(args: ValidateArgs) -> Result<()> {
-    let skill_path = Path::new(&args.path);
+/// A correction `--fix` made to the frontmatter
+#[derive(Debug, PartialEq, Eq)]
+struct Fix {
+    field: &'static str,
+    /// `None` if the field was missing
+    before: Option<String>,
+    after: String,
+}
+
+/// `name` lowercased, with runs of hyphens collapsed and leading and
+/// trailing hyphens trimmed
+fn fixed_name(name: &str) -> String {
+    let mut fixed = String::with_capacity(name.len());
+    for c in name.to_lowercase().chars() {
+        if c == '-' && fixed.ends_with('-') {
+            continue;
+        }
+        fixed.push(c);
+    }
+    fixed.trim_matches('-').to_string()
+}
 
-    // Load and parse the skill
+/// Apply the safe fixes to `frontmatter`, returning what changed
+///
+/// Only mechanical problems are fixed. Anything that needs a judgement call,
+/// like an over-long description, is left to the author.
+fn fix_frontmatter(frontmatter: &mut SkillFrontmatter) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+
+    let name = fixed_name(&frontmatter.name);
+    if !name.is_empty() && name != frontmatter.name {
+        let before = std::mem::replace(&mut frontmatter.name, name.clone());
+        fixes.push(Fix {
+            field: "name",
+            before: Some(before),
+            after: name,
+        });
+    }
+
+    let metadata = frontmatter.metadata.get_or_insert_with(HashMap::new);
+    if !metadata.contains_key("version") {
+        metadata.insert("version".to_string(), DEFAULT_VERSION.to_string());
+        fixes.push(Fix {
+            field: "metadata.version",
+            before: None,
+            after: DEFAULT_VERSION.to_string(),
+        });
+    }
+
+    fixes
+}
+
+/// Fix the skill at `skill_path` in place, saving it only if anything changed
+///
+/// A skill that can't be loaded is left alone for validation to report.
+fn fix_skill(skill_path: &Path) -> Result<Vec<Fix>> {
+    let Ok(mut skill) = Skill::load(skill_path) else {
+        return Ok(Vec::new());
+    };
+    let fixes = fix_frontmatter(&mut skill.frontmatter);
+    if !fixes.is_empty() {
+        skill.save()?;
+    }
+    Ok(fixes)
+}
+
+/// The fixes as a diff of the changed fields
+fn render_fixes(fixes: &[Fix]) -> String {
+    let mut out = String::from("Fixed SKILL.md:\n");
+    for fix in fixes {
+        if let Some(before) = &fix.before {
+            out.push_str(&format!("  - {}: {}\n", fix.field, before));
+        }
+        out.push_str(&format!("  + {}: {}\n", fix.field, fix.after));
+    }
+    out
+}
+
+/// Warning for a license that is neither an SPDX expression nor a bundled file
+fn check_license(skill_path: &Path, license: &str) -> Option<Diagnostic> {
+    // The spec allows pointing at a license file shipped with the skill
+    if skill_path.join(license).is_file() {
+        return None;
+    }
+    let unknown = spdx::unknown_ids(license);
+    if unknown.is_empty() {
+        return None;
+    }
+    Some(Diagnostic::new(
+        "license.unknown",
+        format!(
+            "License '{}' is not a known SPDX expression (unknown: {}; SPDX list {})",
+            license,
+            unknown.join(", "),
+            spdx::list_version()
+        ),
+    ))
+}
+
+/// Warning for a skill without `metadata.version`, which publishing needs
+fn version_warning(skill: &Skill) -> Option<Diagnostic> {
+    skill.version_opt().is_none().then(|| {
+        Diagnostic::new(
+            "version.missing",
+            "No version specified in metadata - required for publishing",
+        )
+    })
+}
+
+/// Summary of the validated skill
+#[derive(Debug, Serialize)]
+struct SkillSummary {
+    name: String,
+    version: Option<String>,
+    description: String,
+    license: Option<String>,
+    keywords: Vec<String>,
+}
+
+/// Everything `validate` found, as printed by `--format json` or `yaml`
+#[derive(Debug, Serialize)]
+struct ValidationReport {
+    valid: bool,
+    errors: Vec<Diagnostic>,
+    warnings: Vec<Diagnostic>,
+    /// `None` if the skill couldn't be loaded
+    skill: Option<SkillSummary>,
+    /// Optional directories that were checked, e.g. `scripts/ (3 files)`
+    #[serde(skip)]
+    checked: Vec<String>,
+}
+
+/// Validate the skill at `skill_path` against `rules`
+///
+/// With `strict`, any warning makes the skill invalid.
+fn validate_skill(skill_path: &Path, rules: &SpecRules, strict: bool) -> ValidationReport {
     let skill = match Skill::load(skill_path) {
         Ok(s) => s,
         Err(e) => {
-            println!("✗ Failed to load skill: {}", e);
-            bail!("Validation failed");
+            return ValidationReport {
+                valid: false,
+                errors: vec![Diagnostic::new(
+                    "skill.load_failed",
+                    format!("Failed to load skill: {}", e),
+                )],
+                warnings: Vec::new(),
+                skill: None,
+                checked: Vec::new(),
+            };
         }
     };
 
-    println!("Validating skill: {}", skill.name());
-
-    let mut has_errors = false;
+    let mut errors = Vec::new();
     let mut warnings = Vec::new();
+    let mut checked = Vec::new();
 
     // Validate frontmatter
-    match skill.frontmatter.validate() {
+    match skill.frontmatter.validate_for(rules) {
         Ok(w) => warnings.extend(w),
-        Err(e) => {
-            println!("  ✗ Frontmatter error: {}", e);
-            has_errors = true;
-        }
+        Err(diagnostic) => errors.push(diagnostic),
     }
 
     // Check for version in metadata (recommended for publishing)
-    if skill.version_opt().is_none() {
-        warnings.push("No version specified in metadata - required for publishing".to_string());
-    }
+    warnings.extend(version_warning(&skill));
 
     // Check for license (recommended)
-    if skill.frontmatter.license.is_none() {
-        warnings.push("No license specified - recommended for sharing".to_string());
+    match &skill.frontmatter.license {
+        Some(license) => warnings.extend(check_license(skill_path, license)),
+        None => warnings.push(Diagnostic::new(
+            "license.missing",
+            "No license specified - recommended for sharing",
+        )),
     }
 
     // Check optional directories structure
@@ -52,9 +205,12 @@ pub async fn run(args: ValidateArgs) -> Result<()> {
         if let Ok(entries) = std::fs::read_dir(&scripts_dir) {
             let count = entries.count();
             if count == 0 {
-                warnings.push("scripts/ directory is empty".to_string());
+                warnings.push(Diagnostic::new(
+                    "scripts.empty",
+                    "scripts/ directory is empty",
+                ));
             } else {
-                println!("  ✓ scripts/ ({} files)", count);
+                checked.push(format!("scripts/ ({} files)", count));
             }
         }
     }
@@ -64,9 +220,12 @@ pub async fn run(args: ValidateArgs) -> Result<()> {
         if let Ok(entries) = std::fs::read_dir(&refs_dir) {
             let count = entries.count();
             if count == 0 {
-                warnings.push("references/ directory is empty".to_string());
+                warnings.push(Diagnostic::new(
+                    "references.empty",
+                    "references/ directory is empty",
+                ));
             } else {
-                println!("  ✓ references/ ({} files)", count);
+                checked.push(format!("references/ ({} files)", count));
             }
         }
     }
@@ -82,43 +241,285 @@ pub async fn run(args: ValidateArgs) -> Result<()> {
                 })
                 .count();
             if count == 0 {
-                warnings.push("assets/ directory is empty".to_string());
+                warnings.push(Diagnostic::new(
+                    "assets.empty",
+                    "assets/ directory is empty",
+                ));
             } else {
-                println!("  ✓ assets/ ({} files)", count);
+                checked.push(format!("assets/ ({} files)", count));
             }
         }
     }
 
-    // Print warnings
-    for warning in &warnings {
-        println!("  ⚠ {}", warning);
-    }
+    // Files the instructions mention, and files they don't
+    warnings.extend(skill.validate_structure());
 
     // In strict mode, warnings are errors
-    if args.strict && !warnings.is_empty() {
-        has_errors = true;
+    let valid = errors.is_empty() && (!strict || warnings.is_empty());
+
+    ValidationReport {
+        valid,
+        errors,
+        warnings,
+        skill: Some(SkillSummary {
+            name: skill.name().to_string(),
+            version: skill.version_opt().map(str::to_string),
+            description: skill.frontmatter.description.clone(),
+            license: skill.frontmatter.license.clone(),
+            keywords: skill.frontmatter.keywords.clone(),
+        }),
+        checked,
     }
+}
 
-    if has_errors {
-        println!("\n✗ Validation failed");
+pub async fn run(args: ValidateArgs) -> Result<()> {
+    let skill_path = Path::new(&args.path);
+    let rules = spec::rules(args.spec_version.as_deref())?;
+    if args.fix {
+        let fixes = fix_skill(skill_path)?;
+        if !fixes.is_empty() {
+            // Keep stdout parseable for json and yaml
+            match args.format {
+                OutputFormat::Table => println!("{}", render_fixes(&fixes)),
+                OutputFormat::Json | OutputFormat::Yaml => eprintln!("{}", render_fixes(&fixes)),
+            }
+        }
+    }
+    let report = validate_skill(skill_path, rules, args.strict);
+
+    match args.format {
+        OutputFormat::Table => print_report(&report, rules),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml_ng::to_string(&report)?),
+    }
+
+    if !report.valid {
         bail!("Validation failed");
-    } else if warnings.is_empty() {
+    }
+    Ok(())
+}
+
+fn print_report(report: &ValidationReport, rules: &SpecRules) {
+    let Some(skill) = &report.skill else {
+        for error in &report.errors {
+            println!("✗ {}", error);
+        }
+        return;
+    };
+
+    println!(
+        "Validating skill: {} (Agent Skills spec {})",
+        skill.name, rules.version
+    );
+    if offline::is_offline() {
+        println!("  Offline: using bundled reference data only");
+    }
+
+    for error in &report.errors {
+        println!("  ✗ Frontmatter error: {}", error);
+    }
+    for checked in &report.checked {
+        println!("  ✓ {}", checked);
+    }
+
+    // Print warnings
+    for warning in &report.warnings {
+        println!("  ⚠ {}", warning);
+    }
+
+    if !report.valid {
+        println!("\n✗ Validation failed");
+        return;
+    } else if report.warnings.is_empty() {
         println!("\n✓ Skill is valid");
     } else {
-        println!("\n✓ Skill is valid ({} warnings)", warnings.len());
+        println!("\n✓ Skill is valid ({} warnings)", report.warnings.len());
     }
 
     // Print skill summary
     println!("\nSkill Summary:");
-    println!("  Name: {}", skill.name());
-    println!("  Version: {}", skill.version());
-    println!("  Description: {}", skill.frontmatter.description);
-    if let Some(license) = &skill.frontmatter.license {
+    println!("  Name: {}", skill.name);
+    println!(
+        "  Version: {}",
+        skill.version.as_deref().unwrap_or(DEFAULT_VERSION)
+    );
+    println!("  Description: {}", skill.description);
+    if let Some(license) = &skill.license {
         println!("  License: {}", license);
     }
-    if !skill.frontmatter.keywords.is_empty() {
-        println!("  Keywords: {}", skill.frontmatter.keywords.join(", "));
+    if !skill.keywords.is_empty() {
+        println!("  Keywords: {}", skill.keywords.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill_with_license(license: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            format!(
+                "---\nname: demo\ndescription: A demo skill for validation tests\n\
+                 license: {}\nmetadata:\n  version: 1.0.0\n---\n\n# Demo\n",
+                license
+            ),
+        )
+        .unwrap();
+        dir
     }
 
-    Ok(())
+    #[test]
+    fn test_check_license() {
+        let dir = skill_with_license("MIT");
+        assert_eq!(check_license(dir.path(), "Apache-2.0 OR MIT"), None);
+
+        let warning = check_license(dir.path(), "Acme Public License").unwrap();
+        assert_eq!(warning.code, "license.unknown");
+        assert!(warning.message.contains("unknown: Acme, Public, License"));
+
+        std::fs::write(dir.path().join("LICENSE.txt"), "terms").unwrap();
+        assert_eq!(check_license(dir.path(), "LICENSE.txt"), None);
+    }
+
+    #[test]
+    fn test_version_warning() {
+        let dir = skill_with_license("MIT");
+        assert_eq!(version_warning(&Skill::load(dir.path()).unwrap()), None);
+
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: demo\ndescription: A demo skill without a version\n---\n\n# Demo\n",
+        )
+        .unwrap();
+        let warning = version_warning(&Skill::load(dir.path()).unwrap()).unwrap();
+        assert!(warning.message.contains("No version specified"));
+    }
+
+    #[test]
+    fn test_report_json() {
+        let dir = skill_with_license("MIT");
+        let rules = spec::latest();
+        let report = validate_skill(dir.path(), rules, false);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["valid"], true);
+        assert_eq!(json["errors"], serde_json::json!([]));
+        assert_eq!(json["skill"]["name"], "demo");
+        assert_eq!(json["skill"]["version"], "1.0.0");
+        assert_eq!(json["skill"]["license"], "MIT");
+
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: Demo\ndescription: A demo skill with a bad name\n---\n",
+        )
+        .unwrap();
+        let report = validate_skill(dir.path(), rules, false);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["valid"], false);
+        assert_eq!(json["errors"][0]["code"], "name.invalid_chars");
+        let codes: Vec<_> = report.warnings.iter().map(|w| w.code).collect();
+        assert_eq!(codes, ["version.missing", "license.missing"]);
+
+        let report = validate_skill(&dir.path().join("missing"), rules, false);
+        assert_eq!(report.errors[0].code, "skill.load_failed");
+        assert!(report.skill.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_json_format_still_fails() {
+        let dir = skill_with_license("Acme Public License");
+        let err = run(ValidateArgs {
+            path: dir.path().to_string_lossy().into_owned(),
+            strict: true,
+            spec_version: None,
+            format: OutputFormat::Json,
+            fix: false,
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Validation failed");
+    }
+
+    #[test]
+    fn test_missing_script_fails_strict() {
+        let dir = skill_with_license("MIT");
+        let skill_md = dir.path().join("SKILL.md");
+        let content = std::fs::read_to_string(&skill_md).unwrap();
+        std::fs::write(&skill_md, content + "Run `scripts/deploy.sh` to deploy.\n").unwrap();
+
+        let rules = spec::latest();
+        let report = validate_skill(dir.path(), rules, false);
+        assert!(report.valid);
+        let codes: Vec<_> = report.warnings.iter().map(|w| w.code).collect();
+        assert_eq!(codes, ["structure.missing_file"]);
+
+        assert!(!validate_skill(dir.path(), rules, true).valid);
+    }
+
+    #[test]
+    fn test_fix_name() {
+        for (name, fixed) in [
+            ("My-Skill", "my-skill"),
+            ("-demo-", "demo"),
+            ("deploy--to---k8s", "deploy-to-k8s"),
+            ("--Bad--Name--", "bad-name"),
+            ("fine-name", "fine-name"),
+        ] {
+            assert_eq!(fixed_name(name), fixed, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_fix_skill() {
+        let dir = tempfile::tempdir().unwrap();
+        let long_description = "x".repeat(2000);
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            format!(
+                "---\nname: -My--Skill-\ndescription: {}\n---\n\n# Demo\n",
+                long_description
+            ),
+        )
+        .unwrap();
+
+        let fixes = fix_skill(dir.path()).unwrap();
+        assert_eq!(
+            fixes,
+            [
+                Fix {
+                    field: "name",
+                    before: Some("-My--Skill-".to_string()),
+                    after: "my-skill".to_string(),
+                },
+                Fix {
+                    field: "metadata.version",
+                    before: None,
+                    after: "0.1.0".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            render_fixes(&fixes),
+            "Fixed SKILL.md:\n  - name: -My--Skill-\n  + name: my-skill\n  + metadata.version: 0.1.0\n"
+        );
+
+        let skill = Skill::load(dir.path()).unwrap();
+        assert_eq!(skill.name(), "my-skill");
+        assert_eq!(skill.version_opt(), Some("0.1.0"));
+        // Not safely fixable, so left as it was
+        assert_eq!(skill.frontmatter.description, long_description);
+        assert_eq!(skill.instructions, "# Demo");
+    }
+
+    #[test]
+    fn test_fix_leaves_valid_skill_untouched() {
+        let dir = skill_with_license("MIT");
+        let skill_md = dir.path().join("SKILL.md");
+        let before = std::fs::read(&skill_md).unwrap();
+
+        assert!(fix_skill(dir.path()).unwrap().is_empty());
+        assert_eq!(std::fs::read(&skill_md).unwrap(), before);
+        assert!(fix_skill(&dir.path().join("missing")).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,54 @@
+//! Version command - print build information
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// Build information baked in by `build.rs`
+#[derive(Debug, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub commit: &'static str,
+    pub build_date: &'static str,
+    pub rustc_version: &'static str,
+}
+
+impl VersionInfo {
+    /// Build information for the running binary
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            commit: env!("PAKS_GIT_COMMIT"),
+            build_date: env!("PAKS_BUILD_DATE"),
+            rustc_version: env!("PAKS_RUSTC_VERSION"),
+        }
+    }
+}
+
+pub async fn run(json: bool) -> Result<()> {
+    let info = VersionInfo::current();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+    } else {
+        println!(
+            "paks {} ({} {})\n{}",
+            info.version, info.commit, info.build_date, info.rustc_version
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_json_contains_package_version() {
+        let json = serde_json::to_value(VersionInfo::current()).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        for key in ["commit", "build_date", "rustc_version"] {
+            assert!(json[key].as_str().is_some_and(|s| !s.is_empty()), "{key}");
+        }
+    }
+}
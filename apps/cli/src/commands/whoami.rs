@@ -0,0 +1,133 @@
+//! Whoami command - show the user the configured token belongs to
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use paks_api::{ApiError, UserInfo};
+use serde::Serialize;
+
+use super::core::config::Config;
+use super::core::token;
+
+pub struct WhoamiArgs {
+    pub json: bool,
+}
+
+/// The authenticated user and when their token lapses
+#[derive(Debug, Serialize)]
+struct Whoami {
+    username: String,
+    email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<String>,
+    /// `None` when the token never expires or the registry didn't say
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_expires_at: Option<DateTime<Utc>>,
+    /// Whether `token_expires_at` came from the registry, so `None` means
+    /// the token never expires rather than that nobody knows
+    #[serde(skip)]
+    expiry_known: bool,
+}
+
+impl Whoami {
+    fn new(user: UserInfo, token_expires_at: Option<DateTime<Utc>>, expiry_known: bool) -> Self {
+        Self {
+            username: user.username,
+            email: user.email,
+            avatar_url: user.profile_img_url,
+            token_expires_at,
+            expiry_known,
+        }
+    }
+}
+
+pub async fn run(args: WhoamiArgs) -> Result<()> {
+    let config = Config::load()?;
+    let client = token::authenticated_client(&config).await?;
+
+    let user = match client.get_current_user().await {
+        Ok(user) => user,
+        Err(ApiError::AuthRequired | ApiError::InvalidToken) => {
+            bail!("Not authenticated. Run 'paks login' first.")
+        }
+        Err(e) => return Err(e).context("Failed to fetch the current user"),
+    };
+    // Expiry is informational; fall back to what was recorded at login
+    let (expires_at, expiry_known) = match client.token_expiry().await {
+        Ok(expires_at) => (expires_at, true),
+        Err(_) => {
+            let recorded = config.get_auth_token_expiry();
+            (recorded, recorded.is_some())
+        }
+    };
+    if let Some(warning) = token::expiry_warning(expires_at, Utc::now()) {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+
+    let whoami = Whoami::new(user, expires_at, expiry_known);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&whoami)?);
+    } else {
+        print!("{}", render(&whoami));
+    }
+    Ok(())
+}
+
+fn render(whoami: &Whoami) -> String {
+    let mut out = format!("Logged in as: {}\n", whoami.username);
+    out.push_str(&format!("  Email:         {}\n", whoami.email));
+    if let Some(avatar) = &whoami.avatar_url {
+        out.push_str(&format!("  Avatar:        {}\n", avatar));
+    }
+    match whoami.token_expires_at {
+        Some(expires_at) => out.push_str(&format!(
+            "  Token expires: {}\n",
+            expires_at.format("%Y-%m-%d %H:%M UTC")
+        )),
+        None if whoami.expiry_known => out.push_str("  Token expires: never\n"),
+        None => out.push_str("  Token expires: unknown\n"),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(avatar: Option<&str>) -> UserInfo {
+        serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "username": "alice",
+            "email": "alice@example.com",
+            "profile_img_url": avatar,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_optional_fields() {
+        let whoami = Whoami::new(user(None), None, true);
+        let out = render(&whoami);
+        assert!(out.starts_with("Logged in as: alice\n"));
+        assert!(!out.contains("Avatar"));
+        assert!(out.contains("Token expires: never"));
+
+        // The registry couldn't be asked and login recorded nothing
+        let whoami = Whoami::new(user(None), None, false);
+        assert!(render(&whoami).contains("Token expires: unknown"));
+
+        let expires_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let whoami = Whoami::new(user(Some("https://img/alice.png")), Some(expires_at), true);
+        let out = render(&whoami);
+        assert!(out.contains("Avatar:        https://img/alice.png"));
+        assert!(out.contains("Token expires: 2023-11-14 22:13 UTC"));
+    }
+
+    #[test]
+    fn test_json_omits_missing_fields() {
+        let json = serde_json::to_value(Whoami::new(user(None), None, true)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"username": "alice", "email": "alice@example.com"})
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Yank command - pull a bad version of a published pak
+//!
+//! A yanked version is never chosen as the latest version. Installing it by
+//! exact version, or from a lockfile, needs `--allow-yanked`.
+
+use anyhow::{Result, bail};
+use paks_api::{ApiError, SkillRef};
+use std::io::{self, Write};
+
+use super::core::config::Config;
+use super::core::token;
+
+pub struct YankArgs {
+    /// Pak version reference (owner/name@version)
+    pub pak: String,
+    /// Skip confirmation prompt
+    pub yes: bool,
+}
+
+pub async fn run(args: YankArgs) -> Result<()> {
+    let (skill_ref, version) = yank_target(&args.pak)?;
+    let uri = skill_ref.to_uri();
+
+    if !confirm_yank(&uri, args.yes)? {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    let client = token::authenticated_client(&config).await?;
+
+    match client
+        .yank_version(skill_ref.account(), skill_ref.name(), &version)
+        .await
+    {
+        Ok(()) => {}
+        Err(ApiError::NotFound(_)) => bail!("Version '{}' not found in registry", uri),
+        Err(ApiError::Api { status: 403, .. }) => {
+            bail!("You don't have permission to yank '{}'", uri)
+        }
+        Err(ApiError::AuthRequired | ApiError::InvalidToken) => {
+            bail!("Not authenticated. Run 'paks login' first.")
+        }
+        Err(e) => bail!("Failed to yank '{}': {}", uri, e),
+    }
+
+    println!("✓ Yanked {}", uri);
+    println!("  {}", yanked_note(&version));
+    Ok(())
+}
+
+/// What a yank means for installs of `version`
+fn yanked_note(version: &str) -> String {
+    format!(
+        "It is no longer the latest; installing {} by exact version or \
+         from a lockfile now needs --allow-yanked.",
+        version
+    )
+}
+
+/// The pak and exact version named by `pak` (`owner/name@version`)
+fn yank_target(pak: &str) -> Result<(SkillRef, String)> {
+    let skill_ref = SkillRef::parse(pak)?;
+    let Some(version) = skill_ref.version() else {
+        bail!(
+            "Yanking applies to a single version.\n\
+             Hint: Use 'paks yank {}@<version>'",
+            skill_ref
+        );
+    };
+    if semver::Version::parse(version).is_err() {
+        bail!("'{}' is not an exact version (e.g. 1.2.0)", version);
+    }
+    let version = version.to_string();
+    Ok((skill_ref, version))
+}
+
+/// Confirm the yank with the user (unless --yes)
+fn confirm_yank(uri: &str, skip_confirm: bool) -> Result<bool> {
+    if skip_confirm {
+        return Ok(true);
+    }
+
+    print!(
+        "Yank {}? It will no longer resolve as the latest version. [y/N] ",
+        uri
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y") || input.trim().eq_ignore_ascii_case("yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yank_target() {
+        let (skill_ref, version) = yank_target("acme/deploy@1.2.0").unwrap();
+        assert_eq!(skill_ref.account(), "acme");
+        assert_eq!(skill_ref.name(), "deploy");
+        assert_eq!(version, "1.2.0");
+
+        let err = yank_target("acme/deploy").unwrap_err().to_string();
+        assert!(err.contains("paks yank acme/deploy@<version>"));
+        assert!(yank_target("acme/deploy@^1.2").is_err());
+        assert!(yank_target("deploy@1.2.0").is_err());
+    }
+
+    #[test]
+    fn test_yanked_note_names_allow_yanked() {
+        let note = yanked_note("1.2.0");
+        assert!(note.contains("1.2.0"));
+        assert!(note.contains("--allow-yanked"));
+    }
+}
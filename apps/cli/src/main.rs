@@ -1,23 +1,22 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use paks_api::PakSortBy;
+use std::ffi::OsString;
 
 mod commands;
 
 use commands::{
-    agent::AgentCommand,
-    create::CreateArgs,
-    info::InfoArgs,
-    install::InstallArgs,
-    list::{ListArgs, OutputFormat},
-    login::LoginArgs,
-    publish::PublishArgs,
-    remove::RemoveArgs,
-    search::SearchArgs,
-    validate::ValidateArgs,
+    agent::AgentCommand, cache::CacheCommand, core::output::OutputFormat, create::CreateArgs,
+    deprecate::DeprecateArgs, diff::DiffArgs, doctor::DoctorArgs, info::InfoArgs, init::InitArgs,
+    install::InstallArgs, list::ListArgs, lock::LockArgs, login::LoginArgs, mine::MineArgs,
+    outdated::OutdatedArgs, ping::PingArgs, publish::PublishArgs, remove::RemoveArgs,
+    rename::RenameArgs, search::SearchArgs, tree::TreeArgs, update::UpdateArgs,
+    validate::ValidateArgs, whoami::WhoamiArgs, yank::YankArgs,
 };
 
 #[derive(Parser)]
 #[command(name = "paks")]
 #[command(version)]
+#[command(allow_external_subcommands = true)]
 #[command(about = "Agent Skills package manager - scaffold, install, and publish skills")]
 #[command(
     long_about = "Paks is a CLI tool for managing Agent Skills (https://agentskills.io).
@@ -29,6 +28,20 @@ It helps you:
   • Manage installed skills across different agents"
 )]
 struct Cli {
+    /// Disable colored output (also honors the NO_COLOR environment variable)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Make no network requests; installs are served from the clone cache
+    /// (also enabled by PAKS_OFFLINE=1)
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Registry request timeout in seconds; 0 keeps the default of 30
+    /// (also set by PAKS_TIMEOUT)
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -49,6 +62,10 @@ enum Commands {
         #[arg(short, long, default_value = "basic")]
         template: String,
 
+        /// Scaffold from a template repository (git URL, supports #ref=...&path=...)
+        #[arg(long, conflicts_with = "template")]
+        template_url: Option<String>,
+
         /// Include example scripts directory
         #[arg(long)]
         with_scripts: bool,
@@ -62,11 +79,18 @@ enum Commands {
         with_assets: bool,
     },
 
+    /// Create a paks.toml manifest for the current project
+    Init {
+        /// Project directory (defaults to the current directory)
+        dir: Option<String>,
+    },
+
     /// Install a skill to your agent's skills directory
     Install {
-        /// Skill source (registry name, git URL, or local path)
+        /// Skill sources (registry name, git or gist URL, or local path)
         /// Use account/skill@version for specific versions
-        source: String,
+        #[arg(required_unless_present = "frozen", value_name = "SOURCE")]
+        sources: Vec<String>,
 
         /// Target agent to install for
         #[arg(short, long, value_enum)]
@@ -79,6 +103,81 @@ enum Commands {
         /// Force reinstall if already exists
         #[arg(short, long)]
         force: bool,
+
+        /// Only install these files or directories of a registry pak (repeatable);
+        /// the registry checksum covers the whole pak, so it isn't verified
+        #[arg(long, value_name = "PATH")]
+        only: Vec<String>,
+
+        /// Allow installing a specific version that has been yanked
+        #[arg(long)]
+        allow_yanked: bool,
+
+        /// Fail instead of warning when a registry pak is deprecated (e.g. in CI)
+        #[arg(long)]
+        no_deprecated: bool,
+
+        /// Allow replacing an installed skill with an older version (with --force)
+        #[arg(long)]
+        allow_downgrade: bool,
+
+        /// Clone registry skills over SSH instead of HTTPS (or set prefer_ssh = true in config)
+        #[arg(long)]
+        ssh: bool,
+
+        /// Record the skill in the project's paks.toml and paks.lock
+        #[arg(long)]
+        save: bool,
+
+        /// Fail unless the skill carries a valid signature
+        #[arg(long)]
+        require_signature: bool,
+
+        /// Clone afresh instead of reusing a cached clone
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Don't verify installed files against the registry's checksum
+        #[arg(long)]
+        skip_checksum: bool,
+
+        /// Stop at the first failed install when installing several skills
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// Don't ask before installing paks over confirm_install_over_mb
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Refuse registry paks larger than this many megabytes
+        #[arg(long, value_name = "MB")]
+        max_size: Option<u64>,
+
+        /// Download registry paks as an archive from the registry instead of cloning git
+        #[arg(long, conflicts_with_all = ["only", "ssh"])]
+        from_archive: bool,
+
+        /// Commits of history to clone for git installs (0 for the full history)
+        #[arg(
+            long,
+            value_name = "N",
+            default_value = "1",
+            conflicts_with = "from_archive"
+        )]
+        git_depth: u32,
+
+        /// Also clone the repository's git submodules
+        #[arg(long, conflicts_with = "from_archive")]
+        recurse_submodules: bool,
+
+        /// Run a skill's postinstall hook (metadata.postinstall) without asking
+        #[arg(long)]
+        run_hooks: bool,
+
+        /// Install exactly the versions and commits pinned in the skills directory's paks.lock
+        /// (every locked registry skill when no sources are given)
+        #[arg(long)]
+        frozen: bool,
     },
 
     /// Publish a skill to the registry
@@ -102,6 +201,22 @@ enum Commands {
         /// Use an existing tag instead of creating a new one
         #[arg(long, short)]
         tag: Option<String>,
+
+        /// Publish even with uncommitted changes (otherwise an error when non-interactive)
+        #[arg(long)]
+        allow_dirty: bool,
+
+        /// Branch to publish from instead of the checked-out one (e.g. in CI with a detached HEAD)
+        #[arg(long)]
+        branch: Option<String>,
+
+        /// Path of the skill within the repository instead of detecting it
+        #[arg(long)]
+        repo_path: Option<String>,
+
+        /// Output format for the --dry-run plan
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: CliOutputFormat,
     },
 
     /// List installed skills
@@ -117,6 +232,10 @@ enum Commands {
         /// Output format
         #[arg(short, long, value_enum, default_value = "table")]
         format: CliOutputFormat,
+
+        /// List paks published to the registry by OWNER (default: you) instead
+        #[arg(long, value_name = "OWNER", num_args = 0..=1, conflicts_with_all = ["agent", "all"])]
+        remote: Option<Option<String>>,
     },
 
     /// Remove an installed skill
@@ -135,6 +254,108 @@ enum Commands {
         /// Skip confirmation prompt
         #[arg(short, long)]
         yes: bool,
+
+        /// Run the skill's preremove hook (metadata.preremove) without asking
+        #[arg(long)]
+        run_hooks: bool,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: CliOutputFormat,
+    },
+
+    /// Upgrade installed registry skills to their latest versions
+    Update {
+        /// Skills to update (owner/name); updates all registry skills if omitted
+        skills: Vec<String>,
+
+        /// Target agent to update skills for
+        #[arg(short, long, value_enum)]
+        agent: Option<CliAgent>,
+
+        /// Show what would be updated without installing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Registry requests in flight at once (default from config, or 4)
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Don't ask before installing paks over confirm_install_over_mb
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Show installed skills with newer versions in the registry
+    Outdated {
+        /// Target agent to check skills for
+        #[arg(short, long, value_enum)]
+        agent: Option<CliAgent>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: CliOutputFormat,
+
+        /// Registry requests in flight at once (default from config, or 4)
+        #[arg(long)]
+        jobs: Option<usize>,
+    },
+
+    /// Show what changed between the installed copy of a skill and its latest version
+    Diff {
+        /// Registry reference (owner/name)
+        skill: String,
+
+        /// Registry version to diff from, instead of the installed copy
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Registry version to diff to (defaults to the latest)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Agent whose installed copy to diff
+        #[arg(short, long, value_enum)]
+        agent: Option<CliAgent>,
+    },
+
+    /// Regenerate the skills directory's paks.lock from the skills installed there
+    Lock {
+        /// Agent whose skills directory to lock
+        #[arg(short, long, value_enum)]
+        agent: Option<CliAgent>,
+
+        /// Custom skills directory (overrides agent default)
+        #[arg(short, long)]
+        dir: Option<String>,
+    },
+
+    /// Check the environment, then find and repair problems with installed skills
+    Doctor {
+        /// Agent whose skills directory to check
+        #[arg(short, long, value_enum)]
+        agent: Option<CliAgent>,
+
+        /// Offer to repair each problem found
+        #[arg(long)]
+        fix: bool,
+
+        /// Apply every fix without prompting
+        #[arg(short, long, requires = "fix")]
+        yes: bool,
+    },
+
+    /// Rename an installed skill's directory within an agent
+    Rename {
+        /// Current directory name of the installed skill, or an alias from an earlier rename
+        old: String,
+
+        /// New directory name
+        new: String,
+
+        /// Agent whose skills directory to rename in
+        #[arg(short, long, value_enum)]
+        agent: Option<CliAgent>,
     },
 
     /// Validate a skill's structure and SKILL.md
@@ -146,26 +367,105 @@ enum Commands {
         /// Strict mode - treat warnings as errors
         #[arg(long)]
         strict: bool,
+
+        /// Agent Skills spec version to validate against (default: latest)
+        #[arg(long, value_name = "X.Y")]
+        spec_version: Option<String>,
+
+        /// Output format (json and yaml include stable error codes)
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: CliOutputFormat,
+
+        /// Fix the skill's name and add a missing metadata.version in place
+        #[arg(long)]
+        fix: bool,
     },
 
     /// Search for skills in the registry
     Search {
-        /// Search query
-        query: String,
+        /// Search query (optional with --owner)
+        #[arg(required_unless_present = "owner")]
+        query: Option<String>,
+
+        /// Only show skills published by this owner
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Order results on the registry (default: by relevance, then downloads)
+        #[arg(long, value_enum)]
+        sort: Option<CliSearchSort>,
 
         /// Maximum results to show
         #[arg(short, long, default_value = "10")]
         limit: usize,
+
+        /// Number of results to skip
+        #[arg(long, default_value = "0", conflicts_with = "page")]
+        offset: usize,
+
+        /// Page of results to show, counting from 1 in pages of --limit
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        page: Option<u64>,
+
+        /// Only show results whose SKILL.md mentions this term
+        #[arg(long, value_name = "TERM")]
+        in_content: Option<String>,
+
+        /// Maximum number of SKILL.md bodies to fetch with --in-content
+        #[arg(long, default_value = "10", requires = "in_content")]
+        content_limit: usize,
+
+        /// Bypass the local metadata cache
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Registry requests in flight at once with --in-content (default from config, or 4)
+        #[arg(long)]
+        jobs: Option<usize>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: CliOutputFormat,
+
+        /// Only include these fields per result (comma-separated, json/yaml only)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+
+        /// Registry name or URL to search (defaults to the default registry)
+        #[arg(long)]
+        registry: Option<String>,
+
+        /// Search as the logged-in user, including your private and unlisted paks
+        #[arg(long, alias = "include-unlisted", conflicts_with = "registry")]
+        private: bool,
     },
 
     /// Show details about a skill
     Info {
-        /// Skill name or path
+        /// Skill path or registry reference (owner/name[@version][/path])
         skill: String,
 
         /// Show full SKILL.md content
         #[arg(long)]
         full: bool,
+
+        /// For a local or installed skill, also show its registry stats and latest version
+        #[arg(long)]
+        remote: bool,
+    },
+
+    /// Print the directory tree of a skill, or its dependency tree with --deps
+    Tree {
+        /// Skill path or registry reference (owner/name[@version])
+        skill: String,
+
+        /// Maximum depth to show
+        #[arg(short, long)]
+        depth: Option<usize>,
+
+        /// Show the dependency tree instead of the files
+        #[arg(long)]
+        deps: bool,
     },
 
     /// Login to the registry
@@ -173,14 +473,106 @@ enum Commands {
         /// API token (will prompt if not provided)
         #[arg(short, long)]
         token: Option<String>,
+
+        /// Read the API token from stdin
+        #[arg(long, conflicts_with = "token")]
+        token_stdin: bool,
+
+        /// Store the token without verifying it against the registry
+        #[arg(long)]
+        no_verify: bool,
+
+        /// Registry name or URL to log in to (a new URL is added to the config)
+        #[arg(long)]
+        registry: Option<String>,
     },
 
     /// Logout from the registry
     Logout,
 
+    /// Show the user you're logged in as
+    Whoami {
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check that the registry is reachable and measure latency
+    Ping {
+        /// Registry name or URL to ping (defaults to the default registry)
+        #[arg(long)]
+        registry: Option<String>,
+    },
+
+    /// Mark a published pak as deprecated
+    Deprecate {
+        /// Pak reference (owner/name)
+        pak: String,
+
+        /// Message telling users what to use instead
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+
+    /// Mark a deprecated pak as active again
+    Undeprecate {
+        /// Pak reference (owner/name)
+        pak: String,
+    },
+
+    /// Yank a published version so it no longer resolves as the latest
+    ///
+    /// Installing it by exact version, or from a lockfile, needs --allow-yanked.
+    Yank {
+        /// Pak version reference (owner/name@version)
+        pak: String,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// List paks you have published, including private and unlisted ones
+    Mine {
+        /// Output format
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: CliOutputFormat,
+    },
+
     /// Manage agent configurations
     #[command(subcommand)]
     Agent(AgentCommands),
+
+    /// Manage the local registry metadata cache
+    #[command(subcommand)]
+    Cache(CacheCommands),
+
+    /// Print a shell completion script
+    ///
+    /// For example, add `source <(paks completions bash)` to ~/.bashrc.
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// Print build information
+    #[command(hide = true)]
+    Version {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run `paks-<name>` from PATH for commands that aren't built in
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Remove all cached registry metadata and git clones
+    Clear,
 }
 
 #[derive(Subcommand)]
@@ -210,6 +602,56 @@ enum AgentCommands {
         name: String,
     },
 
+    /// Change an agent's skills directory (built-in agents too)
+    Set {
+        /// Agent identifier
+        name: String,
+
+        /// New skills directory path
+        #[arg(short, long)]
+        dir: String,
+    },
+
+    /// Rename a custom agent
+    Rename {
+        /// Current agent identifier
+        old: String,
+
+        /// New agent identifier
+        new: String,
+    },
+
+    /// Export custom agents and registries (without tokens) for another machine
+    Export {
+        /// File to write; JSON for a .json file, TOML otherwise (stdout if omitted)
+        #[arg(short, long)]
+        file: Option<String>,
+    },
+
+    /// Import agents and registries written by `paks agent export`
+    Import {
+        /// Exported TOML or JSON file
+        file: String,
+
+        /// Replace agents and registries that are already configured
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Show or set the agents tried, in order, when no default agent is set
+    ///
+    /// Installs without --agent go to the first of these agents whose skills
+    /// directory exists, or to ~/.agents/skills if none does.
+    DefaultOrder {
+        /// Agent identifiers in order (shows the current order if omitted)
+        #[arg(conflicts_with = "clear")]
+        names: Vec<String>,
+
+        /// Remove the fallback order
+        #[arg(long)]
+        clear: bool,
+    },
+
     /// Show agent configuration
     Show {
         /// Agent identifier (shows all if not specified)
@@ -260,6 +702,33 @@ enum CliOutputFormat {
     Yaml,
 }
 
+impl From<CliOutputFormat> for OutputFormat {
+    fn from(format: CliOutputFormat) -> Self {
+        match format {
+            CliOutputFormat::Table => OutputFormat::Table,
+            CliOutputFormat::Json => OutputFormat::Json,
+            CliOutputFormat::Yaml => OutputFormat::Yaml,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum CliSearchSort {
+    Trending,
+    Popular,
+    Recent,
+}
+
+impl From<CliSearchSort> for PakSortBy {
+    fn from(sort: CliSearchSort) -> Self {
+        match sort {
+            CliSearchSort::Trending => PakSortBy::Trending,
+            CliSearchSort::Popular => PakSortBy::MostPopular,
+            CliSearchSort::Recent => PakSortBy::Recent,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 enum CliBumpLevel {
     Patch,
@@ -270,12 +739,16 @@ enum CliBumpLevel {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    commands::core::style::init(cli.no_color);
+    commands::core::offline::init(cli.offline);
+    commands::core::client::init(cli.timeout);
 
     match cli.command {
         Commands::Create {
             name,
             output,
             template,
+            template_url,
             with_scripts,
             with_references,
             with_assets,
@@ -284,6 +757,7 @@ async fn main() -> anyhow::Result<()> {
                 name,
                 output,
                 template,
+                template_url,
                 with_scripts,
                 with_references,
                 with_assets,
@@ -291,17 +765,55 @@ async fn main() -> anyhow::Result<()> {
             .await?;
         }
 
+        Commands::Init { dir } => {
+            commands::init::run(InitArgs { dir }).await?;
+        }
+
         Commands::Install {
-            source,
+            sources,
             agent,
             dir,
             force,
+            only,
+            allow_yanked,
+            no_deprecated,
+            allow_downgrade,
+            ssh,
+            save,
+            require_signature,
+            no_cache,
+            skip_checksum,
+            fail_fast,
+            yes,
+            max_size,
+            from_archive,
+            git_depth,
+            recurse_submodules,
+            run_hooks,
+            frozen,
         } => {
             commands::install::run(InstallArgs {
-                source,
+                sources,
                 agent: agent.map(|a| a.to_string()),
                 dir,
                 force,
+                only,
+                allow_yanked,
+                no_deprecated,
+                allow_downgrade,
+                ssh,
+                save,
+                require_signature,
+                no_cache,
+                skip_checksum,
+                fail_fast,
+                yes,
+                max_size,
+                from_archive,
+                git_depth,
+                recurse_submodules,
+                run_hooks,
+                frozen,
             })
             .await?;
         }
@@ -312,6 +824,10 @@ async fn main() -> anyhow::Result<()> {
             dry_run,
             yes,
             tag,
+            allow_dirty,
+            branch,
+            repo_path,
+            format,
         } => {
             commands::publish::run(PublishArgs {
                 path,
@@ -319,19 +835,25 @@ async fn main() -> anyhow::Result<()> {
                 dry_run,
                 yes,
                 tag,
+                allow_dirty,
+                branch,
+                repo_path,
+                format: format.into(),
             })
             .await?;
         }
 
-        Commands::List { agent, all, format } => {
+        Commands::List {
+            agent,
+            all,
+            format,
+            remote,
+        } => {
             commands::list::run(ListArgs {
                 agent: agent.map(|a| a.to_string()),
                 all,
-                format: match format {
-                    CliOutputFormat::Table => OutputFormat::Table,
-                    CliOutputFormat::Json => OutputFormat::Json,
-                    CliOutputFormat::Yaml => OutputFormat::Yaml,
-                },
+                format: format.into(),
+                remote,
             })
             .await?;
         }
@@ -341,47 +863,299 @@ async fn main() -> anyhow::Result<()> {
             agent,
             all,
             yes,
+            run_hooks,
+            format,
         } => {
             commands::remove::run(RemoveArgs {
                 name,
                 agent: agent.map(|a| a.to_string()),
                 all,
                 yes,
+                run_hooks,
+                format: format.into(),
             })
             .await?;
         }
 
-        Commands::Validate { path, strict } => {
-            commands::validate::run(ValidateArgs { path, strict }).await?;
+        Commands::Update {
+            skills,
+            agent,
+            dry_run,
+            jobs,
+            yes,
+        } => {
+            commands::update::run(UpdateArgs {
+                skills,
+                agent: agent.map(|a| a.to_string()),
+                dry_run,
+                jobs,
+                yes,
+            })
+            .await?;
         }
 
-        Commands::Search { query, limit } => {
-            commands::search::run(SearchArgs { query, limit }).await?;
+        Commands::Outdated {
+            agent,
+            format,
+            jobs,
+        } => {
+            commands::outdated::run(OutdatedArgs {
+                agent: agent.map(|a| a.to_string()),
+                format: format.into(),
+                jobs,
+            })
+            .await?;
         }
 
-        Commands::Info { skill, full } => {
-            commands::info::run(InfoArgs { skill, full }).await?;
+        Commands::Diff {
+            skill,
+            from,
+            to,
+            agent,
+        } => {
+            commands::diff::run(DiffArgs {
+                skill,
+                from,
+                to,
+                agent: agent.map(|a| a.to_string()),
+            })
+            .await?;
+        }
+
+        Commands::Lock { agent, dir } => {
+            commands::lock::run(LockArgs {
+                agent: agent.map(|a| a.to_string()),
+                dir,
+            })
+            .await?;
+        }
+
+        Commands::Doctor { agent, fix, yes } => {
+            commands::doctor::run(DoctorArgs {
+                agent: agent.map(|a| a.to_string()),
+                fix,
+                yes,
+            })
+            .await?;
+        }
+
+        Commands::Rename { old, new, agent } => {
+            commands::rename::run(RenameArgs {
+                old,
+                new,
+                agent: agent.map(|a| a.to_string()),
+            })
+            .await?;
+        }
+
+        Commands::Validate {
+            path,
+            strict,
+            spec_version,
+            format,
+            fix,
+        } => {
+            commands::validate::run(ValidateArgs {
+                path,
+                strict,
+                spec_version,
+                format: format.into(),
+                fix,
+            })
+            .await?;
+        }
+
+        Commands::Search {
+            query,
+            owner,
+            sort,
+            limit,
+            offset,
+            page,
+            in_content,
+            content_limit,
+            no_cache,
+            jobs,
+            format,
+            fields,
+            registry,
+            private,
+        } => {
+            commands::search::run(SearchArgs {
+                query,
+                owner,
+                sort: sort.map(Into::into),
+                limit,
+                offset,
+                page: page.map(|p| p as usize),
+                in_content,
+                content_limit,
+                no_cache,
+                jobs,
+                format: format.into(),
+                fields,
+                registry,
+                private,
+            })
+            .await?;
+        }
+
+        Commands::Info {
+            skill,
+            full,
+            remote,
+        } => {
+            commands::info::run(InfoArgs {
+                skill,
+                full,
+                remote,
+            })
+            .await?;
+        }
+
+        Commands::Tree { skill, depth, deps } => {
+            commands::tree::run(TreeArgs { skill, depth, deps }).await?;
         }
 
-        Commands::Login { token } => {
-            commands::login::run_login(LoginArgs { token }).await?;
+        Commands::Login {
+            token,
+            token_stdin,
+            no_verify,
+            registry,
+        } => {
+            commands::login::run_login(LoginArgs {
+                token,
+                token_stdin,
+                no_verify,
+                registry,
+            })
+            .await?;
         }
 
         Commands::Logout => {
             commands::login::run_logout().await?;
         }
 
+        Commands::Whoami { json } => {
+            commands::whoami::run(WhoamiArgs { json }).await?;
+        }
+
+        Commands::Ping { registry } => {
+            commands::ping::run(PingArgs { registry }).await?;
+        }
+
+        Commands::Deprecate { pak, message } => {
+            commands::deprecate::run(DeprecateArgs {
+                pak,
+                message,
+                deprecated: true,
+            })
+            .await?;
+        }
+
+        Commands::Undeprecate { pak } => {
+            commands::deprecate::run(DeprecateArgs {
+                pak,
+                message: None,
+                deprecated: false,
+            })
+            .await?;
+        }
+
+        Commands::Yank { pak, yes } => {
+            commands::yank::run(YankArgs { pak, yes }).await?;
+        }
+
+        Commands::Mine { format } => {
+            commands::mine::run(MineArgs {
+                format: format.into(),
+            })
+            .await?;
+        }
+
         Commands::Agent(cmd) => {
             let agent_cmd = match cmd {
                 AgentCommands::List => AgentCommand::List,
                 AgentCommands::Add { name, dir } => AgentCommand::Add { name, dir },
                 AgentCommands::Remove { name } => AgentCommand::Remove { name },
                 AgentCommands::Default { name } => AgentCommand::Default { name },
+                AgentCommands::Set { name, dir } => AgentCommand::Set { name, dir },
+                AgentCommands::Rename { old, new } => AgentCommand::Rename { old, new },
+                AgentCommands::Export { file } => AgentCommand::Export { file },
+                AgentCommands::Import { file, force } => AgentCommand::Import { file, force },
+                AgentCommands::DefaultOrder { names, clear } => {
+                    AgentCommand::DefaultOrder { names, clear }
+                }
                 AgentCommands::Show { name } => AgentCommand::Show { name },
             };
             commands::agent::run(agent_cmd).await?;
         }
+
+        Commands::Cache(cmd) => {
+            let cache_cmd = match cmd {
+                CacheCommands::Clear => CacheCommand::Clear,
+            };
+            commands::cache::run(cache_cmd).await?;
+        }
+
+        Commands::Completions { shell } => {
+            commands::completions::run(shell, Cli::command())?;
+        }
+
+        Commands::Version { json } => {
+            commands::version::run(json).await?;
+        }
+
+        Commands::External(args) => {
+            // clap always passes the subcommand name first
+            let Some((name, rest)) = args.split_first() else {
+                anyhow::bail!("Missing subcommand");
+            };
+            let name = name.to_string_lossy();
+            let path_var = std::env::var_os("PATH").unwrap_or_default();
+            let Some(program) = commands::external::find_executable(&name, &path_var) else {
+                Cli::command()
+                    .error(
+                        clap::error::ErrorKind::InvalidSubcommand,
+                        format!("unrecognized subcommand '{}'", name),
+                    )
+                    .exit();
+            };
+            let status = commands::external::run(&program, rest)?;
+            std::process::exit(status.code().unwrap_or(1));
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap_complete::Shell;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_completions_for_every_shell() {
+        for shell in [
+            Shell::Bash,
+            Shell::Zsh,
+            Shell::Fish,
+            Shell::PowerShell,
+            Shell::Elvish,
+        ] {
+            let mut script = Vec::new();
+            commands::completions::write_completions(shell, Cli::command(), &mut script);
+            let script = String::from_utf8(script).unwrap();
+            assert!(script.contains("paks"), "{shell} script is empty");
+            assert!(
+                script.contains("install"),
+                "{shell} script lacks subcommands"
+            );
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! Resumable downloads of large pak content
+//!
+//! Content is written to a `.part` file next to the destination. When the
+//! connection drops, the next attempt asks the server for the rest with a
+//! `Range` header instead of starting over.
+
+use crate::client::{PaksClient, is_transient};
+use crate::error::ApiError;
+use reqwest::{Response, StatusCode, header};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use url::Url;
+
+/// Attempts made before a download is given up on
+const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// What a download asks the server for
+pub(crate) struct Accept {
+    /// Value of the `Accept` header
+    pub header: &'static str,
+    /// Whether a response's `Content-Type` is acceptable
+    pub content_type: fn(&str) -> bool,
+}
+
+impl Accept {
+    /// Any content at all
+    pub const ANY: Accept = Accept {
+        header: "*/*",
+        content_type: |_| true,
+    };
+}
+
+impl PaksClient {
+    /// Download `url` to `dest`, resuming after dropped connections
+    ///
+    /// A `.part` file left behind by an earlier, interrupted call is resumed
+    /// as well. If the server ignores the `Range` header (200 instead of 206),
+    /// or answers with a range that doesn't start where the part file ends,
+    /// the download restarts from the beginning. When `sha256` is given (hex,
+    /// optionally prefixed with `sha256:`), the completed file must match it
+    /// or it is discarded. The auth token is only sent if `url` is on the
+    /// registry itself.
+    pub async fn download(
+        &self,
+        url: &str,
+        dest: &Path,
+        sha256: Option<&str>,
+    ) -> Result<(), ApiError> {
+        self.download_accepting(url, dest, sha256, &Accept::ANY)
+            .await
+    }
+
+    /// [`download`](Self::download), failing with [`ApiError::Validation`]
+    /// on content `accept` rejects
+    pub(crate) async fn download_accepting(
+        &self,
+        url: &str,
+        dest: &Path,
+        sha256: Option<&str>,
+        accept: &Accept,
+    ) -> Result<(), ApiError> {
+        let part = part_path(dest);
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            match self
+                .download_to_part(url, &part, sha256.is_some(), accept)
+                .await
+            {
+                Ok(()) => break,
+                Err(e) if attempt < DOWNLOAD_ATTEMPTS && is_resumable(&e) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        if let Some(expected) = sha256 {
+            let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+            let actual = sha256_file(&part).await?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                tokio::fs::remove_file(&part).await.ok();
+                return Err(ApiError::ChecksumMismatch {
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+
+        tokio::fs::rename(&part, dest).await?;
+        Ok(())
+    }
+
+    /// One attempt at fetching whatever `part` doesn't have yet
+    ///
+    /// `verified` says whether the finished file will be checked against a
+    /// checksum, which makes it safe to trust a part file the server says is
+    /// already complete.
+    async fn download_to_part(
+        &self,
+        url: &str,
+        part: &Path,
+        verified: bool,
+        accept: &Accept,
+    ) -> Result<(), ApiError> {
+        self.check_online()?;
+        let (mut file, mut response) = loop {
+            let offset = match tokio::fs::metadata(part).await {
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            };
+
+            let mut headers = self.build_headers(true);
+            if !self.is_registry_url(url) {
+                headers.remove(header::AUTHORIZATION);
+            }
+            headers.insert(
+                header::ACCEPT,
+                header::HeaderValue::from_static(accept.header),
+            );
+            if offset > 0 {
+                headers.insert(
+                    header::RANGE,
+                    header::HeaderValue::from_str(&format!("bytes={}-", offset))
+                        .map_err(|e| ApiError::Validation(e.to_string()))?,
+                );
+            }
+            let response = self
+                .send(self.http_client.get(url).headers(headers))
+                .await?;
+
+            match response.status() {
+                StatusCode::PARTIAL_CONTENT if offset > 0 => {
+                    if range_start(&response) != Some(offset) {
+                        // Not the rest of our part file; start over
+                        tokio::fs::remove_file(part).await?;
+                        continue;
+                    }
+                    check_content_type(&response, accept)?;
+                    let file = tokio::fs::OpenOptions::new()
+                        .append(true)
+                        .open(part)
+                        .await?;
+                    break (file, response);
+                }
+                // Nothing left past the offset: the part file is complete,
+                // or can't be told apart from a stale one without a checksum
+                StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 => {
+                    if verified {
+                        return Ok(());
+                    }
+                    tokio::fs::remove_file(part).await?;
+                    continue;
+                }
+                status if status.is_success() => {
+                    check_content_type(&response, accept)?;
+                    break (tokio::fs::File::create(part).await?, response);
+                }
+                _ => return Err(self.error_response(response).await),
+            }
+        };
+
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Whether `url` has the same origin as the registry, so the token may
+    /// be sent along
+    fn is_registry_url(&self, url: &str) -> bool {
+        match (Url::parse(url), Url::parse(self.base_url())) {
+            (Ok(url), Ok(base)) => url.origin() == base.origin(),
+            _ => false,
+        }
+    }
+}
+
+/// First byte of a 206 response's `Content-Range` (`bytes <start>-<end>/<size>`)
+fn range_start(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(header::CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes ")?
+        .split('-')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn check_content_type(response: &Response, accept: &Accept) -> Result<(), ApiError> {
+    let content_type = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !(accept.content_type)(content_type) {
+        return Err(ApiError::Validation(format!(
+            "unexpected content type '{}' from {}",
+            content_type,
+            response.url()
+        )));
+    }
+    Ok(())
+}
+
+/// `<dest>.part`, where data is collected until the download completes
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Whether a failed attempt can pick up where it left off
+fn is_resumable(error: &ApiError) -> bool {
+    match error {
+        ApiError::Request(e) => e.is_body() || e.is_decode() || is_transient(error),
+        _ => is_transient(error),
+    }
+}
+
+/// Hex-encoded SHA-256 of a file
+async fn sha256_file(path: &Path) -> Result<String, ApiError> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    fn sha256_hex(data: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(data))
+    }
+
+    /// Serve `BODY`, dropping the first connection halfway through and
+    /// answering later requests with the range they ask for
+    async fn flaky_server() -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/pak.tar.gz", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for connection in 0..2 {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..read]).to_lowercase();
+                requests.push(request.clone());
+
+                if connection == 0 {
+                    let head = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n", BODY.len());
+                    socket.write_all(head.as_bytes()).await.unwrap();
+                    socket.write_all(&BODY[..BODY.len() / 2]).await.unwrap();
+                    // Drop the connection mid-body
+                    continue;
+                }
+
+                let offset: usize = request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("range: bytes="))
+                    .and_then(|range| range.trim_end_matches('-').parse().ok())
+                    .unwrap_or(0);
+                let head = format!(
+                    "HTTP/1.1 206 Partial Content\r\ncontent-length: {}\r\n\
+                     content-range: bytes {}-{}/{}\r\nconnection: close\r\n\r\n",
+                    BODY.len() - offset,
+                    offset,
+                    BODY.len() - 1,
+                    BODY.len()
+                );
+                socket.write_all(head.as_bytes()).await.unwrap();
+                socket.write_all(&BODY[offset..]).await.unwrap();
+            }
+            requests
+        });
+
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_disconnect() {
+        let (url, server) = flaky_server().await;
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("pak.tar.gz");
+
+        PaksClient::new()
+            .unwrap()
+            .download(&url, &dest, Some(&format!("sha256:{}", sha256_hex(BODY))))
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert!(!part_path(&dest).exists());
+
+        let requests = server.await.unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains(&format!("range: bytes={}-", BODY.len() / 2)));
+    }
+
+    #[tokio::test]
+    async fn test_download_checksum_mismatch_discards_file() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(BODY))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("pak.tar.gz");
+        let err = PaksClient::new()
+            .unwrap()
+            .download(&server.uri(), &dest, Some(&sha256_hex(b"other")))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, ApiError::ChecksumMismatch { .. }));
+        assert!(!dest.exists());
+        assert!(!part_path(&dest).exists());
+    }
+
+    /// Mock server answering ranged requests with `ranged` and the rest with
+    /// the whole of `BODY`
+    async fn ranged_server(ranged: wiremock::ResponseTemplate) -> wiremock::MockServer {
+        use wiremock::matchers::{header, header_exists, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("authorization", "Bearer secret"))
+            .and(header_exists("range"))
+            .respond_with(ranged)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(header("authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(BODY))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn client(server: &wiremock::MockServer) -> PaksClient {
+        PaksClient::builder()
+            .base_url(server.uri())
+            .auth_token("secret")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_download_restarts_on_misplaced_range() {
+        let server = ranged_server(
+            wiremock::ResponseTemplate::new(206)
+                .insert_header(
+                    "content-range",
+                    format!("bytes 0-{}/{}", BODY.len() - 1, BODY.len()),
+                )
+                .set_body_bytes(BODY),
+        )
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("pak.tar.gz");
+        std::fs::write(part_path(&dest), b"stale").unwrap();
+
+        client(&server)
+            .download(&server.uri(), &dest, None)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[1].headers.contains_key("range"));
+    }
+
+    #[tokio::test]
+    async fn test_download_range_not_satisfiable() {
+        let server = ranged_server(wiremock::ResponseTemplate::new(416)).await;
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("pak.tar.gz");
+
+        // Without a checksum a stale part file can't be trusted
+        std::fs::write(part_path(&dest), b"stale").unwrap();
+        client(&server)
+            .download(&server.uri(), &dest, None)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+        // With one, a complete part file is kept and then verified
+        let server = ranged_server(wiremock::ResponseTemplate::new(416)).await;
+        std::fs::write(part_path(&dest), BODY).unwrap();
+        client(&server)
+            .download(&server.uri(), &dest, Some(&sha256_hex(BODY)))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_sends_token_only_to_registry() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let registry = ranged_server(ResponseTemplate::new(416)).await;
+        let other = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(BODY))
+            .mount(&other)
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+
+        let client = client(&registry);
+        client
+            .download(&registry.uri(), &dir.path().join("a"), None)
+            .await
+            .unwrap();
+        client
+            .download(&other.uri(), &dir.path().join("b"), None)
+            .await
+            .unwrap();
+
+        let requests = other.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(!requests[0].headers.contains_key("authorization"));
+    }
+}
@@ -1,5 +1,6 @@
 //! API error types
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur when interacting with the Paks Registry API
@@ -7,15 +8,38 @@ use thiserror::Error;
 pub enum ApiError {
     /// HTTP request failed
     #[error("HTTP request failed: {0}")]
-    Request(#[from] reqwest::Error),
+    Request(reqwest::Error),
+
+    /// The registry didn't respond within the client's timeout
+    #[error("Request timed out after {0:?}; try again")]
+    Timeout(Duration),
+
+    /// Network access is disabled
+    #[error("Network access is disabled (offline mode)")]
+    Offline,
+
+    /// Could not open a connection to `url`
+    #[error("Could not connect to {url}; check your network connection")]
+    Connection {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
 
     /// Failed to parse response
     #[error("Failed to parse response: {0}")]
     Parse(#[from] serde_json::Error),
 
     /// API returned an error response
-    #[error("API error ({status}): {message}")]
-    Api { status: u16, message: String },
+    ///
+    /// `code` is the machine-readable code from the error body (e.g.
+    /// `VERSION_MISMATCH`), when the server sent one.
+    #[error("API error ({status}{}): {message}", code_suffix(.code))]
+    Api {
+        status: u16,
+        code: Option<String>,
+        message: String,
+    },
 
     /// Authentication required but no token provided
     #[error("Authentication required")]
@@ -33,6 +57,10 @@ pub enum ApiError {
     #[error("Rate limited. Retry after {retry_after:?} seconds")]
     RateLimited { retry_after: Option<u64> },
 
+    /// Could not connect to the registry at all
+    #[error("Registry unreachable: {0}")]
+    Unreachable(String),
+
     /// Invalid URL
     #[error("Invalid URL: {0}")]
     InvalidUrl(#[from] url::ParseError),
@@ -40,4 +68,54 @@ pub enum ApiError {
     /// Validation error
     #[error("Validation error: {0}")]
     Validation(String),
+
+    /// Failed to read or write a local file
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A request kept failing after being retried
+    #[error("{source} (after {retries} retries)")]
+    RetriesExhausted { retries: u32, source: Box<ApiError> },
+
+    /// Downloaded content doesn't match the expected checksum
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+}
+
+impl From<reqwest::Error> for ApiError {
+    /// Connection failures become [`ApiError::Connection`]. Timeouts stay
+    /// [`ApiError::Request`] here, since only the client knows how long it
+    /// waited; it reports them as [`ApiError::Timeout`].
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_connect() {
+            let url = error.url().map(|u| u.to_string()).unwrap_or_default();
+            ApiError::Connection { url, source: error }
+        } else {
+            ApiError::Request(error)
+        }
+    }
+}
+
+impl ApiError {
+    /// The underlying error, looking through [`ApiError::RetriesExhausted`]
+    pub fn root(&self) -> &ApiError {
+        match self {
+            ApiError::RetriesExhausted { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// The server's error code, if this is an [`ApiError::Api`] that has one
+    pub fn code(&self) -> Option<&str> {
+        match self.root() {
+            ApiError::Api { code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn code_suffix(code: &Option<String>) -> String {
+    code.as_ref()
+        .map(|code| format!(", {}", code))
+        .unwrap_or_default()
 }
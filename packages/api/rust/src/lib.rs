@@ -4,10 +4,19 @@
 //! Types are re-exported from `paks-api-schema`.
 
 pub mod client;
+pub mod download;
 pub mod error;
+pub mod pagination;
+pub mod skill_ref;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 pub use client::PaksClient;
 pub use error::ApiError;
+pub use skill_ref::SkillRef;
+
+/// SKILL.md parsing, shared with the CLI
+pub use paks_core::skill_md;
 
 // Re-export schema types for convenience
 pub use paks_api_schema::*;
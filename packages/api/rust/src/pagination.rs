@@ -0,0 +1,131 @@
+//! Walking every page of a paginated listing
+//!
+//! The registry returns `list_paks` results a page at a time. These helpers
+//! keep requesting the next offset until a short page comes back, so callers
+//! don't have to track offsets themselves.
+
+use crate::client::PaksClient;
+use crate::error::ApiError;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use paks_api_schema::{ListPaksQuery, PakWithLatestVersion};
+
+/// Page size used when the query doesn't set `limit`
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// Most results a paginated listing yields, however many pages the server has
+pub const MAX_PAGINATED_RESULTS: usize = 10_000;
+
+impl PaksClient {
+    /// List every pak matching `query`, across all pages
+    ///
+    /// `query.limit` is the page size (default [`DEFAULT_PAGE_SIZE`]) and
+    /// `query.offset` where the first page starts. Stops after
+    /// [`MAX_PAGINATED_RESULTS`] results.
+    pub async fn list_paks_all(
+        &self,
+        query: ListPaksQuery,
+    ) -> Result<Vec<PakWithLatestVersion>, ApiError> {
+        self.list_paks_stream(query).try_collect().await
+    }
+
+    /// Stream every pak matching `query`, fetching pages as they're consumed
+    ///
+    /// Paging works as in [`list_paks_all`](Self::list_paks_all). The stream
+    /// ends after the first error.
+    pub fn list_paks_stream(
+        &self,
+        query: ListPaksQuery,
+    ) -> impl Stream<Item = Result<PakWithLatestVersion, ApiError>> + '_ {
+        let page_size = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+        let start = query.offset.unwrap_or(0);
+
+        stream::try_unfold(Some(start), move |offset| {
+            let query = query.clone();
+            async move {
+                let Some(offset) = offset else {
+                    return Ok(None);
+                };
+                let page = self
+                    .list_paks(ListPaksQuery {
+                        limit: Some(page_size),
+                        offset: Some(offset),
+                        ..query
+                    })
+                    .await?;
+                // A short page is the last one
+                let next = if page.len() < page_size as usize {
+                    None
+                } else {
+                    offset.checked_add(page_size)
+                };
+                Ok::<_, ApiError>(Some((page, next)))
+            }
+        })
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok::<_, ApiError>)))
+        .try_flatten()
+        .take(MAX_PAGINATED_RESULTS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::pak_json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn three_pages() -> MockServer {
+        let server = MockServer::start().await;
+        for (offset, names) in [("0", &["a", "b"][..]), ("2", &["c", "d"]), ("4", &["e"])] {
+            let items: Vec<_> = names.iter().map(|n| pak_json("acme", n)).collect();
+            Mock::given(method("GET"))
+                .and(path("/v1/paks"))
+                .and(query_param("limit", "2"))
+                .and(query_param("offset", offset))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "items": items,
+                    "total_count": 5,
+                })))
+                .mount(&server)
+                .await;
+        }
+        server
+    }
+
+    #[tokio::test]
+    async fn test_list_paks_all_walks_pages() {
+        let server = three_pages().await;
+        let client = PaksClient::builder()
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let query = ListPaksQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let paks = client.list_paks_all(query).await.unwrap();
+        let names: Vec<&str> = paks.iter().map(|p| p.pak.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c", "d", "e"]);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_list_paks_stream_is_lazy() {
+        let server = three_pages().await;
+        let client = PaksClient::builder()
+            .base_url(server.uri())
+            .build()
+            .unwrap();
+
+        let query = ListPaksQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first: Vec<_> = client.list_paks_stream(query).take(3).collect().await;
+        assert_eq!(first.len(), 3);
+        // Only the first two pages were needed
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+    }
+}
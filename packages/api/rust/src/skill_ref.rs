@@ -0,0 +1,161 @@
+//! Skill references
+//!
+//! Typed `owner/name[@version]` references to paks in the registry.
+
+use crate::error::ApiError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Reference to a pak in the registry: `account/name[@version]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SkillRef {
+    /// Account/owner name (e.g., "stakpak")
+    account: String,
+    /// Skill name (e.g., "kubernetes-deploy")
+    name: String,
+    /// Optional version (e.g., "1.2.3")
+    version: Option<String>,
+}
+
+impl SkillRef {
+    /// Parse a skill reference from input string
+    /// Format: account/skill[@version]
+    pub fn parse(input: &str) -> Result<Self, ApiError> {
+        let (identifier, version) = if let Some(at_pos) = input.rfind('@') {
+            let id = &input[..at_pos];
+            let ver = &input[at_pos + 1..];
+            if ver.is_empty() {
+                return Err(ApiError::Validation(
+                    "Version cannot be empty after @".to_string(),
+                ));
+            }
+            (id, Some(ver.to_string()))
+        } else {
+            (input, None)
+        };
+
+        let parts: Vec<&str> = identifier.split('/').collect();
+        if parts.len() != 2 {
+            return Err(ApiError::Validation(format!(
+                "Invalid skill reference '{}'. Expected format: account/skill[@version]",
+                input
+            )));
+        }
+
+        let account = parts[0].to_string();
+        let name = parts[1].to_string();
+
+        // Validate account name (lowercase alphanumeric + hyphens, 1-39 chars)
+        if account.is_empty() || account.len() > 39 {
+            return Err(ApiError::Validation(
+                "Account name must be 1-39 characters".to_string(),
+            ));
+        }
+        if !is_valid_name(&account) {
+            return Err(ApiError::Validation(
+                "Account name must contain only lowercase letters, numbers, and hyphens"
+                    .to_string(),
+            ));
+        }
+
+        // Validate skill name (lowercase alphanumeric + hyphens, 1-64 chars)
+        if name.is_empty() || name.len() > 64 {
+            return Err(ApiError::Validation(
+                "Skill name must be 1-64 characters".to_string(),
+            ));
+        }
+        if !is_valid_name(&name) {
+            return Err(ApiError::Validation(
+                "Skill name must contain only lowercase letters, numbers, and hyphens".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            account,
+            name,
+            version,
+        })
+    }
+
+    /// Account/owner name
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Skill name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Requested version, if any
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Get the URI for API calls (account/name[@version])
+    pub fn to_uri(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for SkillRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(v) => write!(f, "{}/{}@{}", self.account, self.name, v),
+            None => write!(f, "{}/{}", self.account, self.name),
+        }
+    }
+}
+
+impl FromStr for SkillRef {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Lowercase letters, digits and hyphens only
+fn is_valid_name(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skill_ref_parse() {
+        let ref1 = SkillRef::parse("stakpak/kubernetes-deploy").unwrap();
+        assert_eq!(ref1.account(), "stakpak");
+        assert_eq!(ref1.name(), "kubernetes-deploy");
+        assert!(ref1.version().is_none());
+
+        let ref2 = SkillRef::parse("stakpak/kubernetes-deploy@1.2.3").unwrap();
+        assert_eq!(ref2.account(), "stakpak");
+        assert_eq!(ref2.name(), "kubernetes-deploy");
+        assert_eq!(ref2.version(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn test_skill_ref_parse_invalid() {
+        assert!(SkillRef::parse("invalid").is_err());
+        assert!(SkillRef::parse("too/many/slashes").is_err());
+        assert!(SkillRef::parse("UPPERCASE/skill").is_err());
+        assert!(matches!(
+            SkillRef::parse("stakpak/skill@"),
+            Err(ApiError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_skill_ref_display_round_trip() {
+        for input in ["stakpak/kubernetes-deploy", "acme/my-skill@1.0.0-beta.1"] {
+            let skill_ref: SkillRef = input.parse().unwrap();
+            assert_eq!(skill_ref.to_string(), input);
+            assert_eq!(skill_ref.to_uri(), input);
+            assert_eq!(SkillRef::parse(&skill_ref.to_uri()).unwrap(), skill_ref);
+        }
+    }
+}
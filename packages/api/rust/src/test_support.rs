@@ -0,0 +1,29 @@
+//! Fixtures shared by tests of this crate and its dependents
+//!
+//! Enabled for other crates by the `test-support` feature.
+
+/// Registry JSON for a public, active pak `owner/name` with no versions
+///
+/// Tests tweak fields on the returned value before deserializing it.
+pub fn pak_json(owner: &str, name: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": "00000000-0000-0000-0000-000000000001",
+        "name": name,
+        "owner_name": owner,
+        "uri": format!("{}/{}", owner, name),
+        "full_uri": format!("stakpak://{}/{}", owner, name),
+        "path": null,
+        "repository_url": format!("https://github.com/{}/skills", owner),
+        "description": null,
+        "tags": null,
+        "visibility": "PUBLIC",
+        "status": "ACTIVE",
+        "download_count": 0,
+        "usage_count": 0,
+        "total_downloads": 0,
+        "total_usages": 0,
+        "created_at": "2025-01-01T00:00:00Z",
+        "updated_at": "2025-01-01T00:00:00Z",
+        "latest_version": null,
+    })
+}
@@ -143,8 +143,12 @@ pub struct Pak {
     pub repository_url: String,
     /// Pak description
     pub description: Option<String>,
-    /// Tags/keywords
+    /// Tags/keywords (absent or null when the pak has none)
     pub tags: Option<Vec<String>>,
+    /// License from the pak's SKILL.md frontmatter, if it declares one
+    /// (absent from older registry responses)
+    #[serde(default)]
+    pub license: Option<String>,
     /// Visibility level
     pub visibility: PakVisibility,
     /// Status
@@ -305,11 +309,21 @@ pub struct ListPaksQuery {
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 pub struct ListPaksResponse {
     /// List of paks for the current page
+    ///
+    /// Older servers send this as `results`.
+    #[serde(alias = "results")]
     pub items: Vec<PakWithLatestVersion>,
     /// Total number of paks matching the query (before pagination)
     pub total_count: u64,
 }
 
+/// Response from listing the published versions of a pak
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+pub struct ListPakVersionsResponse {
+    /// Versions of the pak
+    pub results: Vec<PakVersion>,
+}
+
 /// Query parameters for searching paks
 #[derive(Serialize, Deserialize, Debug, Default, Clone, JsonSchema)]
 pub struct SearchPaksQuery {
@@ -322,6 +336,9 @@ pub struct SearchPaksQuery {
     /// Freeform keyword query
     #[serde(skip_serializing_if = "Option::is_none")]
     pub query: Option<String>,
+    /// Sort order: TRENDING, MOST_POPULAR, or RECENT (by relevance if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<PakSortBy>,
     /// Maximum number of results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u32>,
@@ -335,6 +352,9 @@ pub struct SearchPaksQuery {
 pub struct SearchPaksResponse {
     /// List of matching paks
     pub results: Vec<Pak>,
+    /// Total number of paks matching the query (before pagination), if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
 }
 
 // ============================================================================
@@ -401,6 +421,16 @@ pub struct PublishPakRequest {
 #[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
 pub struct PublishPakResponse {}
 
+/// Request body for PUT /v1/paks/{owner}/{name}/status
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+pub struct SetPakStatusRequest {
+    /// New status
+    pub status: PakStatus,
+    /// Message shown to users when deprecated (e.g., what to migrate to)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 // ============================================================================
 // Install Models
 // ============================================================================
@@ -419,6 +449,12 @@ pub struct InstallPakInfo {
     pub description: Option<String>,
     /// Visibility level
     pub visibility: PakVisibility,
+    /// Status
+    #[serde(default)]
+    pub status: PakStatus,
+    /// Deprecation message set by the owner
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_message: Option<String>,
 }
 
 /// Version info for installation
@@ -432,6 +468,18 @@ pub struct InstallVersionInfo {
     pub commit_hash: String,
     /// Publication timestamp
     pub published_at: DateTime<Utc>,
+    /// Whether this version has been yanked by its owner
+    #[serde(default)]
+    pub yanked: bool,
+    /// Content checksum (`sha256:<hex>`) over the pak's files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// Detached minisign signature over the version's SKILL.md
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i64>,
 }
 
 /// Repository info for installation
@@ -520,6 +568,7 @@ pub struct PaksApiSchema {
     // Request/Response types
     pub list_paks_query: ListPaksQuery,
     pub list_paks_response: ListPaksResponse,
+    pub list_pak_versions_response: ListPakVersionsResponse,
     pub search_paks_query: SearchPaksQuery,
     pub search_paks_response: SearchPaksResponse,
 
@@ -530,6 +579,7 @@ pub struct PaksApiSchema {
     // Publish models
     pub publish_pak_request: PublishPakRequest,
     pub publish_pak_response: PublishPakResponse,
+    pub set_pak_status_request: SetPakStatusRequest,
 
     // Install models
     pub install_pak_info: InstallPakInfo,
@@ -7,8 +7,10 @@
 import type {
   ListPaksQuery,
   ListPaksResponse,
+  ListPakVersionsResponse,
   SearchPaksQuery,
   Pak,
+  PakVersion,
   PakContentResponse,
   PakInstallResponse,
   VerifyTokenResponse,
@@ -133,6 +135,13 @@ export class PaksClient {
     return results[0] ?? null;
   }
 
+  /** List every published version of a pak */
+  async listPakVersions(owner: string, pakName: string): Promise<PakVersion[]> {
+    const url = `${this.baseUrl}/v1/paks/${encodeURIComponent(owner)}/${encodeURIComponent(pakName)}/versions`;
+    const response = await this.request<ListPakVersionsResponse>(url);
+    return response.results;
+  }
+
   // ========================================================================
   // Install Endpoints
   // ========================================================================
@@ -60,6 +60,7 @@ export interface PaksApiSchema {
   install_path_info: InstallPathInfo;
   install_repository_info: InstallRepositoryInfo;
   install_version_info: InstallVersionInfo;
+  list_pak_versions_response: ListPakVersionsResponse;
   list_paks_query: ListPaksQuery;
   list_paks_response: ListPaksResponse;
   pak: Pak;
@@ -78,6 +79,7 @@ export interface PaksApiSchema {
   publish_pak_response: PublishPakResponse;
   search_paks_query: SearchPaksQuery;
   search_paks_response: SearchPaksResponse;
+  set_pak_status_request: SetPakStatusRequest;
   user_info: UserInfo;
   verify_token_response: VerifyTokenResponse;
 }
@@ -132,6 +134,10 @@ export interface ErrorResponse {
  * Pak info for installation
  */
 export interface InstallPakInfo {
+  /**
+   * Deprecation message set by the owner
+   */
+  deprecation_message?: string | null;
   /**
    * Pak description
    */
@@ -148,6 +154,10 @@ export interface InstallPakInfo {
    * Owner/account name
    */
   owner: string;
+  /**
+   * Status
+   */
+  status?: PakStatus;
   /**
    * Visibility level
    */
@@ -191,6 +201,10 @@ export interface InstallRepositoryInfo {
  * Version info for installation
  */
 export interface InstallVersionInfo {
+  /**
+   * Content checksum (`sha256:<hex>`) over the pak's files
+   */
+  checksum?: string | null;
   /**
    * Full commit SHA
    */
@@ -199,6 +213,14 @@ export interface InstallVersionInfo {
    * Publication timestamp
    */
   published_at: string;
+  /**
+   * Detached minisign signature over the version's SKILL.md
+   */
+  signature?: string | null;
+  /**
+   * Size in bytes
+   */
+  size_bytes?: number | null;
   /**
    * Git tag (e.g., v1.2.3)
    */
@@ -207,6 +229,19 @@ export interface InstallVersionInfo {
    * Semantic version (e.g., 1.2.3)
    */
   version: string;
+  /**
+   * Whether this version has been yanked by its owner
+   */
+  yanked?: boolean;
+}
+/**
+ * Response from listing the published versions of a pak
+ */
+export interface ListPakVersionsResponse {
+  /**
+   * Versions of the pak
+   */
+  results: PakVersion[];
 }
 /**
  * Query parameters for listing paks
@@ -235,6 +270,8 @@ export interface ListPaksQuery {
 export interface ListPaksResponse {
   /**
    * List of paks for the current page
+   *
+   * Older servers send this as `results`.
    */
   items: PakWithLatestVersion[];
   /**
@@ -291,7 +328,7 @@ export interface PakWithLatestVersion {
    */
   status: PakStatus;
   /**
-   * Tags/keywords
+   * Tags/keywords (absent or null when the pak has none)
    */
   tags?: string[] | null;
   /**
@@ -396,6 +433,10 @@ export interface Pak {
    * Unique identifier
    */
   id: string;
+  /**
+   * License from the pak's SKILL.md frontmatter, if it declares one (absent from older registry responses)
+   */
+  license?: string | null;
   /**
    * Pak name
    */
@@ -417,7 +458,7 @@ export interface Pak {
    */
   status: PakStatus;
   /**
-   * Tags/keywords
+   * Tags/keywords (absent or null when the pak has none)
    */
   tags?: string[] | null;
   /**
@@ -589,6 +630,10 @@ export interface SearchPaksQuery {
    * Freeform keyword query
    */
   query?: string | null;
+  /**
+   * Sort order: TRENDING, MOST_POPULAR, or RECENT (by relevance if unset)
+   */
+  sort_by?: PakSortBy | null;
 }
 /**
  * Response from searching paks
@@ -598,6 +643,23 @@ export interface SearchPaksResponse {
    * List of matching paks
    */
   results: Pak[];
+  /**
+   * Total number of paks matching the query (before pagination), if reported
+   */
+  total_count?: number | null;
+}
+/**
+ * Request body for PUT /v1/paks/{owner}/{name}/status
+ */
+export interface SetPakStatusRequest {
+  /**
+   * Message shown to users when deprecated (e.g., what to migrate to)
+   */
+  message?: string | null;
+  /**
+   * New status
+   */
+  status: PakStatus;
 }
 /**
  * User information returned from auth endpoints
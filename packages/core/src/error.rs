@@ -11,6 +11,9 @@ pub enum Error {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("Unsupported spec version: {version}. Supported versions: {supported}")]
+    UnsupportedSpecVersion { version: String, supported: String },
+
     #[error("{0}")]
     Other(String),
 }
@@ -0,0 +1,389 @@
+//! SKILL.md frontmatter and its validation
+//!
+//! Paks extends the Agent Skills spec frontmatter with package management
+//! fields. Validation follows the rules of a chosen spec version.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::spec::{self, FieldStatus, SpecRules};
+
+/// SKILL.md frontmatter - combines Agent Skills spec with paks package fields
+///
+/// Required fields (Agent Skills spec):
+/// - name: Skill identifier (1-64 chars, lowercase + hyphens)
+/// - description: What the skill does (1-1024 chars)
+///
+/// Optional fields (Agent Skills spec):
+/// - license: License name or reference
+/// - compatibility: Environment requirements
+/// - metadata: Arbitrary key-value pairs (includes version for paks)
+/// - allowed-tools: Pre-approved tools (experimental)
+///
+/// Paks extensions (for package management):
+/// - metadata.version: Semantic version for publishing (inside metadata)
+/// - authors: List of authors
+/// - repository: Source repository URL
+/// - homepage: Project homepage
+/// - keywords: Search keywords
+/// - categories: Skill categories
+/// - dependencies: Other skills this depends on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillFrontmatter {
+    // === Agent Skills spec required fields ===
+    /// Skill name (required, 1-64 chars, lowercase + hyphens)
+    pub name: String,
+
+    /// Description of what the skill does (required, 1-1024 chars)
+    pub description: String,
+
+    // === Agent Skills spec optional fields ===
+    /// License
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+
+    /// Compatibility notes (max 500 chars), or structured requirements
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compatibility: Option<Compatibility>,
+
+    /// Additional metadata (Agent Skills spec)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// Pre-approved tools (experimental, Agent Skills spec)
+    #[serde(
+        default,
+        rename = "allowed-tools",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allowed_tools: Option<String>,
+
+    // === Paks package management extensions ===
+    /// Authors (paks extension)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+
+    /// Repository URL (paks extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+
+    /// Homepage URL (paks extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+
+    /// Keywords for search (paks extension)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+
+    /// Categories (paks extension)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+
+    /// Dependencies on other skills (paks extension)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<SkillDependency>,
+}
+
+/// Skill dependency specification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillDependency {
+    /// Dependency skill name
+    pub name: String,
+
+    /// Version requirement (semver range)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Git repository URL (alternative to registry)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+
+    /// Git ref (branch/tag/commit)
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+
+    /// Local path (for development)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// The `compatibility` field: free-text notes as in the Agent Skills spec, or
+/// requirements paks can check at install time (paks extension)
+///
+/// ```yaml
+/// compatibility:
+///   agents: [claude-code, cursor]
+///   paks: ">=0.2"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Compatibility {
+    Notes(String),
+    Requirements(CompatibilityRequirements),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityRequirements {
+    /// Agents the skill supports; any agent when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<String>,
+
+    /// Version requirement on paks itself (semver range)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paks: Option<String>,
+}
+
+impl Compatibility {
+    /// Reasons the skill may not work when installed into `agent` by paks
+    /// `paks_version`; `agent` is `None` for installs into an explicit dir
+    pub fn install_warnings(&self, agent: Option<&str>, paks_version: &str) -> Vec<String> {
+        let Compatibility::Requirements(reqs) = self else {
+            return Vec::new();
+        };
+        let mut warnings = Vec::new();
+        if let Some(agent) = agent
+            && !reqs.agents.is_empty()
+            && !reqs.agents.iter().any(|a| a.eq_ignore_ascii_case(agent))
+        {
+            warnings.push(format!(
+                "skill supports {}, not {}",
+                reqs.agents.join(", "),
+                agent
+            ));
+        }
+        if let Some(req) = &reqs.paks {
+            match (
+                semver::VersionReq::parse(req),
+                semver::Version::parse(paks_version),
+            ) {
+                (Ok(req), Ok(version)) if !req.matches(&version) => warnings.push(format!(
+                    "skill requires paks {}, this is paks {}",
+                    req, version
+                )),
+                (Err(_), _) => warnings.push(format!(
+                    "skill has an invalid paks version requirement '{}'",
+                    req
+                )),
+                _ => {}
+            }
+        }
+        warnings
+    }
+}
+
+impl std::fmt::Display for Compatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compatibility::Notes(notes) => write!(f, "{}", notes),
+            Compatibility::Requirements(reqs) => {
+                let mut parts = Vec::new();
+                if !reqs.agents.is_empty() {
+                    parts.push(format!("agents: {}", reqs.agents.join(", ")));
+                }
+                if let Some(paks) = &reqs.paks {
+                    parts.push(format!("paks {}", paks));
+                }
+                write!(f, "{}", parts.join("; "))
+            }
+        }
+    }
+}
+
+/// A validation problem, with a stable `code` (like `name.invalid_chars`) for
+/// tools that consume `paks validate --format json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+impl SkillFrontmatter {
+    /// Validate the frontmatter according to the latest Agent Skills spec
+    pub fn validate(&self) -> Result<Vec<Diagnostic>, Diagnostic> {
+        self.validate_for(spec::latest())
+    }
+
+    /// Validate the frontmatter according to a specific Agent Skills spec version
+    ///
+    /// Returns the warnings found, or the first error.
+    pub fn validate_for(&self, rules: &SpecRules) -> Result<Vec<Diagnostic>, Diagnostic> {
+        let mut warnings = Vec::new();
+
+        // Name validation
+        if self.name.is_empty() || self.name.len() > 64 {
+            return Err(Diagnostic::new(
+                "name.length",
+                "name must be 1-64 characters",
+            ));
+        }
+
+        if !self
+            .name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c == '-' || c.is_ascii_digit())
+        {
+            return Err(Diagnostic::new(
+                "name.invalid_chars",
+                "name must contain only lowercase letters, numbers, and hyphens",
+            ));
+        }
+
+        if self.name.starts_with('-') || self.name.ends_with('-') {
+            return Err(Diagnostic::new(
+                "name.edge_hyphen",
+                "name must not start or end with a hyphen",
+            ));
+        }
+
+        if self.name.contains("--") {
+            return Err(Diagnostic::new(
+                "name.consecutive_hyphens",
+                "name must not contain consecutive hyphens",
+            ));
+        }
+
+        // Description validation
+        if self.description.is_empty() || self.description.len() > rules.description_max {
+            let code = if self.description.is_empty() {
+                "description.empty"
+            } else {
+                "description.too_long"
+            };
+            return Err(Diagnostic::new(
+                code,
+                format!(
+                    "description must be 1-{} characters (spec {})",
+                    rules.description_max, rules.version
+                ),
+            ));
+        }
+
+        if self.description.len() < 20 {
+            warnings.push(Diagnostic::new(
+                "description.too_short",
+                "description is very short; consider adding more detail",
+            ));
+        }
+
+        // Compatibility validation
+        if let Some(compat) = &self.compatibility {
+            if let Compatibility::Requirements(CompatibilityRequirements {
+                paks: Some(req), ..
+            }) = compat
+                && semver::VersionReq::parse(req).is_err()
+            {
+                return Err(Diagnostic::new(
+                    "compatibility.invalid_paks",
+                    format!(
+                        "compatibility.paks '{}' is not a valid version requirement",
+                        req
+                    ),
+                ));
+            }
+            match rules.compatibility_max {
+                Some(max) if compat.to_string().len() > max => {
+                    return Err(Diagnostic::new(
+                        "compatibility.too_long",
+                        format!("compatibility must be at most {} characters", max),
+                    ));
+                }
+                Some(_) => {}
+                None => warnings.push(Diagnostic::new(
+                    "compatibility.unsupported",
+                    format!(
+                        "compatibility is not part of spec {}; agents may ignore it",
+                        rules.version
+                    ),
+                )),
+            }
+        }
+
+        if self.allowed_tools.is_some() && rules.allowed_tools == FieldStatus::Unsupported {
+            warnings.push(Diagnostic::new(
+                "allowed_tools.unsupported",
+                format!(
+                    "allowed-tools is not part of spec {}; agents may ignore it",
+                    rules.version
+                ),
+            ));
+        }
+
+        Ok(warnings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter(name: &str, description: &str) -> SkillFrontmatter {
+        SkillFrontmatter {
+            name: name.to_string(),
+            description: description.to_string(),
+            license: None,
+            compatibility: None,
+            metadata: None,
+            allowed_tools: None,
+            authors: Vec::new(),
+            repository: None,
+            homepage: None,
+            keywords: Vec::new(),
+            categories: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_frontmatter_validation() {
+        let valid = frontmatter("my-skill", "A skill that does something useful for users");
+        assert!(valid.validate().is_ok());
+
+        // uppercase not allowed
+        let invalid_name = frontmatter("My-Skill", "A skill");
+        assert_eq!(
+            invalid_name.validate().unwrap_err().code,
+            "name.invalid_chars"
+        );
+    }
+
+    #[test]
+    fn test_validate_for_spec_version() {
+        let mut frontmatter = frontmatter("demo", &"Deploys services. ".repeat(20));
+        frontmatter.allowed_tools = Some("Bash".to_string());
+
+        let latest = spec::rules(Some("1.0")).unwrap();
+        assert!(frontmatter.validate_for(latest).unwrap().is_empty());
+
+        let old = spec::rules(Some("0.9")).unwrap();
+        let err = frontmatter.validate_for(old).unwrap_err();
+        assert!(err.to_string().contains("1-200 characters"));
+        assert_eq!(err.code, "description.too_long");
+
+        frontmatter.description = "Deploys services to Kubernetes".to_string();
+        let warnings = frontmatter.validate_for(old).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, "allowed_tools.unsupported");
+        assert!(
+            warnings[0]
+                .message
+                .contains("allowed-tools is not part of spec 0.9")
+        );
+    }
+}
@@ -1,9 +1,17 @@
 //! Paks Core Library
 //!
-//! Shared types and utilities for the paks ecosystem.
+//! Shared types and utilities for the paks ecosystem, including SKILL.md
+//! parsing and validation used by both the CLI and the API client.
 
 pub mod config;
 pub mod error;
+pub mod frontmatter;
+pub mod skill_md;
+pub mod spec;
 
 pub use config::Config;
 pub use error::Error;
+pub use frontmatter::{
+    Compatibility, CompatibilityRequirements, Diagnostic, SkillDependency, SkillFrontmatter,
+};
+pub use skill_md::{SkillMdError, generate_skill_md, parse_skill_md, split_skill_md};
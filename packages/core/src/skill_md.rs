@@ -0,0 +1,131 @@
+//! SKILL.md parsing
+//!
+//! A SKILL.md is YAML frontmatter between `---` lines followed by the
+//! markdown instructions.
+
+use thiserror::Error;
+
+use crate::frontmatter::SkillFrontmatter;
+
+/// Why a SKILL.md couldn't be parsed or written
+#[derive(Error, Debug)]
+pub enum SkillMdError {
+    #[error("SKILL.md must start with YAML frontmatter (---)")]
+    MissingFrontmatter,
+
+    #[error("SKILL.md frontmatter not properly closed (missing ---)")]
+    UnclosedFrontmatter,
+
+    #[error("Failed to parse SKILL.md frontmatter as YAML")]
+    InvalidYaml(#[source] serde_yaml_ng::Error),
+
+    #[error("Failed to serialize frontmatter")]
+    Serialize(#[source] serde_yaml_ng::Error),
+}
+
+/// Split SKILL.md `content` into its frontmatter and body, both trimmed
+pub fn split_skill_md(content: &str) -> Result<(&str, &str), SkillMdError> {
+    let rest = content
+        .trim()
+        .strip_prefix("---")
+        .ok_or(SkillMdError::MissingFrontmatter)?;
+    let end_marker = rest
+        .find("\n---")
+        .ok_or(SkillMdError::UnclosedFrontmatter)?;
+
+    Ok((rest[..end_marker].trim(), rest[end_marker + 4..].trim()))
+}
+
+/// Parse SKILL.md content into frontmatter and body
+pub fn parse_skill_md(content: &str) -> Result<(SkillFrontmatter, String), SkillMdError> {
+    let (frontmatter_str, body) = split_skill_md(content)?;
+
+    // Parse YAML frontmatter
+    let frontmatter: SkillFrontmatter =
+        serde_yaml_ng::from_str(frontmatter_str).map_err(SkillMdError::InvalidYaml)?;
+
+    Ok((frontmatter, body.to_string()))
+}
+
+/// Generate SKILL.md content from frontmatter and body
+pub fn generate_skill_md(
+    frontmatter: &SkillFrontmatter,
+    body: &str,
+) -> Result<String, SkillMdError> {
+    let yaml = serde_yaml_ng::to_string(frontmatter).map_err(SkillMdError::Serialize)?;
+
+    Ok(format!("---\n{}---\n\n{}", yaml, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontmatter::Compatibility;
+
+    #[test]
+    fn test_split_skill_md() {
+        let content = "---\nname: deploy\n---\n\n# Deploy\n\nSteps.\n";
+        let (frontmatter, body) = split_skill_md(content).unwrap();
+        assert_eq!(frontmatter, "name: deploy");
+        assert_eq!(body, "# Deploy\n\nSteps.");
+
+        assert!(matches!(
+            split_skill_md("# No frontmatter"),
+            Err(SkillMdError::MissingFrontmatter)
+        ));
+        assert!(matches!(
+            split_skill_md("---\nname: deploy\n"),
+            Err(SkillMdError::UnclosedFrontmatter)
+        ));
+    }
+
+    #[test]
+    fn test_parse_skill_md() {
+        let content = r#"---
+name: test-skill
+description: A test skill for unit testing
+---
+
+# Test Skill
+
+Instructions go here.
+"#;
+        let (fm, body) = parse_skill_md(content).unwrap();
+        assert_eq!(fm.name, "test-skill");
+        assert!(body.contains("# Test Skill"));
+
+        let generated = generate_skill_md(&fm, &body).unwrap();
+        let (reparsed, rebody) = parse_skill_md(&generated).unwrap();
+        assert_eq!(reparsed.name, fm.name);
+        assert_eq!(rebody, body);
+    }
+
+    #[test]
+    fn test_compatibility_forms() {
+        let notes = "---\nname: a\ndescription: d\ncompatibility: Requires git\n---\n";
+        let (fm, _) = parse_skill_md(notes).unwrap();
+        let compat = fm.compatibility.unwrap();
+        assert_eq!(compat, Compatibility::Notes("Requires git".to_string()));
+        assert!(compat.install_warnings(Some("cursor"), "0.1.0").is_empty());
+
+        let structured = "---\nname: a\ndescription: d\ncompatibility:\n  \
+                          agents: [claude-code, cursor]\n  paks: \">=0.2\"\n---\n";
+        let (fm, _) = parse_skill_md(structured).unwrap();
+        let compat = fm.compatibility.unwrap();
+        assert_eq!(
+            compat.to_string(),
+            "agents: claude-code, cursor; paks >=0.2"
+        );
+        assert!(compat.install_warnings(Some("Cursor"), "0.2.1").is_empty());
+        assert!(compat.install_warnings(None, "0.2.0").is_empty());
+
+        let warnings = compat.install_warnings(Some("windsurf"), "0.1.5");
+        assert_eq!(
+            warnings,
+            [
+                "skill supports claude-code, cursor, not windsurf",
+                "skill requires paks >=0.2, this is paks 0.1.5"
+            ]
+        );
+    }
+}
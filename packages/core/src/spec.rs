@@ -0,0 +1,79 @@
+//! Agent Skills spec versions and the rules each one enforces
+//!
+//! Older agents validate against older revisions of the spec, so authors can
+//! pick which rules `paks validate` applies with `--spec-version`.
+
+use crate::error::{Error, Result};
+
+/// How a spec version treats an optional frontmatter field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldStatus {
+    /// Not part of this spec version; agents may ignore or reject it
+    Unsupported,
+    /// Defined, but marked experimental
+    Experimental,
+}
+
+/// Rules of one Agent Skills spec version
+#[derive(Debug, PartialEq, Eq)]
+pub struct SpecRules {
+    pub version: &'static str,
+    /// Maximum description length in characters
+    pub description_max: usize,
+    /// Maximum compatibility length, if the field exists in this version
+    pub compatibility_max: Option<usize>,
+    pub allowed_tools: FieldStatus,
+}
+
+/// Supported spec versions, oldest first; the last entry is the latest
+pub const SPEC_VERSIONS: &[SpecRules] = &[
+    SpecRules {
+        version: "0.9",
+        description_max: 200,
+        compatibility_max: None,
+        allowed_tools: FieldStatus::Unsupported,
+    },
+    SpecRules {
+        version: "1.0",
+        description_max: 1024,
+        compatibility_max: Some(500),
+        allowed_tools: FieldStatus::Experimental,
+    },
+];
+
+/// Rules of the latest spec version
+pub fn latest() -> &'static SpecRules {
+    &SPEC_VERSIONS[SPEC_VERSIONS.len() - 1]
+}
+
+/// Rules for `version`, or the latest spec if `None`
+pub fn rules(version: Option<&str>) -> Result<&'static SpecRules> {
+    let Some(version) = version else {
+        return Ok(latest());
+    };
+    match SPEC_VERSIONS.iter().find(|rules| rules.version == version) {
+        Some(rules) => Ok(rules),
+        None => Err(Error::UnsupportedSpecVersion {
+            version: version.to_string(),
+            supported: SPEC_VERSIONS
+                .iter()
+                .map(|rules| rules.version)
+                .collect::<Vec<_>>()
+                .join(", "),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_lookup() {
+        assert_eq!(rules(None).unwrap(), latest());
+        assert_eq!(rules(Some("0.9")).unwrap().description_max, 200);
+
+        let err = rules(Some("2.0")).unwrap_err().to_string();
+        assert!(err.contains("Supported versions: 0.9, 1.0"));
+    }
+}